@@ -23,3 +23,9 @@ pub const LOCKTIME_THRESHOLD: u32 = 500000000; // Tue Nov  5 00:53:20 1985 UTC
 
 /// Number of Satoshis in single coin
 pub const SATOSHIS_IN_COIN: u64 = 100_000_000;
+
+/// Maximum size of the `OP_RETURN` output payload relayed by standard nodes.
+pub const MAX_OP_RETURN_RELAY: usize = 80;
+
+/// Maximum weight of a single transaction relayed by standard nodes.
+pub const MAX_STANDARD_TX_WEIGHT: usize = 400_000;
@@ -0,0 +1,103 @@
+//! Komodo specific OP_RETURN outputs.
+
+use bytes::Bytes;
+use hash::H256;
+
+const OP_RETURN: u8 = 0x6a;
+const OP_PUSHDATA1: u8 = 0x4c;
+const OP_PUSHDATA2: u8 = 0x4d;
+/// Longest symbol of the Komodo asset chain.
+const MAX_SYMBOL_LEN: usize = 65;
+
+/// Data carried by the `OP_RETURN <payload>` output.
+#[derive(Debug, PartialEq, Clone)]
+pub enum KomodoOpReturn {
+	/// Notarization of the chain `symbol`: `block_hash || height || symbol \0 || extra`.
+	Notarization {
+		block_hash: H256,
+		height: u32,
+		symbol: String,
+		/// Layout specific data following the symbol, e.g. MoM hash and depth.
+		extra: Bytes,
+	},
+	/// Any other payload.
+	Data(Bytes),
+}
+
+/// Parses `OP_RETURN` output script with a single data push.
+/// Returns `None` if script is not a nulldata script.
+pub fn parse_opreturn(script: &[u8]) -> Option<KomodoOpReturn> {
+	if script.first() != Some(&OP_RETURN) {
+		return None;
+	}
+
+	let (offset, len) = match *script.get(1)? {
+		len @ 0x01..=0x4b => (2, len as usize),
+		OP_PUSHDATA1 => (3, *script.get(2)? as usize),
+		OP_PUSHDATA2 => (4, *script.get(2)? as usize | (*script.get(3)? as usize) << 8),
+		_ => return None,
+	};
+
+	if script.len() != offset + len {
+		return None;
+	}
+
+	let payload = &script[offset..];
+	Some(parse_notarization(payload).unwrap_or_else(|| KomodoOpReturn::Data(payload.into())))
+}
+
+fn parse_notarization(payload: &[u8]) -> Option<KomodoOpReturn> {
+	if payload.len() < 32 + 4 + 2 {
+		return None;
+	}
+
+	let symbol_len = payload[36..].iter().take(MAX_SYMBOL_LEN + 1).position(|c| *c == 0)?;
+	let symbol = &payload[36..36 + symbol_len];
+	if symbol.is_empty() || !symbol.iter().all(|c| c.is_ascii_alphanumeric() || *c == b'-' || *c == b'_') {
+		return None;
+	}
+
+	let notarization = KomodoOpReturn::Notarization {
+		block_hash: H256::from(&payload[..32]),
+		height: payload[32] as u32 | (payload[33] as u32) << 8 | (payload[34] as u32) << 16 | (payload[35] as u32) << 24,
+		symbol: String::from_utf8(symbol.to_vec()).ok()?,
+		extra: payload[36 + symbol_len + 1..].into(),
+	};
+
+	Some(notarization)
+}
+
+#[cfg(test)]
+mod tests {
+	use bytes::Bytes;
+	use super::{parse_opreturn, KomodoOpReturn};
+
+	#[test]
+	fn test_parse_opreturn_data() {
+		let script: Bytes = "6a083037646431356334".into();
+		assert_eq!(parse_opreturn(&script), Some(KomodoOpReturn::Data("3037646431356334".into())));
+
+		let p2pkh: Bytes = "76a91473122bcec852f394e51496e39fca5111c3d7ae5688ac".into();
+		assert_eq!(parse_opreturn(&p2pkh), None);
+		let truncated: Bytes = "6a0830376464313563".into();
+		assert_eq!(parse_opreturn(&truncated), None);
+	}
+
+	#[test]
+	fn test_parse_opreturn_notarization() {
+		let script: Bytes = concat!(
+			"6a", "2c",
+			"a3b3c46f8b8d9ce60bc2ba6b84bf5a4e5a87e0a1a4f1b3c70a12349f4b1cd0d4",
+			"10270000",
+			"4b4d4400",
+			"01020304",
+		).into();
+
+		assert_eq!(parse_opreturn(&script), Some(KomodoOpReturn::Notarization {
+			block_hash: "a3b3c46f8b8d9ce60bc2ba6b84bf5a4e5a87e0a1a4f1b3c70a12349f4b1cd0d4".into(),
+			height: 10000,
+			symbol: "KMD".into(),
+			extra: "01020304".into(),
+		}));
+	}
+}
@@ -4,11 +4,16 @@ extern crate bitcrypto as crypto;
 extern crate serialization as ser;
 #[macro_use]
 extern crate serialization_derive;
+#[cfg(feature = "serde")]
+extern crate serde;
 #[cfg(test)]
 #[macro_use]
 extern crate unwrap;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
 
 pub mod constants;
+pub mod komodo;
 
 mod block;
 mod block_header;
@@ -30,7 +35,8 @@ pub use primitives::{hash, bytes, bigint, compact};
 pub use block::Block;
 pub use block_header::BlockHeader;
 pub use merkle_root::{merkle_root, merkle_node_hash};
-pub use transaction::{Transaction, TransactionInput, TransactionOutput, OutPoint, JoinSplit, ShieldedSpend, ShieldedOutput};
+pub use transaction::{Transaction, TransactionInput, TransactionOutput, OutPoint, OutPointParseError, JoinSplit, ShieldedSpend, ShieldedOutput, FeeError, TxError,
+	TransactionIterator};
 
 pub use read_and_hash::{ReadAndHash, HashedData};
 pub use indexed_block::IndexedBlock;
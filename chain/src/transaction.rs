@@ -1,14 +1,17 @@
 //! Bitcoin transaction.
 //! https://en.bitcoin.it/wiki/Protocol_documentation#tx
 
-use std::io;
+use std::{cmp, fmt, io, str};
+use std::collections::HashSet;
 use hex::FromHex;
 use bytes::Bytes;
-use ser::{deserialize, serialize, serialize_with_flags, SERIALIZE_TRANSACTION_WITNESS};
-use crypto::dhash256;
+use ser::{deserialize, serialize, serialize_with_flags, serialized_list_size, serialized_list_size_with_flags,
+	SERIALIZE_TRANSACTION_WITNESS};
+use crypto::{dhash256, sha256};
 use hash::{H64, H256, H512, EncCipherText, OutCipherText, ZkProof, ZkProofSapling, CipherText};
-use constants::{SEQUENCE_FINAL, LOCKTIME_THRESHOLD};
-use ser::{CompactInteger, Error, Serializable, Deserializable, Stream, Reader};
+use constants::{SEQUENCE_FINAL, LOCKTIME_THRESHOLD, SEQUENCE_LOCKTIME_DISABLE_FLAG, SEQUENCE_LOCKTIME_TYPE_FLAG,
+	SEQUENCE_LOCKTIME_MASK, MAX_OP_RETURN_RELAY};
+use ser::{CompactInteger, Error, Serializable, Deserializable, Stream, Reader, MAX_SCRIPT_BYTES};
 use std::io::Read;
 
 /// Must be zero.
@@ -18,7 +21,15 @@ const WITNESS_FLAG: u8 = 1;
 /// Maximum supported list size (inputs, outputs, etc.)
 const MAX_LIST_SIZE: usize = 8192;
 
-#[derive(Debug, PartialEq, Eq, Clone, Default, Serializable, Deserializable)]
+/// `OP_RETURN OP_PUSHBYTES_36` followed by the BIP141 commitment header.
+const WITNESS_COMMITMENT_HEADER: [u8; 6] = [0x6a, 0x24, 0xaa, 0x21, 0xa9, 0xed];
+
+const OP_RETURN: u8 = 0x6a;
+const OP_PUSHDATA1: u8 = 0x4c;
+const OP_PUSHDATA2: u8 = 0x4d;
+const OP_PUSHDATA4: u8 = 0x4e;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default, Serializable, Deserializable)]
 pub struct OutPoint {
 	pub hash: H256,
 	pub index: u32,
@@ -37,6 +48,47 @@ impl OutPoint {
 	}
 }
 
+/// Errors returned when parsing `OutPoint` from "txid:vout" string.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum OutPointParseError {
+	/// String doesn't contain ':' separator.
+	MissingSeparator,
+	/// Txid is not 64 hex characters.
+	InvalidTxid,
+	/// Output index is not a valid u32.
+	InvalidIndex,
+}
+
+/// Formats outpoint as "txid:vout" with txid in the reversed, display byte order.
+impl fmt::Display for OutPoint {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}:{}", self.hash.to_reversed_str(), self.index)
+	}
+}
+
+impl str::FromStr for OutPoint {
+	type Err = OutPointParseError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let separator = s.rfind(':').ok_or(OutPointParseError::MissingSeparator)?;
+		let (txid, index) = (&s[..separator], &s[separator + 1..]);
+		if txid.len() != 64 {
+			return Err(OutPointParseError::InvalidTxid);
+		}
+
+		let hash = H256::from_display_hex(txid).map_err(|_| OutPointParseError::InvalidTxid)?;
+		if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+			return Err(OutPointParseError::InvalidIndex);
+		}
+		let index = index.parse().map_err(|_| OutPointParseError::InvalidIndex)?;
+
+		Ok(OutPoint {
+			hash,
+			index,
+		})
+	}
+}
+
 #[derive(Debug, PartialEq, Default, Clone)]
 pub struct TransactionInput {
 	pub previous_output: OutPoint,
@@ -62,14 +114,153 @@ impl TransactionInput {
 	pub fn has_witness(&self) -> bool {
 		!self.script_witness.is_empty()
 	}
+
+	/// Sighash type of the signature in this input, if any.
+	/// Looks at the first non-empty push of `script_sig` (skipping the multisig dummy)
+	/// or at the first witness element when `script_sig` is empty.
+	pub fn detected_sighash(&self) -> Option<u32> {
+		let signature = if self.script_sig.is_empty() {
+			self.script_witness.first().map(|item| &item[..])
+		} else {
+			first_signature_push(&self.script_sig)
+		};
+
+		match signature {
+			// DER encoded signature followed by a sighash byte
+			Some(signature) if signature.len() > 8 && signature[0] == 0x30 => signature.last().map(|s| *s as u32),
+			_ => None,
+		}
+	}
+
+	/// Input signals opt-in replace-by-fee (BIP125) if its sequence is below `SEQUENCE_FINAL - 1`.
+	pub fn signals_rbf(&self) -> bool {
+		self.sequence < SEQUENCE_FINAL - 1
+	}
+
+	/// Weight contributed by this input: base size * 4 plus the witness size.
+	/// `include_witness` should be true when the transaction is serialized with witness,
+	/// as then every input contributes at least the witness stack length.
+	pub fn weight(&self, include_witness: bool) -> usize {
+		let base_size = self.serialized_size() * 4;
+		if include_witness {
+			base_size + self.witness_size()
+		} else {
+			base_size
+		}
+	}
+
+	/// Size of the serialized witness stack, including its length.
+	/// Empty witness takes one byte, when the transaction is serialized with witness.
+	pub fn witness_size(&self) -> usize {
+		serialized_list_size::<Bytes, Bytes>(&self.script_witness)
+	}
 }
 
-#[derive(Debug, PartialEq, Clone, Serializable, Deserializable)]
+/// Returns the first non-empty data push of the script.
+fn first_signature_push(script: &[u8]) -> Option<&[u8]> {
+	let mut pc = 0;
+	while pc < script.len() {
+		let opcode = script[pc] as usize;
+		let (offset, len) = match opcode {
+			// OP_0
+			0x00 => {
+				pc += 1;
+				continue;
+			},
+			// OP_PUSHBYTES_1 ... OP_PUSHBYTES_75
+			0x01..=0x4b => (pc + 1, opcode),
+			// OP_PUSHDATA1
+			0x4c => (pc + 2, *script.get(pc + 1)? as usize),
+			// OP_PUSHDATA2
+			0x4d => (pc + 3, *script.get(pc + 1)? as usize | (*script.get(pc + 2)? as usize) << 8),
+			_ => return None,
+		};
+
+		return script.get(offset..offset + len);
+	}
+
+	None
+}
+
+#[derive(Debug, PartialEq, Clone, Serializable)]
 pub struct TransactionOutput {
 	pub value: u64,
 	pub script_pubkey: Bytes,
 }
 
+impl TransactionOutput {
+	/// Weight contributed by this output: serialized size * 4.
+	pub fn weight(&self) -> usize {
+		self.serialized_size() * 4
+	}
+
+	/// Is this the null output (`u64::MAX` value and empty script), returned by `Default`?
+	pub fn is_null(&self) -> bool {
+		self.value == u64::MAX && self.script_pubkey.is_empty()
+	}
+
+	/// Creates zero value nulldata output: `OP_RETURN <data>`.
+	pub fn op_return(data: &[u8]) -> TransactionOutput {
+		let mut script = vec![OP_RETURN];
+		match data.len() {
+			0 => (),
+			len @ 1..=0x4b => script.push(len as u8),
+			len @ 0x4c..=0xff => script.extend_from_slice(&[OP_PUSHDATA1, len as u8]),
+			len @ 0x100..=0xffff => {
+				script.push(OP_PUSHDATA2);
+				script.extend_from_slice(&(len as u16).to_le_bytes());
+			},
+			len => {
+				script.push(OP_PUSHDATA4);
+				script.extend_from_slice(&(len as u32).to_le_bytes());
+			},
+		}
+		script.extend_from_slice(data);
+
+		TransactionOutput {
+			value: 0,
+			script_pubkey: script.into(),
+		}
+	}
+
+	/// Payload of the nulldata output, `None` if the script is not `OP_RETURN` followed by a single push.
+	pub fn op_return_data(&self) -> Option<Bytes> {
+		let script = &self.script_pubkey;
+		if script.first() != Some(&OP_RETURN) {
+			return None;
+		}
+
+		if script.len() == 1 {
+			return Some(Bytes::new());
+		}
+
+		let (offset, len) = match script[1] {
+			len @ 0x00..=0x4b => (2, len as usize),
+			OP_PUSHDATA1 => (3, *script.get(2)? as usize),
+			OP_PUSHDATA2 => (4, *script.get(2)? as usize | (*script.get(3)? as usize) << 8),
+			OP_PUSHDATA4 => {
+				let mut len = [0u8; 4];
+				len.copy_from_slice(script.get(2..6)?);
+				(6, u32::from_le_bytes(len) as usize)
+			},
+			_ => return None,
+		};
+
+		if script.len() != offset + len {
+			return None;
+		}
+
+		Some(script[offset..].into())
+	}
+
+	/// Returns true if the output is nulldata with payload not larger than `MAX_OP_RETURN_RELAY`.
+	pub fn is_standard_op_return(&self) -> bool {
+		self.op_return_data().is_some_and(|data| data.len() <= MAX_OP_RETURN_RELAY)
+	}
+}
+
+/// Null output, used as a placeholder e.g. by SIGHASH_SINGLE signature hashing,
+/// which replaces outputs preceding the signed one with it.
 impl Default for TransactionOutput {
 	fn default() -> Self {
 		TransactionOutput {
@@ -128,6 +319,41 @@ pub struct JoinSplit {
 	pub ciphertexts: [CipherText; 2],
 }
 
+impl JoinSplit {
+	/// Creates joinsplit description. Public values are stored as little-endian `u64`, as they are serialized.
+	#[allow(clippy::too_many_arguments)]
+	pub fn new(
+		v_pub_old: u64,
+		v_pub_new: u64,
+		anchor: H256,
+		nullifiers: [H256; 2],
+		commitments: [H256; 2],
+		ephemeral_key: H256,
+		random_seed: H256,
+		macs: [H256; 2],
+		zkproof: JoinSplitProof,
+		ciphertexts: [CipherText; 2],
+	) -> Self {
+		JoinSplit {
+			v_pub_old: v_pub_old.to_le_bytes().into(),
+			v_pub_new: v_pub_new.to_le_bytes().into(),
+			anchor,
+			nullifiers,
+			commitments,
+			ephemeral_key,
+			random_seed,
+			macs,
+			zkproof,
+			ciphertexts,
+		}
+	}
+
+	/// Returns false if both `v_pub_old` and `v_pub_new` are nonzero, which is rejected by consensus.
+	pub fn is_consistent(&self) -> bool {
+		self.v_pub_old.is_zero() || self.v_pub_new.is_zero()
+	}
+}
+
 // TODO Make it more optimal later by adding fixed-size array support to serialization_derive crate
 impl Serializable for JoinSplit {
 	fn serialize(&self, stream: &mut Stream) {
@@ -182,6 +408,8 @@ pub struct Transaction {
 	pub shielded_spends: Vec<ShieldedSpend>,
 	pub shielded_outputs: Vec<ShieldedOutput>,
 	pub join_splits: Vec<JoinSplit>,
+	/// Net value of Sapling spends minus outputs. Signed amount stored as its two's complement,
+	/// use `shielded_value_balance` to read it.
 	pub value_balance: u64,
 	pub join_split_pubkey: H256,
 	pub join_split_sig: H512,
@@ -193,19 +421,127 @@ pub struct Transaction {
 
 impl From<&'static str> for Transaction {
 	fn from(s: &'static str) -> Self {
-		deserialize(&s.from_hex::<Vec<u8>>().unwrap() as &[u8]).unwrap()
+		Transaction::from_hex(s).unwrap()
 	}
 }
 
 impl Transaction {
+	/// Parses hex encoded transaction. Fails with `Error::MalformedData` if the string is not valid hex.
+	pub fn from_hex(s: &str) -> Result<Transaction, Error> {
+		let bytes: Vec<u8> = s.from_hex().map_err(|_| Error::MalformedData)?;
+		Transaction::from_bytes(&bytes)
+	}
+
+	/// Parses serialized transaction, which must take the whole slice.
+	pub fn from_bytes(bytes: &[u8]) -> Result<Transaction, Error> {
+		deserialize(bytes)
+	}
+
 	pub fn hash(&self) -> H256 {
 		dhash256(&serialize(self))
 	}
 
+	/// Groestlcoin transaction id: single sha256 of the transaction serialized without witness data.
+	/// Groestlcoin uses groestl512 only for block header hashes and base58 checksums.
+	pub fn hash_groestl(&self) -> H256 {
+		sha256(&serialize(self))
+	}
+
 	pub fn witness_hash(&self) -> H256 {
 		dhash256(&serialize_with_flags(self, SERIALIZE_TRANSACTION_WITNESS))
 	}
 
+	/// Transaction id: double sha256 of the transaction serialized without witness data.
+	/// Stays the same when the witness of any input is changed.
+	pub fn txid(&self) -> H256 {
+		self.hash()
+	}
+
+	/// Witness transaction id: double sha256 of the transaction serialized with witness data.
+	/// Equals to `txid` for transactions without witness.
+	pub fn wtxid(&self) -> H256 {
+		self.witness_hash()
+	}
+
+	/// Transaction id in the byte-reversed hex form used by explorers and RPC.
+	pub fn txid_string(&self) -> String {
+		self.txid().to_reversed_str()
+	}
+
+	/// Witness transaction id in the byte-reversed hex form used by explorers and RPC.
+	pub fn wtxid_string(&self) -> String {
+		self.wtxid().to_reversed_str()
+	}
+
+	/// Size of the transaction serialized without witness data.
+	pub fn base_size(&self) -> usize {
+		self.serialized_size()
+	}
+
+	/// Size added by serializing the transaction with witness data: witness marker and flag
+	/// followed by witnesses of all inputs. Zero if the transaction has no witness.
+	pub fn witness_size(&self) -> usize {
+		self.serialized_size_with_flags(SERIALIZE_TRANSACTION_WITNESS) - self.base_size()
+	}
+
+	/// Transaction weight as defined by BIP141: `base_size * 3 + total_size`.
+	pub fn weight(&self) -> usize {
+		self.base_size() * 4 + self.witness_size()
+	}
+
+	/// Serializes the transaction, failing with `Error::TooLarge` before
+	/// allocating the buffer if the serialized size exceeds `max_bytes`.
+	pub fn serialize_bounded(&self, max_bytes: usize) -> Result<Bytes, Error> {
+		if self.serialized_size() > max_bytes {
+			return Err(Error::TooLarge);
+		}
+
+		Ok(serialize(self))
+	}
+
+	/// Deserializes transaction from the beginning of `data`.
+	/// Returns the transaction and the number of trailing bytes which are not part of it,
+	/// e.g. coin-specific auxpow data, so callers can decide how to handle them.
+	/// As the trailing bytes may be anything, Zcash join splits are read only from overwintered
+	/// transactions and PoS `str_d_zeel` is never read.
+	pub fn deserialize_with_remaining(data: &[u8]) -> Result<(Transaction, usize), Error> {
+		deserialize_tx_candidates(data, TxBoundary::Unknown)
+	}
+
+	/// Lazily reads up to `count` consecutive transactions from `data`, one per `next()` call.
+	/// Every transaction is read as by `deserialize_with_remaining`, so the following one
+	/// is never taken for its Zcash fields. Iteration stops after the first error.
+	pub fn read_many<'a>(data: &'a [u8], count: usize) -> TransactionIterator<'a> {
+		TransactionIterator {
+			data,
+			count,
+		}
+	}
+
+	/// Virtual transaction size: weight divided by 4, rounded up.
+	/// Equals to the serialized size for transactions without witness.
+	pub fn vsize(&self) -> usize {
+		self.weight().div_ceil(4)
+	}
+
+	/// Returns true if the fee pays at least `min_relay_fee_per_kb` per 1000 virtual bytes,
+	/// so the transaction is relayed by the mempool policy.
+	pub fn meets_min_relay_fee(&self, fee: u64, min_relay_fee_per_kb: u64) -> bool {
+		let min_fee = (self.vsize() as u64).saturating_mul(min_relay_fee_per_kb) / 1000;
+		fee >= min_fee
+	}
+
+	/// Checks that the transaction weight doesn't exceed `max_weight`,
+	/// e.g. `MAX_STANDARD_TX_WEIGHT` for relay policy.
+	pub fn check_weight_limit(&self, max_weight: usize) -> Result<(), TxError> {
+		let weight = self.weight();
+		if weight > max_weight {
+			return Err(TxError::Overweight(weight));
+		}
+
+		Ok(())
+	}
+
 	pub fn inputs(&self) -> &[TransactionInput] {
 		&self.inputs
 	}
@@ -214,8 +550,30 @@ impl Transaction {
 		&self.outputs
 	}
 
+	/// Returns true if any two inputs spend the same outpoint.
+	pub fn has_duplicate_inputs(&self) -> bool {
+		let mut outpoints = HashSet::with_capacity(self.inputs.len());
+		!self.inputs.iter().all(|input| outpoints.insert(&input.previous_output))
+	}
+
+	/// Returns true if the transaction has no inputs or no outputs.
+	/// Shielded spends and outputs and join splits of Zcash transactions are counted as well,
+	/// so shielded-only transactions are not empty.
 	pub fn is_empty(&self) -> bool {
-		self.inputs.is_empty() || self.outputs.is_empty()
+		let no_inputs = self.inputs.is_empty() && self.shielded_spends.is_empty() && self.join_splits.is_empty();
+		let no_outputs = self.outputs.is_empty() && self.shielded_outputs.is_empty() && self.join_splits.is_empty();
+		no_inputs || no_outputs
+	}
+
+	/// Returns true if the transaction has any shielded spends, shielded outputs or join splits.
+	pub fn is_shielded(&self) -> bool {
+		!self.shielded_spends.is_empty() || !self.shielded_outputs.is_empty() || !self.join_splits.is_empty()
+	}
+
+	/// Returns Sapling value balance as a signed amount. Positive balance moves value
+	/// from the shielded pool to transparent outputs, negative balance moves it into the pool.
+	pub fn shielded_value_balance(&self) -> i64 {
+		self.value_balance as i64
 	}
 
 	pub fn is_null(&self) -> bool {
@@ -226,6 +584,50 @@ impl Transaction {
 		self.inputs.len() == 1 && self.inputs[0].previous_output.is_null()
 	}
 
+	/// Returns witness commitment of the coinbase: the last output with `OP_RETURN OP_PUSHBYTES_36 aa21a9ed <32 bytes>`
+	/// script, as defined by BIP141. Returns `None` if the transaction is not a coinbase or has no commitment.
+	pub fn witness_commitment(&self) -> Option<H256> {
+		if !self.is_coinbase() {
+			return None;
+		}
+
+		self.outputs.iter().rev()
+			.map(|output| &output.script_pubkey)
+			.find(|script| script.len() >= 38 && script[..6] == WITNESS_COMMITMENT_HEADER)
+			.map(|script| H256::from(&script[6..38]))
+	}
+
+	/// Returns block height pushed at the beginning of the coinbase script sig, as required by BIP34.
+	/// Returns `None` if the transaction is not a coinbase or the push is malformed.
+	pub fn coinbase_height(&self) -> Option<u32> {
+		if !self.is_coinbase() {
+			return None;
+		}
+
+		let script_sig = &self.inputs[0].script_sig;
+		match script_sig.first().cloned() {
+			// OP_0
+			Some(0) => Some(0),
+			// OP_1 .. OP_16
+			Some(opcode @ 0x51..=0x60) => Some(u32::from(opcode) - 0x50),
+			Some(len @ 1..=4) => {
+				let len = len as usize;
+				if script_sig.len() < len + 1 {
+					return None;
+				}
+
+				let data = &script_sig[1..len + 1];
+				// script numbers are signed, negative height is invalid
+				if data[len - 1] & 0x80 != 0 {
+					return None;
+				}
+
+				Some(data.iter().rev().fold(0u32, |height, byte| (height << 8) | u32::from(*byte)))
+			},
+			_ => None,
+		}
+	}
+
 	pub fn is_final(&self) -> bool {
 		// if lock_time is 0, transaction is final
 		if self.lock_time == 0 {
@@ -236,6 +638,11 @@ impl Transaction {
 		self.inputs.iter().all(TransactionInput::is_final)
 	}
 
+	/// Transaction is replaceable (BIP125) if any of its inputs signals replace-by-fee.
+	pub fn is_rbf_signaling(&self) -> bool {
+		self.inputs.iter().any(TransactionInput::signals_rbf)
+	}
+
 	pub fn is_final_in_block(&self, block_height: u32, block_time: u32) -> bool {
 		if self.lock_time == 0 {
 			return true;
@@ -254,10 +661,41 @@ impl Transaction {
 		self.inputs.iter().all(TransactionInput::is_final)
 	}
 
+	/// Checks BIP68 relative lock-times of all inputs.
+	///
+	/// `input_heights[i]` is the height of the block the i-th input was confirmed in and
+	/// `input_times[i]` is the median time past of the block preceding it.
+	/// `spend_height` and `spend_mtp` are the height of the spending block and the median
+	/// time past of its parent. Returns false if any input is not mature yet.
+	pub fn relative_locktime_satisfied(&self, input_heights: &[u32], input_times: &[u32], spend_height: u32, spend_mtp: u32) -> bool {
+		if input_heights.len() != self.inputs.len() || input_times.len() != self.inputs.len() {
+			return false;
+		}
+
+		// relative lock-times are only enforced for transactions of version 2 and above
+		if self.version < 2 {
+			return true;
+		}
+
+		self.inputs.iter().enumerate().all(|(index, input)| {
+			if input.sequence & SEQUENCE_LOCKTIME_DISABLE_FLAG != 0 {
+				return true;
+			}
+
+			let lock = input.sequence & SEQUENCE_LOCKTIME_MASK;
+			if input.sequence & SEQUENCE_LOCKTIME_TYPE_FLAG != 0 {
+				(spend_mtp as u64) >= input_times[index] as u64 + ((lock as u64) << 9)
+			} else {
+				(spend_height as u64) >= input_heights[index] as u64 + lock as u64
+			}
+		})
+	}
+
 	pub fn has_witness(&self) -> bool {
 		self.inputs.iter().any(TransactionInput::has_witness)
 	}
 
+	/// Sum of all output values. Saturates at `u64::max_value()` instead of overflowing.
 	pub fn total_spends(&self) -> u64 {
 		let mut result = 0u64;
 		for output in self.outputs.iter() {
@@ -268,6 +706,72 @@ impl Transaction {
 		}
 		result
 	}
+
+	/// Fee paid by the transaction, given the values of outputs spent by its inputs
+	/// in the same order as `inputs`.
+	pub fn fee(&self, input_amounts: &[u64]) -> Result<u64, FeeError> {
+		if input_amounts.len() != self.inputs.len() {
+			return Err(FeeError::InputAmountsMismatch);
+		}
+
+		let total_inputs = input_amounts.iter()
+			.try_fold(0u64, |acc, amount| acc.checked_add(*amount))
+			.ok_or(FeeError::InputAmountsOverflow)?;
+
+		let total_outputs = self.outputs.iter()
+			.try_fold(0u64, |acc, output| acc.checked_add(output.value))
+			.ok_or(FeeError::OutputsOverflow)?;
+
+		total_inputs.checked_sub(total_outputs).ok_or(FeeError::InsufficientInputs)
+	}
+}
+
+/// Errors returned by `Transaction::fee`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum FeeError {
+	/// Number of input amounts differs from number of inputs.
+	InputAmountsMismatch,
+	/// Sum of input amounts overflows.
+	InputAmountsOverflow,
+	/// Sum of output values overflows.
+	OutputsOverflow,
+	/// Inputs don't cover the outputs.
+	InsufficientInputs,
+}
+
+/// Errors returned by transaction limit checks.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TxError {
+	/// Transaction weight exceeds the limit, contains the actual weight.
+	Overweight(usize),
+}
+
+/// Iterator over consecutive transactions, see `Transaction::read_many`.
+pub struct TransactionIterator<'a> {
+	data: &'a [u8],
+	count: usize,
+}
+
+impl<'a> Iterator for TransactionIterator<'a> {
+	type Item = Result<Transaction, Error>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.count == 0 {
+			return None;
+		}
+
+		match Transaction::deserialize_with_remaining(self.data) {
+			Ok((transaction, remaining)) => {
+				self.data = &self.data[self.data.len() - remaining..];
+				self.count -= 1;
+				Some(Ok(transaction))
+			},
+			Err(e) => {
+				self.count = 0;
+				Some(Err(e))
+			},
+		}
+	}
 }
 
 impl Serializable for TransactionInput {
@@ -277,19 +781,34 @@ impl Serializable for TransactionInput {
 			.append(&self.script_sig)
 			.append(&self.sequence);
 	}
+
+	fn serialized_size(&self) -> usize {
+		self.previous_output.serialized_size() +
+			self.script_sig.serialized_size() +
+			self.sequence.serialized_size()
+	}
 }
 
 impl Deserializable for TransactionInput {
 	fn deserialize<T>(reader: &mut Reader<T>) -> Result<Self, Error> where Self: Sized, T: io::Read {
 		Ok(TransactionInput {
 			previous_output: reader.read()?,
-			script_sig: reader.read()?,
+			script_sig: reader.read_bytes_max(MAX_SCRIPT_BYTES)?,
 			sequence: reader.read()?,
 			script_witness: vec![],
 		})
 	}
 }
 
+impl Deserializable for TransactionOutput {
+	fn deserialize<T>(reader: &mut Reader<T>) -> Result<Self, Error> where Self: Sized, T: io::Read {
+		Ok(TransactionOutput {
+			value: reader.read()?,
+			script_pubkey: reader.read_bytes_max(MAX_SCRIPT_BYTES)?,
+		})
+	}
+}
+
 impl Serializable for Transaction {
 	fn serialize(&self, stream: &mut Stream) {
 		let include_transaction_witness = stream.include_transaction_witness() && self.has_witness();
@@ -324,14 +843,14 @@ impl Serializable for Transaction {
 				if self.zcash {
 					if self.version == 2 || self.overwintered {
 						stream.append_list(&self.join_splits);
-						if self.join_splits.len() > 0 {
+						if !self.join_splits.is_empty() {
 							stream.append(&self.join_split_pubkey)
 								.append(&self.join_split_sig);
 						}
 					}
 
 					if self.version >= 4 && self.overwintered &&
-						!(self.shielded_outputs.len() == 0 && self.shielded_spends.len() == 0)
+						!(self.shielded_outputs.is_empty() && self.shielded_spends.is_empty())
 					{
 						stream.append(&self.binding_sig);
 					}
@@ -359,16 +878,102 @@ impl Serializable for Transaction {
 			}
 		};
 	}
+
+	fn serialized_size(&self) -> usize {
+		self.serialized_size_with_flags(0)
+	}
+
+	fn serialized_size_with_flags(&self, flags: u32) -> usize {
+		let include_transaction_witness = (flags & SERIALIZE_TRANSACTION_WITNESS) != 0 && self.has_witness();
+		if include_transaction_witness {
+			return self.version.serialized_size() +
+				WITNESS_MARKER.serialized_size() +
+				WITNESS_FLAG.serialized_size() +
+				serialized_list_size::<TransactionInput, TransactionInput>(&self.inputs) +
+				serialized_list_size::<TransactionOutput, TransactionOutput>(&self.outputs) +
+				self.inputs.iter().map(|input| serialized_list_size::<Bytes, Bytes>(&input.script_witness)).sum::<usize>() +
+				self.lock_time.serialized_size();
+		}
+
+		let mut size = self.version.serialized_size();
+		if self.overwintered {
+			size += self.version_group_id.serialized_size();
+		}
+
+		if let Some(n_time) = self.n_time {
+			size += n_time.serialized_size();
+		}
+
+		size += serialized_list_size_with_flags::<TransactionInput, TransactionInput>(&self.inputs, flags) +
+			serialized_list_size_with_flags::<TransactionOutput, TransactionOutput>(&self.outputs, flags) +
+			self.lock_time.serialized_size();
+
+		if self.overwintered && self.version >= 3 {
+			size += self.expiry_height.serialized_size();
+			if self.version >= 4 {
+				size += self.value_balance.serialized_size() +
+					serialized_list_size::<ShieldedSpend, ShieldedSpend>(&self.shielded_spends) +
+					serialized_list_size::<ShieldedOutput, ShieldedOutput>(&self.shielded_outputs);
+			}
+		}
+
+		if self.zcash {
+			if self.version == 2 || self.overwintered {
+				size += serialized_list_size::<JoinSplit, JoinSplit>(&self.join_splits);
+				if !self.join_splits.is_empty() {
+					size += self.join_split_pubkey.serialized_size() + self.join_split_sig.serialized_size();
+				}
+			}
+
+			if self.version >= 4 && self.overwintered &&
+				!(self.shielded_outputs.is_empty() && self.shielded_spends.is_empty())
+			{
+				size += self.binding_sig.serialized_size();
+			}
+		}
+
+		if let Some(ref string) = self.str_d_zeel {
+			size += CompactInteger::from(string.len()).serialized_size() + string.len();
+		}
+
+		size
+	}
 }
 
-#[derive(Eq, PartialEq)]
+#[derive(Eq, PartialEq, Clone, Copy)]
 enum TxType {
     StandardWithWitness,
     Zcash,
     PosWithNTime,
 }
 
-fn deserialize_tx<T>(reader: &mut Reader<T>, tx_type: TxType) -> Result<Transaction, Error> where T: io::Read {
+/// Tells whether fields following the lock time may be detected by the end of data.
+#[derive(Eq, PartialEq, Clone, Copy)]
+enum TxBoundary {
+	/// Transaction takes all the data, so Zcash join splits or PoS `str_d_zeel`
+	/// are present if and only if there are bytes left after the lock time.
+	EndOfData,
+	/// Unrelated data may follow the transaction, so only its type and header tell which fields are present.
+	Unknown,
+}
+
+/// Tries to deserialize transaction of every known type, returns the first one parsed
+/// and the number of bytes left after it.
+fn deserialize_tx_candidates(data: &[u8], boundary: TxBoundary) -> Result<(Transaction, usize), Error> {
+	let mut last_error = Error::MalformedData;
+	// every list element takes at least 1 byte, so longer lists are malformed
+	let max_list_size = cmp::min(MAX_LIST_SIZE, data.len());
+	for tx_type in &[TxType::StandardWithWitness, TxType::PosWithNTime, TxType::Zcash] {
+		let mut reader = Reader::new(data);
+		match deserialize_tx(&mut reader, *tx_type, boundary, max_list_size) {
+			Ok(t) => return Ok((t, reader.remaining_bytes())),
+			Err(e) => last_error = e,
+		}
+	}
+	Err(last_error)
+}
+
+fn deserialize_tx<T>(reader: &mut Reader<T>, tx_type: TxType, boundary: TxBoundary, max_list_size: usize) -> Result<Transaction, Error> where T: io::Read {
 	let header: i32 = reader.read()?;
 	let overwintered: bool = (header >> 31) != 0;
 	let version = if overwintered {
@@ -387,22 +992,27 @@ fn deserialize_tx<T>(reader: &mut Reader<T>, tx_type: TxType) -> Result<Transact
 	} else {
 		None
 	};
-	let mut inputs: Vec<TransactionInput> = reader.read_list_max(MAX_LIST_SIZE)?;
+	let mut inputs: Vec<TransactionInput> = reader.read_list_max(max_list_size)?;
 	let read_witness = if inputs.is_empty() && !overwintered && tx_type == TxType::StandardWithWitness {
 		let witness_flag: u8 = reader.read()?;
 		if witness_flag != WITNESS_FLAG {
 			return Err(Error::MalformedData);
 		}
 
-		inputs = reader.read_list_max(MAX_LIST_SIZE)?;
+		inputs = reader.read_list_max(max_list_size)?;
+		// segwit transaction must have inputs, otherwise the stream is ambiguous
+		if inputs.is_empty() {
+			return Err(Error::MalformedData);
+		}
+
 		true
 	} else {
 		false
 	};
-	let outputs = reader.read_list_max(MAX_LIST_SIZE)?;
+	let outputs = reader.read_list_max(max_list_size)?;
 	if read_witness {
 		for input in inputs.iter_mut() {
-			input.script_witness = reader.read_list_max(MAX_LIST_SIZE)?;
+			input.script_witness = reader.read_list_max(max_list_size)?;
 		}
 	}
 
@@ -416,8 +1026,8 @@ fn deserialize_tx<T>(reader: &mut Reader<T>, tx_type: TxType) -> Result<Transact
 		expiry_height = reader.read()?;
 		if version >= 4 {
 			value_balance = reader.read()?;
-			shielded_spends = reader.read_list_max(MAX_LIST_SIZE)?;
-			shielded_outputs = reader.read_list_max(MAX_LIST_SIZE)?;
+			shielded_spends = reader.read_list_max(max_list_size)?;
+			shielded_outputs = reader.read_list_max(max_list_size)?;
 		}
 	}
 
@@ -428,12 +1038,14 @@ fn deserialize_tx<T>(reader: &mut Reader<T>, tx_type: TxType) -> Result<Transact
 	// Non-Zcash transactions usually end here
 	// But when overwintered is true then we have Zcash transaction for sure.
 	// If reader is already finished when overwintered we should just error
-	let zcash = !reader.is_finished() || overwintered || tx_type == TxType::Zcash;
+	// PoS transactions may end with str_d_zeel instead
+	let has_more_fields = boundary == TxBoundary::EndOfData && !reader.is_finished();
+	let zcash = tx_type != TxType::PosWithNTime && (has_more_fields || overwintered || tx_type == TxType::Zcash);
 	if zcash {
 		if version == 2 || overwintered {
             let len: usize = reader.read::<CompactInteger>()?.into();
             if len > 0 {
-                if len > MAX_LIST_SIZE { return Err(Error::MalformedData) };
+                if len > max_list_size { return Err(Error::MalformedData) };
                 let use_groth = version > 2;
                 for _ in 0..len {
                     join_splits.push(deserialize_join_split(reader, use_groth)?);
@@ -448,13 +1060,8 @@ fn deserialize_tx<T>(reader: &mut Reader<T>, tx_type: TxType) -> Result<Transact
 		}
 	};
 
-    let str_d_zeel = if tx_type == TxType::PosWithNTime && !reader.is_finished() {
-        use hex::ToHex;
-        let len: CompactInteger = reader.read()?;
-        let mut buf = vec![0; len.into()];
-        reader.read_slice(&mut buf)?;
-        let string = std::str::from_utf8(&buf).map_err(|_| Error::MalformedData)?;
-        Some(string.into())
+    let str_d_zeel = if tx_type == TxType::PosWithNTime && has_more_fields {
+        Some(reader.read_string_max(MAX_SCRIPT_BYTES)?)
     } else {
         None
     };
@@ -488,29 +1095,66 @@ impl Deserializable for Transaction {
 		// specific use case
 		let mut buffer = vec![];
 		reader.read_to_end(&mut buffer)?;
-		if let Ok(t) = deserialize_tx(&mut Reader::from_read(buffer.as_slice()), TxType::StandardWithWitness) {
-			return Ok(t);
+		deserialize_tx_candidates(&buffer, TxBoundary::EndOfData).map(|(t, _)| t)
+	}
+}
+
+/// Serde support: transaction is represented by the hex of its serialization, including witness.
+#[cfg(feature = "serde")]
+mod serde_hex {
+	use std::fmt;
+	use hex::{FromHex, ToHex};
+	use serde::{Serialize, Serializer, Deserialize, Deserializer};
+	use serde::de::{Error, Visitor};
+	use ser::{deserialize, serialize_with_flags, SERIALIZE_TRANSACTION_WITNESS};
+	use super::Transaction;
+
+	impl Serialize for Transaction {
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+			let serialized = serialize_with_flags(self, SERIALIZE_TRANSACTION_WITNESS);
+			serializer.serialize_str(&serialized.to_hex::<String>())
+		}
+	}
+
+	impl<'a> Deserialize<'a> for Transaction {
+		fn deserialize<D>(deserializer: D) -> Result<Transaction, D::Error> where D: Deserializer<'a> {
+			deserializer.deserialize_str(TransactionVisitor)
+		}
+	}
+
+	struct TransactionVisitor;
+
+	impl<'a> Visitor<'a> for TransactionVisitor {
+		type Value = Transaction;
+
+		fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+			formatter.write_str("a hex encoded transaction")
 		}
-        if let Ok(t) = deserialize_tx(&mut Reader::from_read(buffer.as_slice()), TxType::PosWithNTime) {
-			return Ok(t);
+
+		fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> where E: Error {
+			let bytes: Vec<u8> = value.from_hex().map_err(|_| E::custom("invalid hex"))?;
+			deserialize(&bytes as &[u8]).map_err(|e| E::custom(format!("invalid transaction: {:?}", e)))
 		}
-        deserialize_tx(&mut Reader::from_read(buffer.as_slice()), TxType::Zcash)
 	}
 }
 
 #[cfg(test)]
 mod tests {
 	use hash::{H256, H512};
-	use ser::{Serializable, serialize, deserialize, serialize_with_flags, SERIALIZE_TRANSACTION_WITNESS};
-	use super::{Transaction, TransactionInput, OutPoint, TransactionOutput, Bytes};
+	use ser::{Error, Reader, Serializable, serialize, deserialize, serialize_with_flags, SERIALIZE_TRANSACTION_WITNESS};
+	use super::{Transaction, TransactionInput, OutPoint, OutPointParseError, TransactionOutput, Bytes, FeeError, TxError, TxType, TxBoundary, deserialize_tx,
+		JoinSplit, JoinSplitProof};
+	use constants::{SEQUENCE_LOCKTIME_DISABLE_FLAG, SEQUENCE_LOCKTIME_TYPE_FLAG, MAX_STANDARD_TX_WEIGHT};
 	use hex::ToHex;
 
 	// real transaction from block 80000
 	// https://blockchain.info/rawtx/5a4ebf66822b0b2d56bd9dc64ece0bc38ee7844a23ff1d7320a88c5fdb2ad3e2
 	// https://blockchain.info/rawtx/5a4ebf66822b0b2d56bd9dc64ece0bc38ee7844a23ff1d7320a88c5fdb2ad3e2?format=hex
+	const BLOCK_80000_TX: &str = "0100000001a6b97044d03da79c005b20ea9c0e1a6d9dc12d9f7b91a5911c9030a439eed8f5000000004948304502206e21798a42fae0e854281abd38bacd1aeed3ee3738d9e1446618c4571d1090db022100e2ac980643b0b82c0e88ffdfec6b64e3e6ba35e7ba5fdd7d5d6cc8d25c6b241501ffffffff0100f2052a010000001976a914404371705fa9bd789a2fcd52d2c580b65d35549d88ac00000000";
+
 	#[test]
 	fn test_transaction_reader() {
-		let t: Transaction = "0100000001a6b97044d03da79c005b20ea9c0e1a6d9dc12d9f7b91a5911c9030a439eed8f5000000004948304502206e21798a42fae0e854281abd38bacd1aeed3ee3738d9e1446618c4571d1090db022100e2ac980643b0b82c0e88ffdfec6b64e3e6ba35e7ba5fdd7d5d6cc8d25c6b241501ffffffff0100f2052a010000001976a914404371705fa9bd789a2fcd52d2c580b65d35549d88ac00000000".into();
+		let t: Transaction = BLOCK_80000_TX.into();
 		assert_eq!(t.version, 1);
 		assert_eq!(t.lock_time, 0);
 		assert_eq!(t.inputs.len(), 1);
@@ -522,6 +1166,297 @@ mod tests {
 		assert_eq!(tx_output.value, 5000000000);
 		assert_eq!(tx_output.script_pubkey, "76a914404371705fa9bd789a2fcd52d2c580b65d35549d88ac".into());
 		assert!(!t.has_witness());
+		assert_eq!(t.txid_string(), "5a4ebf66822b0b2d56bd9dc64ece0bc38ee7844a23ff1d7320a88c5fdb2ad3e2");
+		assert_eq!(t.wtxid_string(), t.txid_string());
+	}
+
+	#[test]
+	fn test_outpoint_display_from_str() {
+		// spent by the transaction from block 80000
+		let t: Transaction = BLOCK_80000_TX.into();
+		let outpoint = &t.inputs[0].previous_output;
+		assert_eq!(outpoint.to_string(), "f5d8ee39a430901c91a5917b9f2dc19d6d1a0e9cea205b009ca73dd04470b9a6:0");
+		assert_eq!(&outpoint.to_string().parse::<OutPoint>().unwrap(), outpoint);
+
+		let outpoint = OutPoint { hash: t.hash(), index: u32::MAX };
+		assert_eq!(outpoint.to_string(), "5a4ebf66822b0b2d56bd9dc64ece0bc38ee7844a23ff1d7320a88c5fdb2ad3e2:4294967295");
+		assert_eq!(outpoint.to_string().parse::<OutPoint>(), Ok(outpoint));
+
+		let txid = "5a4ebf66822b0b2d56bd9dc64ece0bc38ee7844a23ff1d7320a88c5fdb2ad3e2";
+		assert_eq!(txid.parse::<OutPoint>(), Err(OutPointParseError::MissingSeparator));
+		assert_eq!(format!("{}:", txid).parse::<OutPoint>(), Err(OutPointParseError::InvalidIndex));
+		assert_eq!(format!("{}:-1", txid).parse::<OutPoint>(), Err(OutPointParseError::InvalidIndex));
+		assert_eq!(format!("{}:+1", txid).parse::<OutPoint>(), Err(OutPointParseError::InvalidIndex));
+		assert_eq!(format!("{}:4294967296", txid).parse::<OutPoint>(), Err(OutPointParseError::InvalidIndex));
+		assert_eq!(format!("{}:0", &txid[2..]).parse::<OutPoint>(), Err(OutPointParseError::InvalidTxid));
+		assert_eq!(format!("{}00:0", txid).parse::<OutPoint>(), Err(OutPointParseError::InvalidTxid));
+		assert_eq!(format!("{}zz:0", &txid[2..]).parse::<OutPoint>(), Err(OutPointParseError::InvalidTxid));
+	}
+
+	#[test]
+	fn test_transaction_reader_trailing_bytes() {
+		let raw = BLOCK_80000_TX;
+		let expected: Transaction = raw.into();
+		let mut with_trailing = Bytes::from(raw).take();
+		with_trailing.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+
+		let (t, remaining) = Transaction::deserialize_with_remaining(&with_trailing).unwrap();
+		assert_eq!(remaining, 4);
+		assert_eq!(t, expected);
+		assert!(!t.zcash);
+		assert_eq!(&with_trailing[with_trailing.len() - remaining..], &[0xde, 0xad, 0xbe, 0xef]);
+
+		let (_, remaining) = Transaction::deserialize_with_remaining(&Bytes::from(raw)).unwrap();
+		assert_eq!(remaining, 0);
+
+		let mut reader = Reader::new(&with_trailing);
+		reader.read_slice(&mut [0u8; 4]).unwrap();
+		assert_eq!(reader.remaining_bytes(), with_trailing.len() - 4);
+		assert!(!reader.is_finished());
+		assert_eq!(reader.remaining_bytes(), with_trailing.len() - 4);
+	}
+
+	#[test]
+	fn test_transaction_reader_trailing_bytes_version_2() {
+		// trailing bytes must not be taken for Zcash join splits
+		let raw = format!("02{}", &BLOCK_80000_TX[2..]);
+		let expected: Transaction = raw.parse::<Bytes>().map(|bytes| deserialize(bytes.as_ref()).unwrap()).unwrap();
+		assert_eq!(expected.version, 2);
+		assert!(!expected.zcash);
+
+		for trailing in &["deadbeef", "01", "00"] {
+			let with_trailing: Bytes = format!("{}{}", raw, trailing).parse().unwrap();
+			let (t, remaining) = Transaction::deserialize_with_remaining(&with_trailing).unwrap();
+			assert_eq!(remaining, trailing.len() / 2);
+			assert_eq!(t, expected);
+		}
+
+		// overwintered transactions are still read with all Zcash fields
+		let sapling: Bytes = format!("{}deadbeef", ZIP243_SAPLING_TX).parse().unwrap();
+		let (t, remaining) = Transaction::deserialize_with_remaining(&sapling).unwrap();
+		assert_eq!(remaining, 4);
+		assert_eq!(t, ZIP243_SAPLING_TX.into());
+		assert!(t.zcash);
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn test_transaction_serde_hex() {
+		use serde_json;
+
+		let segwit = "01000000000102fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f00000000494830450221008b9d1dc26ba6a9cb62127b02742fa9d754cd3bebf337f7a55d114c8e5cdd30be022040529b194ba3f9281a99f2b1c0a19c0489bc22ede944ccf4ecbab4cc618ef3ed01eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac000247304402203609e17b84f6a7d30c80bfa610b5b4542f32a8a0d5447a12fb1366d7f01cc44a0220573a954c4518331561406f90300e8f3358f51928d43c212a8caed02de67eebee0121025476c2e83188368da1ff3e292e7acafcdb3566bb0ad253f62fc70f07aeee635711000000";
+		for raw in &[BLOCK_80000_TX, segwit] {
+			let t: Transaction = (*raw).into();
+			let json = serde_json::to_string(&t).unwrap();
+			assert_eq!(json, format!("\"{}\"", raw));
+			let deserialized: Transaction = serde_json::from_str(&json).unwrap();
+			assert_eq!(deserialized, t);
+		}
+
+		assert!(serde_json::from_str::<Transaction>("\"0100\"").is_err());
+		assert!(serde_json::from_str::<Transaction>("\"zz\"").is_err());
+		assert!(serde_json::from_str::<Transaction>("1").is_err());
+	}
+
+	#[test]
+	fn test_transaction_witness_commitment() {
+		let commitment = "e2f61c3f71d1defd3fa999dfa36953755c690689799962b48bebd836974e8cf9";
+		let mut t = Transaction {
+			inputs: vec![TransactionInput::coinbase("03a08601".into())],
+			outputs: vec![
+				TransactionOutput { value: 625000000, script_pubkey: "76a914404371705fa9bd789a2fcd52d2c580b65d35549d88ac".into() },
+				TransactionOutput { value: 0, script_pubkey: format!("6a24aa21a9ed{}", commitment).parse().unwrap() },
+			],
+			..Default::default()
+		};
+		assert_eq!(t.witness_commitment(), Some(commitment.into()));
+
+		// the last commitment output is used
+		let other = "0000000000000000000000000000000000000000000000000000000000000001";
+		t.outputs.push(TransactionOutput { value: 0, script_pubkey: format!("6a24aa21a9ed{}00", other).parse().unwrap() });
+		assert_eq!(t.witness_commitment(), Some(other.into()));
+
+		// truncated commitment and other nulldata outputs are ignored
+		t.outputs.truncate(1);
+		t.outputs.push(TransactionOutput { value: 0, script_pubkey: format!("6a24aa21a9ed{}", &commitment[..62]).parse().unwrap() });
+		t.outputs.push(TransactionOutput { value: 0, script_pubkey: format!("6a24aa21a9ee{}", commitment).parse().unwrap() });
+		assert_eq!(t.witness_commitment(), None);
+
+		let mut not_coinbase = t.clone();
+		not_coinbase.outputs.push(TransactionOutput { value: 0, script_pubkey: format!("6a24aa21a9ed{}", commitment).parse().unwrap() });
+		not_coinbase.inputs[0].previous_output.index = 0;
+		assert_eq!(not_coinbase.witness_commitment(), None);
+	}
+
+	#[test]
+	fn test_transaction_hash_groestl() {
+		// coinbase of the Groestlcoin genesis block, its txid is the merkle root of the block
+		let t: Transaction = "01000000010000000000000000000000000000000000000000000000000000000000000000ffffffff3a04ffff001d0104325072657373757265206d75737420626520707574206f6e20566c6164696d697220507574696e206f766572204372696d6561ffffffff010000000000000000434104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac00000000".into();
+		assert_eq!(t.hash_groestl(), H256::from_reversed_str("3ce968df58f9c8a752306c4b7264afab93149dbc578bd08a42c446caaa6628bb"));
+		assert_eq!(t.hash(), H256::from_reversed_str("25db7286f848377a83ec85c067c5e86b7d4631d871cc88eda0c73e66a73363c2"));
+	}
+
+	#[test]
+	fn test_transaction_coinbase_height() {
+		// KMD coinbase of block 150282
+		let t: Transaction = "0400008085202f89010000000000000000000000000000000000000000000000000000000000000000ffffffff06030a4b020101ffffffff0178e600000000000023210388392e0885e449ea9745ce7ad2631fdca5288f9d790cee1b696e67c75ad54a2dac1ad92f5d000000000000000000000000000000".into();
+		assert!(t.is_coinbase());
+		assert_eq!(t.coinbase_height(), Some(150282));
+
+		let coinbase = |script_sig: &'static str| Transaction {
+			inputs: vec![TransactionInput::coinbase(script_sig.into())],
+			..Default::default()
+		};
+		assert_eq!(coinbase("00").coinbase_height(), Some(0));
+		assert_eq!(coinbase("5b").coinbase_height(), Some(11));
+		assert_eq!(coinbase("0180").coinbase_height(), None);
+		assert_eq!(coinbase("028000").coinbase_height(), Some(128));
+		assert_eq!(coinbase("04ffffff7f").coinbase_height(), Some(0x7fffffff));
+		assert_eq!(coinbase("030a4b").coinbase_height(), None);
+		assert_eq!(coinbase("050a4b020101").coinbase_height(), None);
+		assert_eq!(coinbase("").coinbase_height(), None);
+
+		let t: Transaction = BLOCK_80000_TX.into();
+		assert_eq!(t.coinbase_height(), None);
+	}
+
+	#[test]
+	fn test_transaction_read_many() {
+		let first = BLOCK_80000_TX;
+		let second = "01000000000102fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f00000000494830450221008b9d1dc26ba6a9cb62127b02742fa9d754cd3bebf337f7a55d114c8e5cdd30be022040529b194ba3f9281a99f2b1c0a19c0489bc22ede944ccf4ecbab4cc618ef3ed01eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac000247304402203609e17b84f6a7d30c80bfa610b5b4542f32a8a0d5447a12fb1366d7f01cc44a0220573a954c4518331561406f90300e8f3358f51928d43c212a8caed02de67eebee0121025476c2e83188368da1ff3e292e7acafcdb3566bb0ad253f62fc70f07aeee635711000000";
+		let data: Bytes = format!("{}{}", first, second).parse().unwrap();
+
+		let mut iter = Transaction::read_many(&data, 2);
+		assert_eq!(iter.next().unwrap().unwrap().hash(), Transaction::from(first).hash());
+		assert_eq!(iter.next().unwrap().unwrap().hash(), Transaction::from(second).hash());
+		assert!(iter.next().is_none());
+
+		assert_eq!(Transaction::read_many(&data, 1).count(), 1);
+
+		let results: Vec<_> = Transaction::read_many(&data, 3).collect();
+		assert_eq!(results.len(), 3);
+		assert!(results[2].is_err());
+	}
+
+	#[test]
+	fn test_transaction_read_many_version_2() {
+		let version_2 = format!("02{}", &BLOCK_80000_TX[2..]);
+		let expected: Transaction = version_2.parse::<Bytes>().map(|bytes| deserialize(bytes.as_ref()).unwrap()).unwrap();
+		let data: Bytes = format!("{}{}{}", version_2, version_2, BLOCK_80000_TX).parse().unwrap();
+
+		let transactions = Transaction::read_many(&data, 3).collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(transactions.len(), 3);
+		assert_eq!(transactions[0], expected);
+		assert_eq!(transactions[1], expected);
+		assert_eq!(transactions[2], BLOCK_80000_TX.into());
+		assert!(transactions.iter().all(|t| !t.zcash));
+
+		// version 1 transaction followed by another one is not Zcash either
+		let data: Bytes = format!("{}{}", BLOCK_80000_TX, version_2).parse().unwrap();
+		let transactions = Transaction::read_many(&data, 2).collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(transactions[0], BLOCK_80000_TX.into());
+		assert_eq!(transactions[1], expected);
+		assert!(transactions.iter().all(|t| !t.zcash));
+	}
+
+	#[test]
+	fn test_transaction_reader_huge_list_length() {
+		// version, 0xffffffff inputs
+		let raw: Bytes = "01000000feffffffff".into();
+		assert_eq!(Transaction::deserialize_with_remaining(&raw).unwrap_err(), Error::MalformedData);
+		assert_eq!(deserialize::<_, Transaction>(&raw as &[u8]).unwrap_err(), Error::MalformedData);
+
+		// version, 4096 inputs, which is below the list size limit, but longer than the data
+		let raw: Bytes = "01000000fd0010000000000000".into();
+		let mut reader = Reader::new(&raw);
+		assert_eq!(deserialize_tx(&mut reader, TxType::StandardWithWitness, TxBoundary::EndOfData, raw.len()).unwrap_err(), Error::MalformedData);
+	}
+
+	#[test]
+	fn test_transaction_reader_huge_script_length() {
+		// version, 1 input, previous output, 0xffffffff bytes of script_sig
+		let raw: Bytes = "0100000001a6b97044d03da79c005b20ea9c0e1a6d9dc12d9f7b91a5911c9030a439eed8f500000000feffffffff48".into();
+		assert_eq!(Transaction::deserialize_with_remaining(&raw).unwrap_err(), Error::MalformedData);
+
+		// version, n_time, no inputs, 1 output, value, 100001 bytes of script_pubkey
+		let raw: Bytes = "010000000000000000010000000000000000fea1860100".into();
+		let mut reader = Reader::new(&raw);
+		assert_eq!(deserialize_tx(&mut reader, TxType::PosWithNTime, TxBoundary::EndOfData, raw.len()).unwrap_err(), Error::MalformedData);
+
+		// NavCoin style transaction with 0xffffffff bytes of str_d_zeel
+		let raw: Bytes = "020000006f4a8c5a01a6b97044d03da79c005b20ea9c0e1a6d9dc12d9f7b91a5911c9030a439eed8f5000000004948304502206e21798a42fae0e854281abd38bacd1aeed3ee3738d9e1446618c4571d1090db022100e2ac980643b0b82c0e88ffdfec6b64e3e6ba35e7ba5fdd7d5d6cc8d25c6b241501ffffffff0100f2052a010000001976a914404371705fa9bd789a2fcd52d2c580b65d35549d88ac00000000feffffffff6e".into();
+		let mut reader = Reader::new(&raw);
+		assert_eq!(deserialize_tx(&mut reader, TxType::PosWithNTime, TxBoundary::EndOfData, raw.len()).unwrap_err(), Error::MalformedData);
+	}
+
+	#[test]
+	fn test_transaction_from_hex() {
+		let raw = BLOCK_80000_TX;
+		let t = Transaction::from_hex(raw).unwrap();
+		assert_eq!(t, raw.into());
+		assert_eq!(Transaction::from_bytes(&serialize(&t)), Ok(t));
+
+		// truncated in the middle of the output script
+		assert!(Transaction::from_hex(&raw[..raw.len() - 20]).is_err());
+		assert!(Transaction::from_bytes(&[1, 0, 0, 0]).is_err());
+		assert!(Transaction::from_bytes(&[]).is_err());
+		// odd length and not hex
+		assert_eq!(Transaction::from_hex(&raw[..raw.len() - 1]), Err(Error::MalformedData));
+		assert_eq!(Transaction::from_hex("01000000zz"), Err(Error::MalformedData));
+	}
+
+	#[test]
+	fn test_transaction_reader_empty_witness_inputs() {
+		// version, empty inputs, witness flag, empty inputs, empty outputs, lock time
+		let raw: Bytes = "010000000001000000000000".into();
+		let mut reader = Reader::new(&raw);
+		assert_eq!(deserialize_tx(&mut reader, TxType::StandardWithWitness, TxBoundary::EndOfData, raw.len()).unwrap_err(), Error::MalformedData);
+		assert!(Transaction::deserialize_with_remaining(&raw).is_err());
+		assert!(deserialize::<_, Transaction>(&raw as &[u8]).is_err());
+	}
+
+	#[test]
+	fn test_transaction_reader_max_bytes() {
+		let raw: Bytes = BLOCK_80000_TX.into();
+
+		let mut reader = Reader::new(&raw).with_max_bytes(raw.len());
+		let t: Transaction = reader.read().unwrap();
+		assert_eq!(serialize(&t), raw);
+		assert!(reader.is_finished());
+
+		let mut reader = Reader::new(&raw).with_max_bytes(raw.len() - 1);
+		assert_eq!(reader.read::<Transaction>().unwrap_err(), Error::MalformedData);
+
+		let mut reader = Reader::new(&raw).with_max_bytes(10);
+		reader.read::<u32>().unwrap();
+		assert_eq!(reader.read::<H256>().unwrap_err(), Error::MalformedData);
+	}
+
+	#[test]
+	fn test_transaction_reader_pos_n_time() {
+		// transaction from block 80000 with n_time following the version, as used by PoS coins
+		let raw: Bytes = "010000006f4a8c5a01a6b97044d03da79c005b20ea9c0e1a6d9dc12d9f7b91a5911c9030a439eed8f5000000004948304502206e21798a42fae0e854281abd38bacd1aeed3ee3738d9e1446618c4571d1090db022100e2ac980643b0b82c0e88ffdfec6b64e3e6ba35e7ba5fdd7d5d6cc8d25c6b241501ffffffff0100f2052a010000001976a914404371705fa9bd789a2fcd52d2c580b65d35549d88ac00000000".into();
+		let t: Transaction = deserialize(raw.as_ref()).unwrap();
+		assert_eq!(t.version, 1);
+		assert_eq!(t.n_time, Some(0x5a8c4a6f));
+		assert_eq!(t.inputs.len(), 1);
+		assert_eq!(t.outputs.len(), 1);
+		assert!(!t.zcash);
+		assert_eq!(serialize(&t), raw);
+		assert_eq!(t.serialized_size(), raw.len());
+	}
+
+	#[test]
+	fn test_transaction_reader_str_d_zeel() {
+		// NavCoin style transaction: n_time after the version and a string after the lock time
+		let raw: Bytes = "020000006f4a8c5a01a6b97044d03da79c005b20ea9c0e1a6d9dc12d9f7b91a5911c9030a439eed8f5000000004948304502206e21798a42fae0e854281abd38bacd1aeed3ee3738d9e1446618c4571d1090db022100e2ac980643b0b82c0e88ffdfec6b64e3e6ba35e7ba5fdd7d5d6cc8d25c6b241501ffffffff0100f2052a010000001976a914404371705fa9bd789a2fcd52d2c580b65d35549d88ac000000000c6e6176636f696e2074657374".into();
+		let t: Transaction = deserialize(raw.as_ref()).unwrap();
+		assert_eq!(t.version, 2);
+		assert_eq!(t.n_time, Some(0x5a8c4a6f));
+		assert_eq!(t.str_d_zeel, Some("navcoin test".into()));
+		assert!(t.join_splits.is_empty());
+		assert_eq!(serialize(&t), raw);
+		assert_eq!(t.serialized_size(), raw.len());
 	}
 
 	#[test]
@@ -539,9 +1474,11 @@ mod tests {
 	}
 
     // https://github.com/zcash/zips/blob/master/zip-0243.rst#test-vector-1
+	const ZIP243_SAPLING_TX: &str = "0400008085202f890002e7719811893e0000095200ac6551ac636565b2835a0805750200025151481cdd86b3cc4318442117623ceb0500031b3d1a027c2c40590958b7eb13d742a997738c46a458965baf276ba92f272c721fe01f7e9c8e36d6a5e29d4e30a73594bf5098421c69378af1e40f64e125946f62c2fa7b2fecbcb64b6968912a6381ce3dc166d56a1d62f5a8d7551db5fd9313e8c7203d996af7d477083756d59af80d06a745f44ab023752cb5b406ed8985e18130ab33362697b0e4e4c763ccb8f676495c222f7fba1e31defa3d5a57efc2e1e9b01a035587d5fb1a38e01d94903d3c3e0ad3360c1d3710acd20b183e31d49f25c9a138f49b1a537edcf04be34a9851a7af9db6990ed83dd64af3597c04323ea51b0052ad8084a8b9da948d320dadd64f5431e61ddf658d24ae67c22c8d1309131fc00fe7f235734276d38d47f1e191e00c7a1d48af046827591e9733a97fa6b679f3dc601d008285edcbdae69ce8fc1be4aac00ff2711ebd931de518856878f73476f21a482ec9378365c8f7393c94e2885315eb4671098b79535e790fe53e29fef2b3766697ac32b4f473f468a008e72389fc03880d780cb07fcfaabe3f1a15825b7acb4d6b57a61bc68f242b52e4fbf85cf1a09cc45b6d6bb3a391578f499486a7afd04a0d9c74c2995d96b4de37b36046a1ef6d190b916b1111c92887311a20da8aba18d1dbebbc862ded42435e92476930d069896cff30eb414f727b89e001afa2fb8dc3436d75a4a6f26572504b192232ecb9f0c02411e52596bc5e90457e745939ffedbd12863ce71a02af117d417adb3d15cc54dcb1fce467500c6b8fb86b12b56da9c382857deecc40a98d5f2935395ee4762dd21afdbb5d47fa9a6dd984d567db2857b927b7fae2db587105415d4642789d38f50b8dbcc129cab3d17d19f3355bcf73cecb8cb8a5da01307152f13936a270572670dc82d39026c6cb4cd4b0f7f5aa2a4f5a5341ec5dd715406f2fdd2afa733f5f641c8c21862a1bafce2609d9eecfa158cfb5cd79f88008e315dc7d8388e76c1782fd2795d18a763624c25fa959cc97489ce75745824b77868c53239cfbdf73caec65604037314faaceb56218c6bd30f8374ac13386793f21a9fb80ad03bc0cda4a44946c00e1b102c78f11876b7065212183199fb5979ca77d2c24c738fe5145f02602053bb4c2f6556df6ed4b4ddd3d9a69f53357d7767f4f5ccbdbc596631277f8fecd08cb056b95e3025b9792fff7f244fc716269b926d62e9596fa825c6bf21aff9e68625a192440ea06828123d97884806f15fa08da52754a1095e3ff1abd5ce4fddfccfc3a6128aef784a64610a89d1a7099216d0814d3a2d452431c32d411ac1cce82ad0229407bbc48985675e3f874a4533f1d63a84dfa3e0f460fe2f57e34fbc75423c3737f5b2a0615f5722db041a3ef66fa483afd3c2e19e59444a64add6df1d963f5dd5b5010d3d025f0287c4cf19c75f33d51ddddba5d657b43ee8da645443814cc7329f3e9b4e54c236c29af3923101756d9fa4bd0f7d2ddaacb6b0f86a2658e0a07a05ac5b950051cd24c47a88d13d659ba2a46ca1830816d09cd7646f76f716abec5de07fe9b523410806ea6f288f8736c23357c85f45791e1708029d9824d90704607f387a03e49bf9836574431345a7877efaa8a08e73081ef8d62cb780a010fa3207ee2f0408097d563da1b2146819edf88d33e7753664fb71d122a6e36998fbd467f75b780149ae8808f4e68f50c0536acddf6f1aeab016b6bc1ec144b4e59aeb77eef49d00e5fbb67101cdd41e6bc9cf641a52fca98be915f8440a410d74cb30e15914f01bc6bc2307b488d2556d7b7380ea4ffd712f6b02fe806b94569cd4059f396bf29b99d0a40e5e1711ca944f72d436a102fca4b97693da0b086fe9d2e7162470d02e0f05d4bec9512bfb3f38327296efaa74328b118c27402c70c3a90b49ad4bbc68e37c0aa7d9b3fe17799d73b841e751713a02943905aae0803fd69442eb7681ec2a05600054e92eed555028f21b6a155268a2dd6640a69301a52a38d4d9f9f957ae35af7167118141ce4c9be0a6a492fe79f1581a155fa3a2b9dafd82e650b386ad3a08cb6b83131ac300b0846354a7eef9c410e4b62c47c5426907dfc6685c5c99b7141ac626ab4761fd3f41e728e1a28f89db89ffdeca364dd2f0f0739f0534556483199c71f189341ac9b78a269164206a0ea1ce73bfb2a942e7370b247c046f8e75ef8e3f8bd821cf577491864e20e6d08fd2e32b555c92c661f19588b72a89599710a88061253ca285b6304b37da2b5294f5cb354a894322848ccbdc7c2545b7da568afac87ffa005c312241c2d57f4b45d6419f0d2e2c5af33ae243785b325cdab95404fc7aed70525cddb41872cfcc214b13232edc78609753dbff930eb0dc156612b9cb434bc4b693392deb87c530435312edcedc6a961133338d786c4a3e103f60110a16b1337129704bf4754ff6ba9fbe65951e610620f71cda8fc877625f2c5bb04cbe1228b1e886f4050afd8fe94e97d2e9e85c6bb748c0042d3249abb1342bb0eebf62058bf3de080d94611a3750915b5dc6c0b3899d41222bace760ee9c8818ded599e34c56d7372af1eb86852f2a732104bdb750739de6c2c6e0f9eb7cb17f1942bfc9f4fd6ebb6b4cdd4da2bca26fac4578e9f543405acc7d86ff59158bd0cba3aef6f4a8472d144d99f8b8d1dedaa9077d4f01d4bb27bbe31d88fbefac3dcd4797563a26b1d61fcd9a464ab21ed550fe6fa09695ba0b2f10eea6468cc6e20a66f826e3d14c5006f0563887f5e1289be1b2004caca8d3f34d6e84bf59c1e04619a7c23a996941d889e4622a9b9b1d59d5e319094318cd405ba27b7e2c084762d31453ec4549a4d97729d033460fcf89d6494f2ffd789e98082ea5ce9534b3acd60fe49e37e4f666931677319ed89f85588741b3128901a93bd78e4be0225a9e2692c77c969ed0176bdf9555948cbd5a332d045de6ba6bf4490adfe7444cd467a09075417fc0200000000000000000000000000000000062e49f008c51ad4227439c1b4476ccd8e97862dab7be1e8d399c05ef27c6e22ee273e15786e394c8f1be31682a30147963ac8da8d41d804258426a3f70289b8ad19d8de13be4eebe3bd4c8a6f55d6e0c373d456851879f5fbc282db9e134806bff71e11bc33ab75dd6ca067fb73a043b646a7cf39cab4928386786d2f24141ee120fdc34d6764eafc66880ee0204f53cc1167ed20b43a52dea3ca7cff8ef35cd8e6d7c111a68ef44bcd0c1513ad47ca61c659cc5d325b440f6b9f59aff66879bb6688fd2859362b182f207b3175961f6411a493bffd048e7d0d87d82fe6f990a2b0a25f5aa0111a6e68f37bf6f3ac2d26b84686e569d58d99c1383597fad81193c4c1b16e6a90e2d507cdfe6fbdaa86163e9cf5de3100fbca7e8da047b090db9f37952fbfee76af61668190bd52ed490e677b515d014384af07219c7c0ee7fc7bfc79f325644e4df4c0d7db08e9f0bd024943c705abff8994bfa605cfbc7ed746a7d3f7c37d9e8bdc433b7d79e08a12f738a8f0dbddfef2f2657ef3e47d1b0fd11e6a13311fb799c79c641d9da43b33e7ad012e28255398789262275f1175be8462c01491c4d842406d0ec4282c9526174a09878fe8fdde33a29604e5e5e7b2a025d6650b97dbb52befb59b1d30a57433b0a351474444099daa371046613260cf3354cfcdada663ece824ffd7e44393886a86165ddddf2b4c41773554c86995269408b11e6737a4c447586f69173446d8e48bf84cbc000a807899973eb93c5e819aad669413f8387933ad1584aa35e43f4ecd1e2d0407c0b1b89920ffdfdb9bea51ac95b557af71b89f903f5d9848f14fcbeb1837570f544d6359eb23faf38a0822da36ce426c4a2fbeffeb0a8a2e297a9d19ba15024590e3329d9fa9261f9938a4032dd34606c9cf9f3dd33e576f05cd1dd6811c6298757d77d9e810abdb226afcaa4346a6560f8932b3181fd355d5d391976183f8d99388839632d6354f666d09d3e5629ea19737388613d38a34fd0f6e50ee5a0cc9677177f50028c141378187bd2819403fc534f80076e9380cb4964d3b6b45819d3b8e9caf54f051852d671bf8c1ffde2d1510756418cb4810936aa57e6965d6fb656a760b7f19adf96c173488552193b147ee58858033dac7cd0eb204c06490bbdedf5f7571acb2ebe76acef3f2a01ee987486dfe6c3f0a5e234c127258f97a28fb5d164a8176be946b8097d0e317287f33bf9c16f9a545409ce29b1f4273725fc0df02a04ebae178b3414fb0a82d50deb09fcf4e6ee9d180ff4f56ff3bc1d3601fc2dc90d814c3256f4967d3a8d64c83fea339c51f5a8e5801fbb97835581b602465dee04b5922c2761b54245bec0c9eef2db97d22b2b3556cc969fbb13d06509765a52b3fac54b93f421bf08e18d52ddd52cc1c8ca8adfaccab7e5cc2f4573fbbf8239bb0b8aedbf8dad16282da5c9125dba1c059d0df8abf621078f02d6c4bc86d40845ac1d59710c45f07d585eb48b32fc0167ba256e73ca3b9311c62d109497957d8dbe10aa3e866b40c0baa2bc492c19ad1e6372d9622bf163fbffeaeee796a3cd9b6fbbfa4d792f34d7fd6e763cd5859dd26833d21d9bc5452bd19515dff9f4995b35bc0c1f876e6ad11f2452dc9ae85aec01fc56f8cbfda75a7727b75ebbd6bbffb43b63a3b1b671e40feb0db002974a3c3b1a788567231bf6399ff89236981149d423802d2341a3bedb9ddcbac1fe7b6435e1479c72e7089d029e7fbbaf3cf37e9b9a6b776791e4c5e6fda57e8d5f14c8c35a2d270846b9dbe005cda16af4408f3ab06a916eeeb9c9594b70424a4c1d171295b6763b22f47f80b53ccbb904bd68fd65fbd3fbdea1035e98c21a7dbc91a9b5bc7690f05ec317c97f8764eb48e911d428ec8d861b708e8298acb62155145155ae95f0a1d1501034753146e22d05f586d7f6b4fe12dad9a17f5db70b1db96b8d9a83edadc966c8a5466b61fc998c31f1070d9a5c9a6d268d304fe6b8fd3b4010348611abdcbd49fe4f85b623c7828c71382e1034ea67bc8ae97404b0c50b2a04f559e49950afcb0ef462a2ae024b0f0224dfd73684b88c7fbe92d02b68f759c4752663cd7b97a14943649305521326bde085630864629291bae25ff8822a14c4b666a9259ad0dc42a8290ac7bc7f53a16f379f758e5de750f04fd7cad47701c8597f97888bea6fa0bf2999956fbfd0ee68ec36e4688809ae231eb8bc4369f5fe1573f57e099d9c09901bf39caac48dc11956a8ae905ead86954547c448ae43d315e669c4242da565938f417bf43ce7b2b30b1cd4018388e1a910f0fc41fb0877a5925e466819d375b0a912d4fe843b76ef6f223f0f7c894f38f7ab780dfd75f669c8c06cffa0000000000000000000000000000000043eb47565a50e3b1fa45ad61ce9a1c4727b7aaa53562f523e73952bbf33d8a4104078ade3eaaa49699a69fdf1c5ac7732146ee5e1d6b6ca9b9180f964cc9d0878ae1373524d7d510e58227df6de9d30d271867640177b0f1856e28d5c8afb095ef6184fed651589022eeaea4c0ce1fa6f085092b04979489172b3ef8194a798df5724d6b05f1ae000013a08d612bca8a8c31443c10346dbf61de8475c0bbec5104b47556af3d514458e2321d146071789d2335934a680614e83562f82dfd405b54a45eb32c165448d4d5d61ca2859585369f53f1a137e9e82b67b8fdaf01bda54a317311896ae10280a032440c420a421e944d1e952b70d5826cd3b08b7db9630fe4fd5f22125de840fcc40b98038af11d55be25432597b4b65b9ec1c7a8bbfd052cbf7e1c1785314934b262d5853754f1f17771cfb7503072655753fa3f54ecc587e9f83b581916092df26e63e18994cb0db91a0bbdc7b6119b32222adf5e61d8d8ae89dae4954b54813bb33f08d562ba513fee1b09c0fcd516055419474dd7fda038a89c84ea7b9468287f0eb0c10c4b132520194d3d8d5351fc10d09c15c8cc101aa1663bbf17b84111f38bb439f07353bdea3596d15e713e1e2e7d3f1c383135b47fa7f81f46df7a902a404699ec912f5656c35b85763e4de583aecaa1dfd5d2677d9c8ffee877f63f40a5ca0d67f6e554124739f805af876aeede53aa8b0f8e5604a73c30cbd09dad963d6f8a5dcc40def40797342113ba206fae8ebe4f3bc3caf69259e462eff9ba8b3f4bfaa1300c26925a8729cd32915bfc966086f0d5560bbe32a598c22adfb48cef72ba5d4287c0cefbacfd8ce195b4963c34a94bba7a175dae4bbe3ef4863d53708915090f47a068e227433f9e49d3aa09e356d8d66d0c0121e91a3c4aa3f27fa1b63396e2b41db908fdab8b18cc7304e94e970568f9421c0dbbbaf84598d972b0534f48a5e52670436aaa776ed2482ad703430201e53443c36dcfd34a0cb6637876105e79bf3bd58ec148cb64970e3223a91f71dfcfd5a04b667fbaf3d4b3b908b9828820dfecdd753750b5f9d2216e56c615272f854464c0ca4b1e85aedd038292c4e1a57744ebba010b9ebfbb011bd6f0b78805025d27f3c17746bae116c15d9f471f0f6288a150647b2afe9df7cccf01f5cde5f04680bbfed87f6cf429fb27ad6babe791766611cf5bc20e48bef119259b9b8a0e39c3df28cb9582ea338601cdc481b32fb82adeebb3dade25d1a3df20c37e712506b5d996c49a9f0f30ddcb91fe9004e1e83294a6c9203d94e8dc2cbb449de4155032604e47997016b304fd437d8235045e255a19b743a0a9f2e336b44cae307bb3987bd3e4e777fbb34c0ab8cc3d67466c0a88dd4ccad18a07a8d1068df5b629e5718d0f6df5c957cf71bb00a5178f175caca944e635c5159f738e2402a2d21aa081e10e456afb00b9f62416c8b9c0f7228f510729e0be3f305313d77f7379dc2af24869c6c74ee4471498861d192f0ff0f508285dab6b6a36ccf7d12256cc76b95503720ac672d08268d2cf7773b6ba2a5f664847bf707f2fc10c98f2f006ec22ccb5a8c8b7c40c7c2d49a6639b9f2ce33c25c04bc461e744dfa536b00d94baddf4f4d14044c695a33881477df124f0fcf206a9fb2e65e304cdbf0c4d2390170c130ab849c2f22b5cdd3921640c8cf1976ae1010b0dfd9cb2543e45f99749cc4d61f2e8aabfe98bd905fa39951b33ea769c45ab9531c57209862ad12fd76ba4807e65417b6cd12fa8ec916f013ebb8706a96effeda06c4be24b04846392e9d1e6930eae01fa21fbd700583fb598b92c8f4eb8a61aa6235db60f2841cf3a1c6ab54c67066844711d091eb931a1bd6281aedf2a0e8fab18817202a9be06402ed9cc720c16bfe881e4df4255e87afb7fc62f38116bbe03cd8a3cb11a27d568414782f47b1a44c97c680467694bc9709d32916c97e8006cbb07ba0e4180a3738038c374c4cce8f32959afb25f303f5815c4533124acf9d18940e77522ac5dc4b9570aae8f47b7f57fd8767bea1a24ae7bed65b4afdc8f1278c30e2db98fd172730ac6bbed4f1127cd32b04a95b205526cfcb4c4e1cc955175b3e8de1f5d81b18669692350aaa1a1d797617582e54d7a5b57a683b32fb1098062dad7b0c2eb518f6862e83db25e3dbaf7aed504de932acb99d735992ce62bae9ef893ff6acc0ffcf8e3483e146b9d49dd8c7835f43a37dca0787e3ec9f6605223d5ba7ae0ab9025b73bc03f7fac36c009a56d4d95d1e81d3b3ebca7e54cc1a12d127b57c8138976e791013b015f06a624f521b6ee04ec980893c7e5e01a336203594094f82833d7445fe2d09130f63511da54832de9136b39f4599f5aa5dfbb45da60cdceab7eefde89be63f3f7c0d2324847cce1405def7c469b0e272494e5df54f568656cb9c8818d92b72b8bc34db7bb3112487e746eefe4e808bbb287d99bf07d00dabededc5e5f074ffeae0cba7da3a516c173be1c513323e119f635e8209a074b216b7023fadc2d25949c90037e71e3e550726d210a2c688342e52440635e9cc14afe10102621a9c9accb782e9e4a5fa87f0a956f5b";
+
     #[test]
 	fn test_transaction_serde_overwintered_sapling() {
-        let raw = "0400008085202f890002e7719811893e0000095200ac6551ac636565b2835a0805750200025151481cdd86b3cc4318442117623ceb0500031b3d1a027c2c40590958b7eb13d742a997738c46a458965baf276ba92f272c721fe01f7e9c8e36d6a5e29d4e30a73594bf5098421c69378af1e40f64e125946f62c2fa7b2fecbcb64b6968912a6381ce3dc166d56a1d62f5a8d7551db5fd9313e8c7203d996af7d477083756d59af80d06a745f44ab023752cb5b406ed8985e18130ab33362697b0e4e4c763ccb8f676495c222f7fba1e31defa3d5a57efc2e1e9b01a035587d5fb1a38e01d94903d3c3e0ad3360c1d3710acd20b183e31d49f25c9a138f49b1a537edcf04be34a9851a7af9db6990ed83dd64af3597c04323ea51b0052ad8084a8b9da948d320dadd64f5431e61ddf658d24ae67c22c8d1309131fc00fe7f235734276d38d47f1e191e00c7a1d48af046827591e9733a97fa6b679f3dc601d008285edcbdae69ce8fc1be4aac00ff2711ebd931de518856878f73476f21a482ec9378365c8f7393c94e2885315eb4671098b79535e790fe53e29fef2b3766697ac32b4f473f468a008e72389fc03880d780cb07fcfaabe3f1a15825b7acb4d6b57a61bc68f242b52e4fbf85cf1a09cc45b6d6bb3a391578f499486a7afd04a0d9c74c2995d96b4de37b36046a1ef6d190b916b1111c92887311a20da8aba18d1dbebbc862ded42435e92476930d069896cff30eb414f727b89e001afa2fb8dc3436d75a4a6f26572504b192232ecb9f0c02411e52596bc5e90457e745939ffedbd12863ce71a02af117d417adb3d15cc54dcb1fce467500c6b8fb86b12b56da9c382857deecc40a98d5f2935395ee4762dd21afdbb5d47fa9a6dd984d567db2857b927b7fae2db587105415d4642789d38f50b8dbcc129cab3d17d19f3355bcf73cecb8cb8a5da01307152f13936a270572670dc82d39026c6cb4cd4b0f7f5aa2a4f5a5341ec5dd715406f2fdd2afa733f5f641c8c21862a1bafce2609d9eecfa158cfb5cd79f88008e315dc7d8388e76c1782fd2795d18a763624c25fa959cc97489ce75745824b77868c53239cfbdf73caec65604037314faaceb56218c6bd30f8374ac13386793f21a9fb80ad03bc0cda4a44946c00e1b102c78f11876b7065212183199fb5979ca77d2c24c738fe5145f02602053bb4c2f6556df6ed4b4ddd3d9a69f53357d7767f4f5ccbdbc596631277f8fecd08cb056b95e3025b9792fff7f244fc716269b926d62e9596fa825c6bf21aff9e68625a192440ea06828123d97884806f15fa08da52754a1095e3ff1abd5ce4fddfccfc3a6128aef784a64610a89d1a7099216d0814d3a2d452431c32d411ac1cce82ad0229407bbc48985675e3f874a4533f1d63a84dfa3e0f460fe2f57e34fbc75423c3737f5b2a0615f5722db041a3ef66fa483afd3c2e19e59444a64add6df1d963f5dd5b5010d3d025f0287c4cf19c75f33d51ddddba5d657b43ee8da645443814cc7329f3e9b4e54c236c29af3923101756d9fa4bd0f7d2ddaacb6b0f86a2658e0a07a05ac5b950051cd24c47a88d13d659ba2a46ca1830816d09cd7646f76f716abec5de07fe9b523410806ea6f288f8736c23357c85f45791e1708029d9824d90704607f387a03e49bf9836574431345a7877efaa8a08e73081ef8d62cb780a010fa3207ee2f0408097d563da1b2146819edf88d33e7753664fb71d122a6e36998fbd467f75b780149ae8808f4e68f50c0536acddf6f1aeab016b6bc1ec144b4e59aeb77eef49d00e5fbb67101cdd41e6bc9cf641a52fca98be915f8440a410d74cb30e15914f01bc6bc2307b488d2556d7b7380ea4ffd712f6b02fe806b94569cd4059f396bf29b99d0a40e5e1711ca944f72d436a102fca4b97693da0b086fe9d2e7162470d02e0f05d4bec9512bfb3f38327296efaa74328b118c27402c70c3a90b49ad4bbc68e37c0aa7d9b3fe17799d73b841e751713a02943905aae0803fd69442eb7681ec2a05600054e92eed555028f21b6a155268a2dd6640a69301a52a38d4d9f9f957ae35af7167118141ce4c9be0a6a492fe79f1581a155fa3a2b9dafd82e650b386ad3a08cb6b83131ac300b0846354a7eef9c410e4b62c47c5426907dfc6685c5c99b7141ac626ab4761fd3f41e728e1a28f89db89ffdeca364dd2f0f0739f0534556483199c71f189341ac9b78a269164206a0ea1ce73bfb2a942e7370b247c046f8e75ef8e3f8bd821cf577491864e20e6d08fd2e32b555c92c661f19588b72a89599710a88061253ca285b6304b37da2b5294f5cb354a894322848ccbdc7c2545b7da568afac87ffa005c312241c2d57f4b45d6419f0d2e2c5af33ae243785b325cdab95404fc7aed70525cddb41872cfcc214b13232edc78609753dbff930eb0dc156612b9cb434bc4b693392deb87c530435312edcedc6a961133338d786c4a3e103f60110a16b1337129704bf4754ff6ba9fbe65951e610620f71cda8fc877625f2c5bb04cbe1228b1e886f4050afd8fe94e97d2e9e85c6bb748c0042d3249abb1342bb0eebf62058bf3de080d94611a3750915b5dc6c0b3899d41222bace760ee9c8818ded599e34c56d7372af1eb86852f2a732104bdb750739de6c2c6e0f9eb7cb17f1942bfc9f4fd6ebb6b4cdd4da2bca26fac4578e9f543405acc7d86ff59158bd0cba3aef6f4a8472d144d99f8b8d1dedaa9077d4f01d4bb27bbe31d88fbefac3dcd4797563a26b1d61fcd9a464ab21ed550fe6fa09695ba0b2f10eea6468cc6e20a66f826e3d14c5006f0563887f5e1289be1b2004caca8d3f34d6e84bf59c1e04619a7c23a996941d889e4622a9b9b1d59d5e319094318cd405ba27b7e2c084762d31453ec4549a4d97729d033460fcf89d6494f2ffd789e98082ea5ce9534b3acd60fe49e37e4f666931677319ed89f85588741b3128901a93bd78e4be0225a9e2692c77c969ed0176bdf9555948cbd5a332d045de6ba6bf4490adfe7444cd467a09075417fc0200000000000000000000000000000000062e49f008c51ad4227439c1b4476ccd8e97862dab7be1e8d399c05ef27c6e22ee273e15786e394c8f1be31682a30147963ac8da8d41d804258426a3f70289b8ad19d8de13be4eebe3bd4c8a6f55d6e0c373d456851879f5fbc282db9e134806bff71e11bc33ab75dd6ca067fb73a043b646a7cf39cab4928386786d2f24141ee120fdc34d6764eafc66880ee0204f53cc1167ed20b43a52dea3ca7cff8ef35cd8e6d7c111a68ef44bcd0c1513ad47ca61c659cc5d325b440f6b9f59aff66879bb6688fd2859362b182f207b3175961f6411a493bffd048e7d0d87d82fe6f990a2b0a25f5aa0111a6e68f37bf6f3ac2d26b84686e569d58d99c1383597fad81193c4c1b16e6a90e2d507cdfe6fbdaa86163e9cf5de3100fbca7e8da047b090db9f37952fbfee76af61668190bd52ed490e677b515d014384af07219c7c0ee7fc7bfc79f325644e4df4c0d7db08e9f0bd024943c705abff8994bfa605cfbc7ed746a7d3f7c37d9e8bdc433b7d79e08a12f738a8f0dbddfef2f2657ef3e47d1b0fd11e6a13311fb799c79c641d9da43b33e7ad012e28255398789262275f1175be8462c01491c4d842406d0ec4282c9526174a09878fe8fdde33a29604e5e5e7b2a025d6650b97dbb52befb59b1d30a57433b0a351474444099daa371046613260cf3354cfcdada663ece824ffd7e44393886a86165ddddf2b4c41773554c86995269408b11e6737a4c447586f69173446d8e48bf84cbc000a807899973eb93c5e819aad669413f8387933ad1584aa35e43f4ecd1e2d0407c0b1b89920ffdfdb9bea51ac95b557af71b89f903f5d9848f14fcbeb1837570f544d6359eb23faf38a0822da36ce426c4a2fbeffeb0a8a2e297a9d19ba15024590e3329d9fa9261f9938a4032dd34606c9cf9f3dd33e576f05cd1dd6811c6298757d77d9e810abdb226afcaa4346a6560f8932b3181fd355d5d391976183f8d99388839632d6354f666d09d3e5629ea19737388613d38a34fd0f6e50ee5a0cc9677177f50028c141378187bd2819403fc534f80076e9380cb4964d3b6b45819d3b8e9caf54f051852d671bf8c1ffde2d1510756418cb4810936aa57e6965d6fb656a760b7f19adf96c173488552193b147ee58858033dac7cd0eb204c06490bbdedf5f7571acb2ebe76acef3f2a01ee987486dfe6c3f0a5e234c127258f97a28fb5d164a8176be946b8097d0e317287f33bf9c16f9a545409ce29b1f4273725fc0df02a04ebae178b3414fb0a82d50deb09fcf4e6ee9d180ff4f56ff3bc1d3601fc2dc90d814c3256f4967d3a8d64c83fea339c51f5a8e5801fbb97835581b602465dee04b5922c2761b54245bec0c9eef2db97d22b2b3556cc969fbb13d06509765a52b3fac54b93f421bf08e18d52ddd52cc1c8ca8adfaccab7e5cc2f4573fbbf8239bb0b8aedbf8dad16282da5c9125dba1c059d0df8abf621078f02d6c4bc86d40845ac1d59710c45f07d585eb48b32fc0167ba256e73ca3b9311c62d109497957d8dbe10aa3e866b40c0baa2bc492c19ad1e6372d9622bf163fbffeaeee796a3cd9b6fbbfa4d792f34d7fd6e763cd5859dd26833d21d9bc5452bd19515dff9f4995b35bc0c1f876e6ad11f2452dc9ae85aec01fc56f8cbfda75a7727b75ebbd6bbffb43b63a3b1b671e40feb0db002974a3c3b1a788567231bf6399ff89236981149d423802d2341a3bedb9ddcbac1fe7b6435e1479c72e7089d029e7fbbaf3cf37e9b9a6b776791e4c5e6fda57e8d5f14c8c35a2d270846b9dbe005cda16af4408f3ab06a916eeeb9c9594b70424a4c1d171295b6763b22f47f80b53ccbb904bd68fd65fbd3fbdea1035e98c21a7dbc91a9b5bc7690f05ec317c97f8764eb48e911d428ec8d861b708e8298acb62155145155ae95f0a1d1501034753146e22d05f586d7f6b4fe12dad9a17f5db70b1db96b8d9a83edadc966c8a5466b61fc998c31f1070d9a5c9a6d268d304fe6b8fd3b4010348611abdcbd49fe4f85b623c7828c71382e1034ea67bc8ae97404b0c50b2a04f559e49950afcb0ef462a2ae024b0f0224dfd73684b88c7fbe92d02b68f759c4752663cd7b97a14943649305521326bde085630864629291bae25ff8822a14c4b666a9259ad0dc42a8290ac7bc7f53a16f379f758e5de750f04fd7cad47701c8597f97888bea6fa0bf2999956fbfd0ee68ec36e4688809ae231eb8bc4369f5fe1573f57e099d9c09901bf39caac48dc11956a8ae905ead86954547c448ae43d315e669c4242da565938f417bf43ce7b2b30b1cd4018388e1a910f0fc41fb0877a5925e466819d375b0a912d4fe843b76ef6f223f0f7c894f38f7ab780dfd75f669c8c06cffa0000000000000000000000000000000043eb47565a50e3b1fa45ad61ce9a1c4727b7aaa53562f523e73952bbf33d8a4104078ade3eaaa49699a69fdf1c5ac7732146ee5e1d6b6ca9b9180f964cc9d0878ae1373524d7d510e58227df6de9d30d271867640177b0f1856e28d5c8afb095ef6184fed651589022eeaea4c0ce1fa6f085092b04979489172b3ef8194a798df5724d6b05f1ae000013a08d612bca8a8c31443c10346dbf61de8475c0bbec5104b47556af3d514458e2321d146071789d2335934a680614e83562f82dfd405b54a45eb32c165448d4d5d61ca2859585369f53f1a137e9e82b67b8fdaf01bda54a317311896ae10280a032440c420a421e944d1e952b70d5826cd3b08b7db9630fe4fd5f22125de840fcc40b98038af11d55be25432597b4b65b9ec1c7a8bbfd052cbf7e1c1785314934b262d5853754f1f17771cfb7503072655753fa3f54ecc587e9f83b581916092df26e63e18994cb0db91a0bbdc7b6119b32222adf5e61d8d8ae89dae4954b54813bb33f08d562ba513fee1b09c0fcd516055419474dd7fda038a89c84ea7b9468287f0eb0c10c4b132520194d3d8d5351fc10d09c15c8cc101aa1663bbf17b84111f38bb439f07353bdea3596d15e713e1e2e7d3f1c383135b47fa7f81f46df7a902a404699ec912f5656c35b85763e4de583aecaa1dfd5d2677d9c8ffee877f63f40a5ca0d67f6e554124739f805af876aeede53aa8b0f8e5604a73c30cbd09dad963d6f8a5dcc40def40797342113ba206fae8ebe4f3bc3caf69259e462eff9ba8b3f4bfaa1300c26925a8729cd32915bfc966086f0d5560bbe32a598c22adfb48cef72ba5d4287c0cefbacfd8ce195b4963c34a94bba7a175dae4bbe3ef4863d53708915090f47a068e227433f9e49d3aa09e356d8d66d0c0121e91a3c4aa3f27fa1b63396e2b41db908fdab8b18cc7304e94e970568f9421c0dbbbaf84598d972b0534f48a5e52670436aaa776ed2482ad703430201e53443c36dcfd34a0cb6637876105e79bf3bd58ec148cb64970e3223a91f71dfcfd5a04b667fbaf3d4b3b908b9828820dfecdd753750b5f9d2216e56c615272f854464c0ca4b1e85aedd038292c4e1a57744ebba010b9ebfbb011bd6f0b78805025d27f3c17746bae116c15d9f471f0f6288a150647b2afe9df7cccf01f5cde5f04680bbfed87f6cf429fb27ad6babe791766611cf5bc20e48bef119259b9b8a0e39c3df28cb9582ea338601cdc481b32fb82adeebb3dade25d1a3df20c37e712506b5d996c49a9f0f30ddcb91fe9004e1e83294a6c9203d94e8dc2cbb449de4155032604e47997016b304fd437d8235045e255a19b743a0a9f2e336b44cae307bb3987bd3e4e777fbb34c0ab8cc3d67466c0a88dd4ccad18a07a8d1068df5b629e5718d0f6df5c957cf71bb00a5178f175caca944e635c5159f738e2402a2d21aa081e10e456afb00b9f62416c8b9c0f7228f510729e0be3f305313d77f7379dc2af24869c6c74ee4471498861d192f0ff0f508285dab6b6a36ccf7d12256cc76b95503720ac672d08268d2cf7773b6ba2a5f664847bf707f2fc10c98f2f006ec22ccb5a8c8b7c40c7c2d49a6639b9f2ce33c25c04bc461e744dfa536b00d94baddf4f4d14044c695a33881477df124f0fcf206a9fb2e65e304cdbf0c4d2390170c130ab849c2f22b5cdd3921640c8cf1976ae1010b0dfd9cb2543e45f99749cc4d61f2e8aabfe98bd905fa39951b33ea769c45ab9531c57209862ad12fd76ba4807e65417b6cd12fa8ec916f013ebb8706a96effeda06c4be24b04846392e9d1e6930eae01fa21fbd700583fb598b92c8f4eb8a61aa6235db60f2841cf3a1c6ab54c67066844711d091eb931a1bd6281aedf2a0e8fab18817202a9be06402ed9cc720c16bfe881e4df4255e87afb7fc62f38116bbe03cd8a3cb11a27d568414782f47b1a44c97c680467694bc9709d32916c97e8006cbb07ba0e4180a3738038c374c4cce8f32959afb25f303f5815c4533124acf9d18940e77522ac5dc4b9570aae8f47b7f57fd8767bea1a24ae7bed65b4afdc8f1278c30e2db98fd172730ac6bbed4f1127cd32b04a95b205526cfcb4c4e1cc955175b3e8de1f5d81b18669692350aaa1a1d797617582e54d7a5b57a683b32fb1098062dad7b0c2eb518f6862e83db25e3dbaf7aed504de932acb99d735992ce62bae9ef893ff6acc0ffcf8e3483e146b9d49dd8c7835f43a37dca0787e3ec9f6605223d5ba7ae0ab9025b73bc03f7fac36c009a56d4d95d1e81d3b3ebca7e54cc1a12d127b57c8138976e791013b015f06a624f521b6ee04ec980893c7e5e01a336203594094f82833d7445fe2d09130f63511da54832de9136b39f4599f5aa5dfbb45da60cdceab7eefde89be63f3f7c0d2324847cce1405def7c469b0e272494e5df54f568656cb9c8818d92b72b8bc34db7bb3112487e746eefe4e808bbb287d99bf07d00dabededc5e5f074ffeae0cba7da3a516c173be1c513323e119f635e8209a074b216b7023fadc2d25949c90037e71e3e550726d210a2c688342e52440635e9cc14afe10102621a9c9accb782e9e4a5fa87f0a956f5b";
+        let raw = ZIP243_SAPLING_TX;
 		let t: Transaction = raw.into();
 		assert_eq!(t.version, 4);
 		assert!(t.overwintered);
@@ -552,9 +1489,63 @@ mod tests {
         assert_eq!(t.shielded_outputs.len(), 1);
         assert_eq!(t.join_splits.len(), 2);
         assert!(t.zcash);
+		// no transparent inputs, but shielded spends and join splits
+		assert!(!t.is_empty());
 
         let serialized = serialize(&t);
         assert_eq!(Bytes::from(raw), serialized);
+
+		let mut without_shielded_inputs = t.clone();
+		without_shielded_inputs.shielded_spends.clear();
+		assert!(!without_shielded_inputs.is_empty());
+		without_shielded_inputs.join_splits.clear();
+		assert!(without_shielded_inputs.is_empty());
+		assert!(without_shielded_inputs.is_shielded());
+	}
+
+	#[test]
+	fn test_transaction_shielded_value_balance() {
+		let t: Transaction = ZIP243_SAPLING_TX.into();
+		assert!(t.is_shielded());
+		assert_eq!(t.shielded_value_balance(), 1666019459801412);
+
+		let mut negative = t.clone();
+		negative.value_balance = -100_000_000i64 as u64;
+		assert_eq!(negative.shielded_value_balance(), -100_000_000);
+		let raw = serialize(&negative);
+		// little endian two's complement
+		assert!(raw.windows(8).any(|w| w == [0x00, 0x1f, 0x0a, 0xfa, 0xff, 0xff, 0xff, 0xff]));
+		let deserialized: Transaction = deserialize(&raw as &[u8]).unwrap();
+		assert_eq!(deserialized.shielded_value_balance(), -100_000_000);
+		assert_eq!(serialize(&deserialized), raw);
+
+		let mut transparent = t;
+		transparent.shielded_spends.clear();
+		transparent.shielded_outputs.clear();
+		transparent.join_splits.clear();
+		assert!(!transparent.is_shielded());
+	}
+
+	#[test]
+	fn test_transaction_serde_sapling_transparent_only() {
+		let raw = "0400008085202f8901a8c685478265f4c14dada651969c45a65e1aeb8cd6791f2f5bb6a1d9952104d9010000006b483045022100a61e5d557568c2ddc1d9b03a7173c6ce7c996c4daecab007ac8f34bee01e6b9702204d38fdc0bcf2728a69fde78462a10fb45a9baa27873e6a5fc45fb5c76764202a01210365ffea3efa3908918a8b8627724af852fc9b86d7375b103ab0543cf418bcaa7ffeffffff02005a6202000000001976a9148132712c3ff19f3a151234616777420a6d7ef22688ac8b959800000000001976a9145453e4698f02a38abdaa521cd1ff2dee6fac187188ac29b0040048b004000000000000000000000000";
+		let t: Transaction = raw.into();
+		assert_eq!(t.version, 4);
+		assert!(t.overwintered);
+		assert!(t.zcash);
+		assert_eq!(t.value_balance, 0);
+		assert_eq!(t.shielded_value_balance(), 0);
+		assert!(!t.is_shielded());
+		assert!(t.shielded_spends.is_empty());
+		assert!(t.shielded_outputs.is_empty());
+		assert!(t.join_splits.is_empty());
+		assert_eq!(t.binding_sig, H512::default());
+
+		// ends with empty value balance, shielded spends, shielded outputs and join splits, without binding sig
+		let serialized = serialize(&t);
+		assert_eq!(&serialized[serialized.len() - 11..], &[0u8; 11]);
+		assert_eq!(serialized, Bytes::from(raw));
+		assert_eq!(t.serialized_size(), serialized.len());
 	}
 
 	// https://github.com/artemii235/SuperNET/issues/342
@@ -677,14 +1668,14 @@ mod tests {
 
 	#[test]
 	fn test_transaction_hash() {
-		let t: Transaction = "0100000001a6b97044d03da79c005b20ea9c0e1a6d9dc12d9f7b91a5911c9030a439eed8f5000000004948304502206e21798a42fae0e854281abd38bacd1aeed3ee3738d9e1446618c4571d1090db022100e2ac980643b0b82c0e88ffdfec6b64e3e6ba35e7ba5fdd7d5d6cc8d25c6b241501ffffffff0100f2052a010000001976a914404371705fa9bd789a2fcd52d2c580b65d35549d88ac00000000".into();
+		let t: Transaction = BLOCK_80000_TX.into();
 		let hash = H256::from_reversed_str("5a4ebf66822b0b2d56bd9dc64ece0bc38ee7844a23ff1d7320a88c5fdb2ad3e2");
 		assert_eq!(t.hash(), hash);
 	}
 
 	#[test]
 	fn test_transaction_serialized_len() {
-		let raw_tx: &'static str = "0100000001a6b97044d03da79c005b20ea9c0e1a6d9dc12d9f7b91a5911c9030a439eed8f5000000004948304502206e21798a42fae0e854281abd38bacd1aeed3ee3738d9e1446618c4571d1090db022100e2ac980643b0b82c0e88ffdfec6b64e3e6ba35e7ba5fdd7d5d6cc8d25c6b241501ffffffff0100f2052a010000001976a914404371705fa9bd789a2fcd52d2c580b65d35549d88ac00000000";
+		let raw_tx = BLOCK_80000_TX;
 		let tx: Transaction = raw_tx.into();
 		assert_eq!(tx.serialized_size(), raw_tx.len() / 2);
 	}
@@ -740,6 +1731,246 @@ mod tests {
 		assert_eq!(actual, expected);
 	}
 
+	#[test]
+	fn test_transaction_serialize_bounded() {
+		let raw_tx = BLOCK_80000_TX;
+		let tx: Transaction = raw_tx.into();
+		assert_eq!(tx.serialize_bounded(raw_tx.len() / 2), Ok(serialize(&tx)));
+		assert_eq!(tx.serialize_bounded(raw_tx.len() / 2 - 1), Err(Error::TooLarge));
+
+		let huge = Transaction {
+			outputs: vec![TransactionOutput {
+				value: 0,
+				script_pubkey: vec![0u8; 4_000_000].into(),
+			}],
+			..Default::default()
+		};
+		assert_eq!(huge.serialize_bounded(1000), Err(Error::TooLarge));
+	}
+
+	#[test]
+	fn test_transaction_weight_and_vsize() {
+		// test case from https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki
+		let raw_tx: &'static str = "01000000000102fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f00000000494830450221008b9d1dc26ba6a9cb62127b02742fa9d754cd3bebf337f7a55d114c8e5cdd30be022040529b194ba3f9281a99f2b1c0a19c0489bc22ede944ccf4ecbab4cc618ef3ed01eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac000247304402203609e17b84f6a7d30c80bfa610b5b4542f32a8a0d5447a12fb1366d7f01cc44a0220573a954c4518331561406f90300e8f3358f51928d43c212a8caed02de67eebee0121025476c2e83188368da1ff3e292e7acafcdb3566bb0ad253f62fc70f07aeee635711000000";
+		let tx: Transaction = raw_tx.into();
+		assert_eq!(tx.serialized_size_with_flags(SERIALIZE_TRANSACTION_WITNESS), raw_tx.len() / 2);
+		assert_eq!(tx.serialized_size(), 233);
+		assert_eq!(tx.weight(), 233 * 3 + 343);
+		assert_eq!(tx.vsize(), 261);
+
+		let raw_tx = BLOCK_80000_TX;
+		let tx: Transaction = raw_tx.into();
+		assert_eq!(tx.weight(), raw_tx.len() / 2 * 4);
+		assert_eq!(tx.vsize(), raw_tx.len() / 2);
+	}
+
+	#[test]
+	fn test_transaction_base_and_witness_size() {
+		// test case from https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki
+		let raw_tx: &'static str = "01000000000102fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f00000000494830450221008b9d1dc26ba6a9cb62127b02742fa9d754cd3bebf337f7a55d114c8e5cdd30be022040529b194ba3f9281a99f2b1c0a19c0489bc22ede944ccf4ecbab4cc618ef3ed01eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac000247304402203609e17b84f6a7d30c80bfa610b5b4542f32a8a0d5447a12fb1366d7f01cc44a0220573a954c4518331561406f90300e8f3358f51928d43c212a8caed02de67eebee0121025476c2e83188368da1ff3e292e7acafcdb3566bb0ad253f62fc70f07aeee635711000000";
+		let tx: Transaction = raw_tx.into();
+		assert_eq!(tx.base_size(), 233);
+		assert_eq!(tx.witness_size(), 110);
+		assert_eq!(tx.base_size() + tx.witness_size(), raw_tx.len() / 2);
+
+		// empty witness of the first input still takes one byte
+		assert_eq!(tx.inputs[0].witness_size(), 1);
+		assert_eq!(tx.inputs[1].witness_size(), 1 + 72 + 34);
+		// witness marker and flag
+		assert_eq!(tx.witness_size(), 2 + tx.inputs.iter().map(TransactionInput::witness_size).sum::<usize>());
+
+		// version, input count, output count and lock time
+		let components = tx.inputs.iter().map(Serializable::serialized_size)
+			.chain(tx.outputs.iter().map(Serializable::serialized_size))
+			.sum::<usize>();
+		assert_eq!(tx.base_size(), 4 + 1 + 1 + 4 + components);
+
+		let mut without_witness = tx.clone();
+		without_witness.inputs[1].script_witness.clear();
+		assert_eq!(without_witness.witness_size(), 0);
+		assert_eq!(without_witness.base_size(), tx.base_size());
+	}
+
+	#[test]
+	fn test_input_output_weight() {
+		let raw_tx: &'static str = "01000000000102fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f00000000494830450221008b9d1dc26ba6a9cb62127b02742fa9d754cd3bebf337f7a55d114c8e5cdd30be022040529b194ba3f9281a99f2b1c0a19c0489bc22ede944ccf4ecbab4cc618ef3ed01eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac000247304402203609e17b84f6a7d30c80bfa610b5b4542f32a8a0d5447a12fb1366d7f01cc44a0220573a954c4518331561406f90300e8f3358f51928d43c212a8caed02de67eebee0121025476c2e83188368da1ff3e292e7acafcdb3566bb0ad253f62fc70f07aeee635711000000";
+		let tx: Transaction = raw_tx.into();
+		// empty witness of the first input still takes one byte
+		assert_eq!(tx.inputs[0].weight(true), (32 + 4 + 74 + 4) * 4 + 1);
+		assert_eq!(tx.inputs[1].weight(false), (32 + 4 + 1 + 4) * 4);
+		assert_eq!(tx.outputs[0].weight(), 34 * 4);
+
+		// version, input count, output count and lock time, plus witness marker and flag
+		let overhead = (4 + 1 + 1 + 4) * 4 + 2;
+		let inputs_weight: usize = tx.inputs.iter().map(|input| input.weight(true)).sum();
+		let outputs_weight: usize = tx.outputs.iter().map(|output| output.weight()).sum();
+		assert_eq!(overhead + inputs_weight + outputs_weight, tx.weight());
+	}
+
+	#[test]
+	fn test_transaction_output_is_null() {
+		let null = TransactionOutput::default();
+		assert!(null.is_null());
+		assert_eq!(serialize(&null), "ffffffffffffffff00".into());
+
+		let output = TransactionOutput {
+			value: 5000000000,
+			script_pubkey: "76a914404371705fa9bd789a2fcd52d2c580b65d35549d88ac".into(),
+		};
+		assert!(!output.is_null());
+		let output = TransactionOutput {
+			script_pubkey: "6a".into(),
+			..TransactionOutput::default()
+		};
+		assert!(!output.is_null());
+	}
+
+	fn fee_transaction(input_count: usize, output_values: &[u64]) -> Transaction {
+		Transaction {
+			inputs: vec![TransactionInput::coinbase(Bytes::default()); input_count],
+			outputs: output_values.iter().map(|value| TransactionOutput {
+				value: *value,
+				script_pubkey: Bytes::default(),
+			}).collect(),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn test_transaction_output_op_return() {
+		let output = TransactionOutput::op_return(b"07dd15c4");
+		assert_eq!(output.value, 0);
+		assert_eq!(output.script_pubkey, "6a083037646431356334".into());
+		assert_eq!(output.op_return_data(), Some("3037646431356334".into()));
+		assert!(output.is_standard_op_return());
+
+		let data = [0xab; 81];
+		let output = TransactionOutput::op_return(&data);
+		assert_eq!(&output.script_pubkey[..3], &[0x6a, 0x4c, 81]);
+		assert_eq!(output.op_return_data(), Some(data.to_vec().into()));
+		assert!(!output.is_standard_op_return());
+		assert!(TransactionOutput::op_return(&data[..80]).is_standard_op_return());
+
+		let data = vec![0xcd; 300];
+		let output = TransactionOutput::op_return(&data);
+		assert_eq!(&output.script_pubkey[..4], &[0x6a, 0x4d, 0x2c, 0x01]);
+		assert_eq!(output.op_return_data(), Some(data.into()));
+
+		assert_eq!(TransactionOutput::op_return(&[]).op_return_data(), Some(Bytes::new()));
+
+		let p2pkh = TransactionOutput {
+			value: 0,
+			script_pubkey: "76a914404371705fa9bd789a2fcd52d2c580b65d35549d88ac".into(),
+		};
+		assert_eq!(p2pkh.op_return_data(), None);
+		assert!(!p2pkh.is_standard_op_return());
+		let truncated = TransactionOutput {
+			value: 0,
+			script_pubkey: "6a0830376464".into(),
+		};
+		assert_eq!(truncated.op_return_data(), None);
+	}
+
+	#[test]
+	fn test_transaction_check_weight_limit() {
+		let t: Transaction = BLOCK_80000_TX.into();
+		assert_eq!(t.check_weight_limit(MAX_STANDARD_TX_WEIGHT), Ok(()));
+		assert_eq!(t.check_weight_limit(t.weight()), Ok(()));
+		assert_eq!(t.check_weight_limit(t.weight() - 1), Err(TxError::Overweight(t.weight())));
+
+		let mut oversized = t.clone();
+		oversized.outputs = vec![t.outputs[0].clone(); 3500];
+		assert!(oversized.weight() > MAX_STANDARD_TX_WEIGHT);
+		assert_eq!(oversized.check_weight_limit(MAX_STANDARD_TX_WEIGHT), Err(TxError::Overweight(oversized.weight())));
+	}
+
+	#[test]
+	fn test_transaction_has_duplicate_inputs() {
+		let t: Transaction = BLOCK_80000_TX.into();
+		assert!(!t.has_duplicate_inputs());
+
+		let mut other_output = t.clone();
+		let mut input = t.inputs[0].clone();
+		input.previous_output.index = 1;
+		other_output.inputs.push(input);
+		assert!(!other_output.has_duplicate_inputs());
+
+		let mut duplicate = other_output.clone();
+		let mut input = t.inputs[0].clone();
+		input.script_sig = Bytes::default();
+		duplicate.inputs.push(input);
+		assert!(duplicate.has_duplicate_inputs());
+	}
+
+	#[test]
+	fn test_transaction_input_detected_sighash() {
+		let t: Transaction = BLOCK_80000_TX.into();
+		assert_eq!(t.inputs[0].detected_sighash(), Some(1));
+
+		let mut input = t.inputs[0].clone();
+		// multisig scriptSig starting with OP_0 and using SIGHASH_SINGLE | SIGHASH_ANYONECANPAY
+		let mut script_sig = vec![0x00];
+		script_sig.extend_from_slice(&input.script_sig[..input.script_sig.len() - 1]);
+		script_sig.push(0x83);
+		input.script_sig = script_sig.into();
+		assert_eq!(input.detected_sighash(), Some(0x83));
+
+		assert_eq!(TransactionInput::coinbase("0401020304".into()).detected_sighash(), None);
+		assert_eq!(TransactionInput::default().detected_sighash(), None);
+	}
+
+	#[test]
+	fn test_transaction_rbf_signaling() {
+		let input = |sequence| TransactionInput { sequence, ..Default::default() };
+		assert!(!input(0xffffffff).signals_rbf());
+		assert!(!input(0xfffffffe).signals_rbf());
+		assert!(input(0xfffffffd).signals_rbf());
+		assert!(input(0x00000000).signals_rbf());
+
+		let mut tx = Transaction {
+			inputs: vec![input(0xffffffff), input(0xfffffffe)],
+			..Default::default()
+		};
+		assert!(!tx.is_rbf_signaling());
+		tx.inputs.push(input(0x00000000));
+		assert!(tx.is_rbf_signaling());
+		assert!(!Transaction::default().is_rbf_signaling());
+	}
+
+	#[test]
+	fn test_transaction_fee() {
+		let tx = fee_transaction(2, &[30, 50]);
+		assert_eq!(tx.fee(&[40, 60]), Ok(20));
+		assert_eq!(tx.fee(&[40, 40]), Ok(0));
+		assert_eq!(tx.fee(&[40, 39]), Err(FeeError::InsufficientInputs));
+		assert_eq!(tx.fee(&[100]), Err(FeeError::InputAmountsMismatch));
+		assert_eq!(tx.fee(&[u64::MAX, 1]), Err(FeeError::InputAmountsOverflow));
+	}
+
+	#[test]
+	fn test_transaction_meets_min_relay_fee() {
+		let t: Transaction = BLOCK_80000_TX.into();
+		assert_eq!(t.vsize(), 158);
+		// 158 * 1000 / 1000
+		assert!(!t.meets_min_relay_fee(157, 1000));
+		assert!(t.meets_min_relay_fee(158, 1000));
+		// 158 * 1500 / 1000
+		assert!(!t.meets_min_relay_fee(236, 1500));
+		assert!(t.meets_min_relay_fee(237, 1500));
+		assert!(t.meets_min_relay_fee(0, 0));
+	}
+
+	#[test]
+	fn test_transaction_total_spends_saturates() {
+		let tx = fee_transaction(1, &[u64::MAX - 1, 2]);
+		assert_eq!(tx.total_spends(), u64::MAX);
+		assert_eq!(tx.fee(&[u64::MAX]), Err(FeeError::OutputsOverflow));
+
+		let tx = fee_transaction(1, &[u64::MAX - 1, 1]);
+		assert_eq!(tx.total_spends(), u64::MAX);
+		assert_eq!(tx.fee(&[u64::MAX - 1]), Err(FeeError::InsufficientInputs));
+	}
+
 	#[test]
 	fn test_serialization_with_flags() {
 		let transaction_without_witness: Transaction = "000000000100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000".into();
@@ -758,6 +1989,43 @@ mod tests {
 		assert!(transaction_with_witness.hash() != transaction_with_witness.witness_hash());
 	}
 
+	#[test]
+	fn test_txid_ignores_witness() {
+		let mut transaction: Transaction = "0000000000010100000000000000000000000000000000000000000000000000000000000000000000000000000000000001010000000000".into();
+		let txid = transaction.txid();
+		let wtxid = transaction.wtxid();
+		assert_eq!(txid, transaction.hash());
+		assert_eq!(wtxid, transaction.witness_hash());
+
+		transaction.inputs[0].script_witness = vec!["0102".into()];
+		assert_eq!(transaction.txid(), txid);
+		assert!(transaction.wtxid() != wtxid);
+
+		transaction.inputs[0].script_witness.clear();
+		assert_eq!(transaction.txid(), txid);
+		assert_eq!(transaction.wtxid(), txid);
+	}
+
+	#[test]
+	fn test_witness_hash_mixed_inputs() {
+		// test case from https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki
+		// first input has empty witness stack, second one spends P2WPKH
+		let raw: Bytes = "01000000000102fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f00000000494830450221008b9d1dc26ba6a9cb62127b02742fa9d754cd3bebf337f7a55d114c8e5cdd30be022040529b194ba3f9281a99f2b1c0a19c0489bc22ede944ccf4ecbab4cc618ef3ed01eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac000247304402203609e17b84f6a7d30c80bfa610b5b4542f32a8a0d5447a12fb1366d7f01cc44a0220573a954c4518331561406f90300e8f3358f51928d43c212a8caed02de67eebee0121025476c2e83188368da1ff3e292e7acafcdb3566bb0ad253f62fc70f07aeee635711000000".into();
+		let mut transaction: Transaction = deserialize(raw.as_ref()).unwrap();
+		assert!(transaction.inputs[0].script_witness.is_empty());
+		assert!(!transaction.inputs[1].script_witness.is_empty());
+
+		let serialized = serialize_with_flags(&transaction, SERIALIZE_TRANSACTION_WITNESS);
+		assert_eq!(serialized, raw);
+		assert_eq!(serialize_with_flags(&transaction, SERIALIZE_TRANSACTION_WITNESS), serialized);
+
+		assert_eq!(transaction.hash(), H256::from_reversed_str("e8151a2af31c368a35053ddd4bdb285a8595c769a3ad83e0fa02314a602d4609"));
+		assert_eq!(transaction.witness_hash(), H256::from_reversed_str("c36c38370907df2324d9ce9d149d191192f338b37665a82e78e76a12c909b762"));
+
+		transaction.inputs[1].script_witness.clear();
+		assert_eq!(transaction.witness_hash(), transaction.hash());
+	}
+
 	// BLK is PoS coin having nTime field in transaction
 	#[test]
 	fn blk_transaction() {
@@ -786,6 +2054,50 @@ mod tests {
 		assert_eq!(serialize(&t).to_hex::<String>(), transaction);
 	}
 
+	fn relative_locktime_transaction(sequence: u32) -> Transaction {
+		Transaction {
+			version: 2,
+			inputs: vec![TransactionInput {
+				sequence,
+				..Default::default()
+			}],
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn test_relative_locktime_height() {
+		// 10 blocks relative lock
+		let t = relative_locktime_transaction(10);
+		assert!(!t.relative_locktime_satisfied(&[100], &[0], 109, 0));
+		assert!(t.relative_locktime_satisfied(&[100], &[0], 110, 0));
+		assert!(t.relative_locktime_satisfied(&[100], &[0], 111, 0));
+	}
+
+	#[test]
+	fn test_relative_locktime_time() {
+		// 2 * 512 seconds relative lock
+		let t = relative_locktime_transaction(SEQUENCE_LOCKTIME_TYPE_FLAG | 2);
+		assert!(!t.relative_locktime_satisfied(&[0], &[1_000_000], 0, 1_000_000 + 1023));
+		assert!(t.relative_locktime_satisfied(&[0], &[1_000_000], 0, 1_000_000 + 1024));
+	}
+
+	#[test]
+	fn test_relative_locktime_disabled() {
+		let t = relative_locktime_transaction(SEQUENCE_LOCKTIME_DISABLE_FLAG | 10);
+		assert!(t.relative_locktime_satisfied(&[100], &[0], 100, 0));
+
+		let mut t = relative_locktime_transaction(10);
+		t.version = 1;
+		assert!(t.relative_locktime_satisfied(&[100], &[0], 100, 0));
+	}
+
+	#[test]
+	fn test_relative_locktime_mismatched_inputs() {
+		let t = relative_locktime_transaction(0);
+		assert!(!t.relative_locktime_satisfied(&[], &[], 100, 0));
+	}
+
     #[test]
     // https://kmdexplorer.io/tx/687acd73ad23ce93e7ddabeece8eb228a0a0e15e4d265f7c717d7458ddce9bdd
     fn kmd_687acd73ad23ce93e7ddabeece8eb228a0a0e15e4d265f7c717d7458ddce9bdd() {
@@ -803,4 +2115,35 @@ mod tests {
         println!("{:?}", t);
         assert_eq!(transaction, serialize(&t).to_hex::<String>());
     }
+
+	fn join_split(v_pub_old: u64, v_pub_new: u64) -> JoinSplit {
+		JoinSplit::new(
+			v_pub_old,
+			v_pub_new,
+			H256::default(),
+			[H256::default(), H256::default()],
+			[H256::default(), H256::default()],
+			H256::default(),
+			H256::default(),
+			[H256::default(), H256::default()],
+			JoinSplitProof::Groth(Default::default()),
+			[Default::default(), Default::default()],
+		)
+	}
+
+	#[test]
+	fn test_join_split_is_consistent() {
+		assert!(join_split(0, 0).is_consistent());
+		assert!(join_split(100000, 0).is_consistent());
+		assert!(join_split(0, 100000).is_consistent());
+		assert!(!join_split(100000, 1).is_consistent());
+	}
+
+	#[test]
+	fn test_join_split_new_v_pub_little_endian() {
+		let js = join_split(0x0102030405060708, 1);
+		let serialized = serialize(&js);
+		assert_eq!(&serialized[0..8], &[8, 7, 6, 5, 4, 3, 2, 1]);
+		assert_eq!(&serialized[8..16], &[1, 0, 0, 0, 0, 0, 0, 0]);
+	}
 }
@@ -1,4 +1,6 @@
 extern crate groestl;
+extern crate hmac;
+extern crate pbkdf2;
 extern crate primitives;
 extern crate siphasher;
 extern crate ripemd160;
@@ -8,9 +10,10 @@ extern crate sha3;
 
 use std::hash::Hasher;
 use groestl::Groestl512;
+use hmac::{Hmac, Mac};
 use ripemd160::Ripemd160;
 use sha1::Sha1;
-use sha2::{Sha256, Digest};
+use sha2::{Sha256, Sha512, Digest};
 use sha3::{Keccak256};
 use siphasher::sip::SipHasher24;
 use primitives::hash::{H32, H160, H256, H512};
@@ -56,6 +59,28 @@ pub fn sha256(input: &[u8]) -> H256 {
 	(*hasher.result()).into()
 }
 
+/// SHA-512
+#[inline]
+pub fn sha512(input: &[u8]) -> H512 {
+	let mut hasher = Sha512::new();
+	hasher.input(input);
+	(*hasher.result()).into()
+}
+
+/// HMAC-SHA256
+pub fn hmac_sha256(key: &[u8], input: &[u8]) -> H256 {
+	let mut mac = Hmac::<Sha256>::new_varkey(key).expect("HMAC accepts keys of any length; qed");
+	mac.input(input);
+	(*mac.result().code()).into()
+}
+
+/// PBKDF2 with HMAC-SHA512 as the pseudorandom function
+pub fn pbkdf2_hmac_sha512(password: &[u8], salt: &[u8], iterations: usize) -> H512 {
+	let mut result = H512::default();
+	pbkdf2::pbkdf2::<Hmac<Sha512>>(password, salt, iterations, &mut *result);
+	result
+}
+
 /// Groestl-512
 #[inline]
 pub fn groestl512(input: &[u8]) -> H512 {
@@ -90,6 +115,16 @@ pub fn dhash256(input: &[u8]) -> H256 {
 	sha256(&*sha256(input))
 }
 
+/// BIP340 tagged hash: SHA-256(SHA-256(tag) || SHA-256(tag) || input)
+pub fn tagged_hash(tag: &[u8], input: &[u8]) -> H256 {
+	let tag_hash = sha256(tag);
+	let mut hasher = Sha256::new();
+	hasher.input(*tag_hash);
+	hasher.input(*tag_hash);
+	hasher.input(input);
+	(*hasher.result()).into()
+}
+
 /// SipHash-2-4
 #[inline]
 pub fn siphash24(key0: u64, key1: u64, input: &[u8]) -> u64 {
@@ -119,7 +154,8 @@ pub fn checksum(data: &[u8], sum_type: &ChecksumType) -> H32 {
 #[cfg(test)]
 mod tests {
 	use primitives::bytes::Bytes;
-	use super::{ripemd160, sha1, sha256, dhash160, dhash256, siphash24, checksum};
+	use super::{ripemd160, sha1, sha256, sha512, hmac_sha256, pbkdf2_hmac_sha512, dhash160, dhash256, tagged_hash, siphash24,
+		checksum};
 	use ChecksumType;
 
 	#[test]
@@ -143,6 +179,28 @@ mod tests {
 		assert_eq!(result, expected);
 	}
 
+	#[test]
+	fn test_sha512() {
+		let expected = "9b71d224bd62f3785d96d46ad3ea3d73319bfbc2890caadae2dff72519673ca72323c3d99ba5c11d7c7acc6e14b8c5da0c4663475c2e5c3adef46f73bcdec043".into();
+		let result = sha512(b"hello");
+		assert_eq!(result, expected);
+	}
+
+	#[test]
+	fn test_hmac_sha256() {
+		// https://tools.ietf.org/html/rfc4231#section-4.3
+		let expected = "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843".into();
+		let result = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+		assert_eq!(result, expected);
+	}
+
+	#[test]
+	fn test_pbkdf2_hmac_sha512() {
+		let expected = "867f70cf1ade02cff3752599a3a53dc4af34c7a669815ae5d513554e1c8cf252c02d470a285a0501bad999bfe943c08f050235d7d68b1da55e63f73b60a57fce".into();
+		let result = pbkdf2_hmac_sha512(b"password", b"salt", 1);
+		assert_eq!(result, expected);
+	}
+
 	#[test]
 	fn test_dhash160() {
 		let expected = "b6a9c8c230722b7c748331a8b450f05566dc7d0f".into();
@@ -162,6 +220,13 @@ mod tests {
 		assert_eq!(result, expected);
 	}
 
+	#[test]
+	fn test_tagged_hash() {
+		let expected = "38c140830ab88bf8dac2e03f0d765b6677949e5a9aab799378f49dde69c71926".into();
+		let result = tagged_hash(b"TapTweak", b"hello");
+		assert_eq!(result, expected);
+	}
+
 	#[test]
 	fn test_siphash24() {
 		let expected = 0x74f839c593dc67fd_u64;
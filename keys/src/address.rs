@@ -9,8 +9,8 @@ use std::fmt;
 use std::str::FromStr;
 use std::ops::Deref;
 use base58::{ToBase58, FromBase58};
-use crypto::{ChecksumType, checksum, dhash256, dgroestl512, keccak256};
-use {DisplayLayout, Error, AddressHash};
+use crypto::{ChecksumType, checksum, dhash160, dhash256, dgroestl512, keccak256};
+use {bech32, registry, DisplayLayout, Error, AddressHash, KeyPair, Network, Public};
 
 /// There are two address formats currently in use.
 /// https://bitcoin.org/en/developer-reference#address-conversion
@@ -24,6 +24,27 @@ pub enum Type {
 	/// Newer P2SH type starting with the number 3, eg: 3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy.
 	/// https://bitcoin.org/en/glossary/p2sh-address
 	P2SH,
+	/// Pay to Witness PubKey Hash
+	/// Native segwit v0 address, bech32 encoded, eg: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4.
+	/// https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki
+	P2WPKH,
+	/// Pay to Witness Script Hash
+	/// Native segwit v0 address with 32 bytes script hash, eg: bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3.
+	P2WSH,
+	/// Segwit address of witness version 1 and above, eg: taproot outputs, bech32m encoded.
+	/// https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki
+	WitnessUnknown,
+}
+
+/// Witness program of bech32 encoded segwit address.
+#[derive(Debug, PartialEq, Clone)]
+pub struct WitnessProgram {
+	/// Human readable part of the address.
+	pub hrp: String,
+	/// Witness version, 0 to 16.
+	pub version: u8,
+	/// Witness program, 2 to 40 bytes, 20 or 32 bytes for version 0.
+	pub program: Vec<u8>,
 }
 
 /// `AddressHash` with prefix and t addr zcash prefix
@@ -33,10 +54,169 @@ pub struct Address {
 	pub prefix: u8,
 	/// T addr prefix, additional prefix used by Zcash and some forks
 	pub t_addr_prefix: u8,
-	/// Public key hash.
+	/// Public key hash. Witness program of P2WPKH addresses, zeroed for other segwit addresses.
 	pub hash: AddressHash,
 	/// Checksum type
 	pub checksum_type: ChecksumType,
+	/// Witness program of bech32 encoded segwit address. None for base58 addresses.
+	/// Prefixes and checksum type of segwit addresses are left default.
+	pub witness: Option<WitnessProgram>,
+}
+
+impl Default for Address {
+	fn default() -> Self {
+		Address {
+			prefix: 0,
+			t_addr_prefix: 0,
+			hash: AddressHash::default(),
+			checksum_type: ChecksumType::DSHA256,
+			witness: None,
+		}
+	}
+}
+
+impl Address {
+	/// Creates address of given type using prefixes of the network.
+	/// Fails with `InvalidNetwork` for P2WPKH addresses of networks without segwit support,
+	/// and with `InvalidAddress` for other segwit types, which can't be built from 20 bytes hash.
+	pub fn new(network: Network, kind: Type, hash: AddressHash) -> Result<Self, Error> {
+		match kind {
+			Type::P2PKH => Ok(Address::base58(network, network.p2pkh_prefix(), hash)),
+			Type::P2SH => Ok(Address::base58(network, network.p2sh_prefix(), hash)),
+			Type::P2WPKH => {
+				let hrp = network.bech32_hrp().ok_or(Error::InvalidNetwork)?;
+				Ok(Address::p2wpkh(hrp, hash))
+			},
+			Type::P2WSH | Type::WitnessUnknown => Err(Error::InvalidAddress),
+		}
+	}
+
+	/// Creates bech32 encoded P2WPKH address with the human readable part.
+	pub fn p2wpkh(hrp: &str, hash: AddressHash) -> Self {
+		let program = hash.to_vec();
+		Address {
+			hash,
+			witness: Some(WitnessProgram {
+				hrp: hrp.into(),
+				version: 0,
+				program,
+			}),
+			..Default::default()
+		}
+	}
+
+	/// Creates segwit address of the witness version and program,
+	/// checking program length rules of BIP141.
+	pub fn from_witness_program(hrp: &str, version: u8, program: Vec<u8>) -> Result<Self, Error> {
+		if version > 16 || program.len() < 2 || program.len() > 40 || (version == 0 && program.len() != 20 && program.len() != 32) {
+			return Err(Error::InvalidAddress);
+		}
+
+		let mut hash = AddressHash::default();
+		if version == 0 && program.len() == 20 {
+			hash.copy_from_slice(&program);
+		}
+
+		Ok(Address {
+			hash,
+			witness: Some(WitnessProgram {
+				hrp: hrp.into(),
+				version,
+				program,
+			}),
+			..Default::default()
+		})
+	}
+
+	fn base58(network: Network, prefix: u8, hash: AddressHash) -> Self {
+		Address {
+			prefix,
+			t_addr_prefix: network.t_addr_prefix(),
+			hash,
+			checksum_type: network.checksum_type(),
+			witness: None,
+		}
+	}
+
+	/// Creates P2PKH address of the public key.
+	pub fn from_public(public: &Public, network: Network) -> Self {
+		Address::base58(network, network.p2pkh_prefix(), public.address_hash())
+	}
+
+	/// Creates P2SH address of the redeem script hash.
+	pub fn from_script_hash(hash: &AddressHash, network: Network) -> Self {
+		Address::base58(network, network.p2sh_prefix(), hash.clone())
+	}
+
+	/// Creates P2SH address of the redeem script.
+	pub fn p2sh_from_script(script: &[u8], network: Network) -> Self {
+		Address::from_script_hash(&dhash160(script), network)
+	}
+
+	/// Returns copy of the address using another checksum type,
+	/// e.g. to render the same payload for a fork which changed only its checksum.
+	pub fn with_checksum(&self, sum_type: ChecksumType) -> Address {
+		Address {
+			checksum_type: sum_type,
+			..self.clone()
+		}
+	}
+
+	/// Returns true if both addresses have the same prefixes and hash, whatever checksum type they use.
+	/// Segwit addresses match only other segwit addresses with the same human readable part and program.
+	pub fn same_payload(&self, other: &Address) -> bool {
+		self.prefix == other.prefix &&
+			self.t_addr_prefix == other.t_addr_prefix &&
+			self.hash == other.hash &&
+			self.witness == other.witness
+	}
+
+	/// Returns witness version and program of segwit addresses, `None` for base58 addresses.
+	/// The output script is `OP_n <program>`.
+	pub fn witness_program(&self) -> Option<(u8, Vec<u8>)> {
+		self.witness.as_ref().map(|witness| (witness.version, witness.program.clone()))
+	}
+
+	/// Returns type of the address, based on P2SH prefixes of known networks.
+	/// Addresses with unknown prefixes are treated as P2PKH.
+	pub fn kind(&self) -> Type {
+		if let Some(ref witness) = self.witness {
+			return match (witness.version, witness.program.len()) {
+				(0, 20) => Type::P2WPKH,
+				(0, _) => Type::P2WSH,
+				_ => Type::WitnessUnknown,
+			};
+		}
+
+		let is_p2sh = Network::all().iter()
+			.any(|n| n.t_addr_prefix() == self.t_addr_prefix && n.p2sh_prefix() == self.prefix);
+		if is_p2sh {
+			Type::P2SH
+		} else {
+			Type::P2PKH
+		}
+	}
+}
+
+/// Returns the network whose prefixes and checksum type match the address.
+pub fn identify_network(address: &Address) -> Option<Network> {
+	if let Some(ref witness) = address.witness {
+		return Network::all().iter().find(|n| n.bech32_hrp() == Some(witness.hrp.as_str())).cloned();
+	}
+
+	Network::all().iter()
+		.find(|n| {
+			(n.p2pkh_prefix() == address.prefix || n.p2sh_prefix() == address.prefix) &&
+				n.t_addr_prefix() == address.t_addr_prefix &&
+				n.checksum_type() == address.checksum_type
+		})
+		.cloned()
+}
+
+/// Parses WIF encoded private key and returns P2PKH address of its public key on the network.
+pub fn wif_to_address(wif: &str, network: &Network) -> Result<Address, Error> {
+	let keypair = KeyPair::from_private(wif.parse()?)?;
+	Ok(Address::from_public(keypair.public(), *network))
 }
 
 pub fn detect_checksum(data: &[u8], checksum: &[u8]) -> Result<ChecksumType, Error> {
@@ -54,6 +234,41 @@ pub fn detect_checksum(data: &[u8], checksum: &[u8]) -> Result<ChecksumType, Err
 	Err(Error::InvalidChecksum)
 }
 
+/// Returns true if the string is a base58check or bech32 encoded address with a valid checksum.
+/// Network prefixes and human readable parts are not checked.
+pub fn is_valid_address(s: &str) -> bool {
+	// some bech32 strings are valid base58 too, so bech32 is tried first
+	bech32::decode(s).is_ok() || is_valid_base58_address(s)
+}
+
+/// Returns bech32 addresses in lowercase, and base58 addresses unchanged, as base58 is case-sensitive.
+/// Fails for mixed-case bech32 addresses and strings which are not valid addresses.
+pub fn normalize_address_case(s: &str) -> Result<String, Error> {
+	if bech32::decode(s).is_ok() {
+		return Ok(s.to_lowercase());
+	}
+
+	if is_valid_base58_address(s) {
+		return Ok(s.to_owned());
+	}
+
+	Err(Error::InvalidAddress)
+}
+
+fn is_valid_base58_address(s: &str) -> bool {
+	let data = match s.from_base58() {
+		Ok(data) => data,
+		Err(_) => return false,
+	};
+
+	let checksum_start = match data.len() {
+		25 => 21,
+		26 => 22,
+		_ => return false,
+	};
+	detect_checksum(&data[..checksum_start], &data[checksum_start..]).is_ok()
+}
+
 pub struct AddressDisplayLayout(Vec<u8>);
 
 impl Deref for AddressDisplayLayout {
@@ -83,21 +298,20 @@ impl DisplayLayout for Address {
 	}
 
 	fn from_layout(data: &[u8]) -> Result<Self, Error> where Self: Sized {
-		match data.len() {
+		let address = match data.len() {
 			25 => {
 				let sum_type = detect_checksum(&data[0..21], &data[21..])?;
 
 				let mut hash = AddressHash::default();
 				hash.copy_from_slice(&data[1..21]);
 
-				let address = Address {
+				Address {
 					t_addr_prefix: 0,
 					prefix: data[0],
 					hash,
-                    checksum_type: sum_type,
-				};
-
-				Ok(address)
+					checksum_type: sum_type,
+					..Default::default()
+				}
 			},
 			26 => {
 				let sum_type = detect_checksum(&data[0..22], &data[22..])?;
@@ -105,23 +319,37 @@ impl DisplayLayout for Address {
 				let mut hash = AddressHash::default();
 				hash.copy_from_slice(&data[2..22]);
 
-				let address = Address {
+				Address {
 					t_addr_prefix: data[0],
 					prefix: data[1],
 					hash,
-                    checksum_type: sum_type,
-				};
-
-				Ok(address)
+					checksum_type: sum_type,
+					..Default::default()
+				}
 			},
 			_ => return Err(Error::InvalidAddress),
+		};
+
+		// reject blobs which happen to have a valid checksum, but do not belong to any network
+		if !registry::is_known_address_prefix(address.t_addr_prefix, address.prefix) {
+			return Err(Error::InvalidAddress);
 		}
+
+		Ok(address)
 	}
 }
 
 impl fmt::Display for Address {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		self.layout().to_base58().fmt(f)
+		match self.witness {
+			Some(ref witness) => {
+				let variant = if witness.version == 0 { bech32::Variant::Bech32 } else { bech32::Variant::Bech32m };
+				let mut data = vec![witness.version];
+				data.extend(bech32::convert_bits(&witness.program, 8, 5, true).expect("padding is enabled; qed"));
+				bech32::encode(&witness.hrp, &data, variant).fmt(f)
+			},
+			None => self.layout().to_base58().fmt(f),
+		}
 	}
 }
 
@@ -129,7 +357,11 @@ impl FromStr for Address {
 	type Err = Error;
 
 	fn from_str(s: &str) -> Result<Self, Error> where Self: Sized {
-		let hex = try!(s.from_base58().map_err(|_| Error::InvalidAddress));
+		if let Ok((hrp, version, program)) = bech32::decode_segwit(s) {
+			return Address::from_witness_program(&hrp, version, program);
+		}
+
+		let hex = s.from_base58().map_err(|_| Error::InvalidAddress)?;
 		Address::from_layout(&hex)
 	}
 }
@@ -142,7 +374,10 @@ impl From<&'static str> for Address {
 
 #[cfg(test)]
 mod tests {
-	use super::{Address, ChecksumType};
+	use hex::FromHex;
+	use {AddressHash, Network, Error, Public};
+	use super::{Address, Type, ChecksumType, is_valid_address, identify_network, wif_to_address,
+		normalize_address_case};
 
 	#[test]
 	fn test_address_to_string() {
@@ -151,6 +386,7 @@ mod tests {
 			t_addr_prefix: 0,
 			hash: "3f4aa1fedf1f54eeb03b759deadb36676b184911".into(),
 			checksum_type: ChecksumType::DSHA256,
+			..Default::default()
 		};
 
 		assert_eq!("16meyfSoQV6twkAAxPe51RtMVz7PGRmWna".to_owned(), address.to_string());
@@ -163,6 +399,7 @@ mod tests {
 			t_addr_prefix: 0,
 			hash: "05aab5342166f8594baf17a7d9bef5d567443327".into(),
 			checksum_type: ChecksumType::DSHA256,
+			..Default::default()
 		};
 
 		assert_eq!("R9o9xTocqr6CeEDGDH6mEYpwLoMz6jNjMW".to_owned(), address.to_string());
@@ -175,6 +412,7 @@ mod tests {
 			prefix: 37,
 			hash: "05aab5342166f8594baf17a7d9bef5d567443327".into(),
 			checksum_type: ChecksumType::DSHA256,
+			..Default::default()
 		};
 
 		assert_eq!("tmAEKD7psc1ajK76QMGEW8WGQSBBHf9SqCp".to_owned(), address.to_string());
@@ -187,6 +425,7 @@ mod tests {
 			t_addr_prefix: 0,
 			hash: "ca0c3786c96ff7dacd40fdb0f7c196528df35f85".into(),
 			checksum_type: ChecksumType::DSHA256,
+			..Default::default()
 		};
 
 		assert_eq!("bX9bppqdGvmCCAujd76Tq76zs1suuPnB9A".to_owned(), address.to_string());
@@ -199,6 +438,7 @@ mod tests {
 			t_addr_prefix: 0,
 			hash: "3f4aa1fedf1f54eeb03b759deadb36676b184911".into(),
 			checksum_type: ChecksumType::DSHA256,
+			..Default::default()
 		};
 
 		assert_eq!(address, "16meyfSoQV6twkAAxPe51RtMVz7PGRmWna".into());
@@ -212,6 +452,7 @@ mod tests {
 			t_addr_prefix: 0,
 			hash: "05aab5342166f8594baf17a7d9bef5d567443327".into(),
 			checksum_type: ChecksumType::DSHA256,
+			..Default::default()
 		};
 
 		assert_eq!(address, "R9o9xTocqr6CeEDGDH6mEYpwLoMz6jNjMW".into());
@@ -225,6 +466,7 @@ mod tests {
 			prefix: 37,
 			hash: "05aab5342166f8594baf17a7d9bef5d567443327".into(),
 			checksum_type: ChecksumType::DSHA256,
+			..Default::default()
 		};
 
 		assert_eq!(address, "tmAEKD7psc1ajK76QMGEW8WGQSBBHf9SqCp".into());
@@ -238,6 +480,7 @@ mod tests {
 			t_addr_prefix: 0,
 			hash: "ca0c3786c96ff7dacd40fdb0f7c196528df35f85".into(),
 			checksum_type: ChecksumType::DSHA256,
+			..Default::default()
 		};
 
 		assert_eq!(address, "bX9bppqdGvmCCAujd76Tq76zs1suuPnB9A".into());
@@ -251,6 +494,7 @@ mod tests {
 			t_addr_prefix: 0,
 			hash: "c3f710deb7320b0efa6edb14e3ebeeb9155fa90d".into(),
 			checksum_type: ChecksumType::DGROESTL512,
+			..Default::default()
 		};
 
 		assert_eq!(address, "Fo2tBkpzaWQgtjFUkemsYnKyfvd2i8yTki".into());
@@ -264,9 +508,208 @@ mod tests {
 			t_addr_prefix: 0,
 			hash: "56bb05aa20f5a80cf84e90e5dab05be331333e27".into(),
 			checksum_type: ChecksumType::KECCAK256,
+			..Default::default()
 		};
 
 		assert_eq!(address, "SVCbBs6FvPYxJrYoJc4TdCe47QNCgmTabv".into());
 		assert_eq!(address.to_string(), "SVCbBs6FvPYxJrYoJc4TdCe47QNCgmTabv".to_owned());
 	}
+
+	#[test]
+	fn test_address_new() {
+		let address = Address::new(Network::Mainnet, Type::P2PKH, "3f4aa1fedf1f54eeb03b759deadb36676b184911".into()).unwrap();
+		assert_eq!(address.to_string(), "16meyfSoQV6twkAAxPe51RtMVz7PGRmWna");
+		let address = Address::new(Network::Komodo, Type::P2SH, "ca0c3786c96ff7dacd40fdb0f7c196528df35f85".into()).unwrap();
+		assert_eq!(address.to_string(), "bX9bppqdGvmCCAujd76Tq76zs1suuPnB9A");
+		let address = Address::new(Network::Groestlcoin, Type::P2PKH, "c3f710deb7320b0efa6edb14e3ebeeb9155fa90d".into()).unwrap();
+		assert_eq!(address.to_string(), "Fo2tBkpzaWQgtjFUkemsYnKyfvd2i8yTki");
+		let address = Address::new(Network::SmartCash, Type::P2PKH, "56bb05aa20f5a80cf84e90e5dab05be331333e27".into()).unwrap();
+		assert_eq!(address.to_string(), "SVCbBs6FvPYxJrYoJc4TdCe47QNCgmTabv");
+		let address = Address::new(Network::Zcash, Type::P2PKH, "05aab5342166f8594baf17a7d9bef5d567443327".into()).unwrap();
+		assert!(address.to_string().starts_with("t1"));
+		let address = Address::new(Network::Mainnet, Type::P2WPKH, "751e76e8199196d454941c45d1b3a323f1433bd6".into()).unwrap();
+		assert_eq!(address.to_string(), "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+		assert_eq!(Address::new(Network::Komodo, Type::P2WPKH, "751e76e8199196d454941c45d1b3a323f1433bd6".into()), Err(Error::InvalidNetwork));
+	}
+
+	#[test]
+	fn test_bech32_address_from_str() {
+		let address: Address = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".parse().unwrap();
+		assert_eq!(address, Address::new(Network::Mainnet, Type::P2WPKH, "751e76e8199196d454941c45d1b3a323f1433bd6".into()).unwrap());
+		assert_eq!(address.kind(), Type::P2WPKH);
+		assert_eq!(identify_network(&address), Some(Network::Mainnet));
+		assert_eq!(address.to_string().parse::<Address>(), Ok(address.clone()));
+		assert_eq!("BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4".parse::<Address>(), Ok(address));
+
+		let address = Address::new(Network::Groestlcoin, Type::P2WPKH, "751e76e8199196d454941c45d1b3a323f1433bd6".into()).unwrap();
+		assert_eq!(address.to_string().parse::<Address>(), Ok(address));
+
+		assert_eq!("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5".parse::<Address>(), Err(Error::InvalidAddress));
+	}
+
+	#[test]
+	fn test_address_kind_and_network() {
+		let address: Address = "16meyfSoQV6twkAAxPe51RtMVz7PGRmWna".into();
+		assert_eq!(address.kind(), Type::P2PKH);
+		assert_eq!(identify_network(&address), Some(Network::Mainnet));
+
+		let address: Address = "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy".into();
+		assert_eq!(address.kind(), Type::P2SH);
+		assert_eq!(identify_network(&address), Some(Network::Mainnet));
+
+		let address: Address = "R9o9xTocqr6CeEDGDH6mEYpwLoMz6jNjMW".into();
+		assert_eq!(address.kind(), Type::P2PKH);
+		assert_eq!(identify_network(&address), Some(Network::Komodo));
+
+		let address: Address = "bX9bppqdGvmCCAujd76Tq76zs1suuPnB9A".into();
+		assert_eq!(address.kind(), Type::P2SH);
+		assert_eq!(identify_network(&address), Some(Network::Komodo));
+
+		let address: Address = "Fo2tBkpzaWQgtjFUkemsYnKyfvd2i8yTki".into();
+		assert_eq!(identify_network(&address), Some(Network::Groestlcoin));
+
+		// zcash testnet is not in the table
+		let address: Address = "tmAEKD7psc1ajK76QMGEW8WGQSBBHf9SqCp".into();
+		assert_eq!(identify_network(&address), None);
+	}
+
+	#[test]
+	fn test_address_from_str_unknown_prefix() {
+		// Litecoin prefix and a prefix not used by any network, neither is registered
+		for prefix in &[48, 0x99] {
+			let address = Address {
+				prefix: *prefix,
+				t_addr_prefix: 0,
+				hash: "3f4aa1fedf1f54eeb03b759deadb36676b184911".into(),
+				checksum_type: ChecksumType::DSHA256,
+				..Default::default()
+			};
+
+			assert_eq!(address.to_string().parse::<Address>(), Err(Error::InvalidAddress));
+		}
+	}
+
+	#[test]
+	fn test_is_valid_address() {
+		assert!(is_valid_address("16meyfSoQV6twkAAxPe51RtMVz7PGRmWna"));
+		assert!(is_valid_address("tmAEKD7psc1ajK76QMGEW8WGQSBBHf9SqCp"));
+		assert!(is_valid_address("Fo2tBkpzaWQgtjFUkemsYnKyfvd2i8yTki"));
+		assert!(is_valid_address("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"));
+		// decodes as base58 too
+		assert!(is_valid_address("bc1qgweffmq8udz8gyh44y9k3zy3ajtrd6r4zwdkjq"));
+		assert!("bc1qgweffmq8udz8gyh44y9k3zy3ajtrd6r4zwdkjq".parse::<Address>().is_ok());
+
+		assert!(!is_valid_address("16meyfSoQV6twkAAxPe51RtMVz7PGRmWnb"));
+		assert!(!is_valid_address("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5"));
+		assert!(!is_valid_address(""));
+	}
+
+	#[test]
+	fn test_address_p2sh_from_script() {
+		// P2SH-P2WPKH redeem script of the public key of secret 1
+		let redeem_script: Vec<u8> = "0014751e76e8199196d454941c45d1b3a323f1433bd6".from_hex().unwrap();
+		let address = Address::p2sh_from_script(&redeem_script, Network::Mainnet);
+		assert_eq!(address.kind(), Type::P2SH);
+		assert_eq!(address.to_string(), "3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN");
+	}
+
+	#[test]
+	fn test_address_from_public() {
+		let compressed = Public::from_slice(&"0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798".from_hex::<Vec<u8>>().unwrap()).unwrap();
+		assert_eq!(Address::from_public(&compressed, Network::Mainnet).to_string(), "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH");
+
+		let normal = Public::from_slice(&"0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8".from_hex::<Vec<u8>>().unwrap()).unwrap();
+		assert_eq!(Address::from_public(&normal, Network::Mainnet).to_string(), "1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm");
+
+		let komodo = Public::from_slice(&"0388392e0885e449ea9745ce7ad2631fdca5288f9d790cee1b696e67c75ad54a2d".from_hex::<Vec<u8>>().unwrap()).unwrap();
+		assert_eq!(Address::from_public(&komodo, Network::Komodo).to_string(), "RM5wffThEVKQdG98uLa2gc8Nk4CzX9Fq4q");
+	}
+
+	#[test]
+	fn test_address_from_script_hash() {
+		let address = Address::from_script_hash(&"fef59ae800bb89050d25f67be432b231097e1849".into(), Network::Komodo);
+		assert_eq!(address.kind(), Type::P2SH);
+		assert_eq!(address.to_string(), "bbyNYu11Qs3PowiPr1Su4ozQk7hsVmv821");
+	}
+
+	#[test]
+	fn test_address_with_checksum() {
+		let address: Address = "Fo2tBkpzaWQgtjFUkemsYnKyfvd2eqvQma".parse().unwrap();
+		assert_eq!(address.checksum_type, ChecksumType::DSHA256);
+
+		let groestl = address.with_checksum(ChecksumType::DGROESTL512);
+		assert_eq!(groestl.to_string(), "Fo2tBkpzaWQgtjFUkemsYnKyfvd2i8yTki");
+		assert_eq!(groestl.hash, "c3f710deb7320b0efa6edb14e3ebeeb9155fa90d".into());
+		assert!(groestl.same_payload(&address));
+		assert_eq!(groestl.with_checksum(ChecksumType::DSHA256), address);
+	}
+
+	#[test]
+	fn test_address_witness_program() {
+		let address: Address = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".parse().unwrap();
+		assert_eq!(address.kind(), Type::P2WPKH);
+		assert_eq!(address.hash, "751e76e8199196d454941c45d1b3a323f1433bd6".into());
+		assert_eq!(address.witness_program(), Some((0, "751e76e8199196d454941c45d1b3a323f1433bd6".from_hex().unwrap())));
+
+		let address: Address = "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3".parse().unwrap();
+		assert_eq!(address.kind(), Type::P2WSH);
+		assert_eq!(address.witness_program(), Some((0, "1863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262".from_hex().unwrap())));
+		assert_eq!(address.to_string(), "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3");
+		assert_eq!(identify_network(&address), Some(Network::Mainnet));
+
+		let address: Address = "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0".parse().unwrap();
+		assert_eq!(address.kind(), Type::WitnessUnknown);
+		assert_eq!(address.witness_program(), Some((1, "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798".from_hex().unwrap())));
+		assert_eq!(address.to_string(), "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0");
+
+		let address: Address = "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y".parse().unwrap();
+		assert_eq!(address.witness_program().map(|(version, program)| (version, program.len())), Some((1, 40)));
+		assert_eq!(address.to_string(), "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y");
+		// version 1 encoded with bech32 instead of bech32m
+		assert_eq!("bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7k7grplx".parse::<Address>(), Err(Error::InvalidAddress));
+
+		assert_eq!(Address::from_witness_program("bc", 0, vec![0; 21]), Err(Error::InvalidAddress));
+		assert_eq!(Address::from_witness_program("bc", 17, vec![0; 32]), Err(Error::InvalidAddress));
+		assert_eq!(Address::new(Network::Mainnet, Type::P2WSH, AddressHash::default()), Err(Error::InvalidAddress));
+
+		let legacy: Address = "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH".parse().unwrap();
+		assert_eq!(legacy.witness_program(), None);
+		let p2sh = Address::new(Network::Mainnet, Type::P2SH, legacy.hash.clone()).unwrap();
+		assert_eq!(p2sh.witness_program(), None);
+	}
+
+	#[test]
+	fn test_address_same_payload() {
+		let address: Address = "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH".parse().unwrap();
+		let groestl = address.with_checksum(ChecksumType::DGROESTL512);
+		assert!(address != groestl);
+		assert!(address.same_payload(&groestl));
+		assert!(groestl.same_payload(&address));
+
+		let mut other_hash = groestl.clone();
+		other_hash.hash[0] ^= 1;
+		assert!(!address.same_payload(&other_hash));
+
+		let p2sh = Address::new(Network::Mainnet, Type::P2SH, address.hash.clone()).unwrap();
+		assert!(!address.same_payload(&p2sh));
+		let p2wpkh = Address::new(Network::Mainnet, Type::P2WPKH, address.hash.clone()).unwrap();
+		assert!(!address.same_payload(&p2wpkh));
+	}
+
+	#[test]
+	fn test_wif_to_address() {
+		let address = wif_to_address("UwA3FpHWKfwrQ1DTiwbErpEnCEhvLuq1WnbfmqGBPSLNNvXtzYd5", &Network::Komodo).unwrap();
+		assert_eq!(address.to_string(), "RRztpP6Dub1RFFZFK9Dw43kigTYBhgzaVH");
+		assert_eq!(wif_to_address("UwA3FpHWKfwrQ1DTiwbErpEnCEhvLuq1WnbfmqGBPSLNNvXtzYd6", &Network::Komodo), Err(Error::InvalidChecksum));
+	}
+
+	#[test]
+	fn test_normalize_address_case() {
+		assert_eq!(normalize_address_case("BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4"), Ok("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".to_owned()));
+		assert_eq!(normalize_address_case("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"), Ok("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".to_owned()));
+		assert_eq!(normalize_address_case("16meyfSoQV6twkAAxPe51RtMVz7PGRmWna"), Ok("16meyfSoQV6twkAAxPe51RtMVz7PGRmWna".to_owned()));
+
+		assert_eq!(normalize_address_case("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kV8F3T4"), Err(Error::InvalidAddress));
+		assert_eq!(normalize_address_case("16MEYFSOQV6TWKAAXPE51RTMVZ7PGRMWNA"), Err(Error::InvalidAddress));
+	}
 }
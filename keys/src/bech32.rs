@@ -0,0 +1,207 @@
+//! Bech32 and bech32m encoding
+//!
+//! https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki
+//! https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki
+
+use Error;
+
+const CHARSET: &'static [u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc830a3;
+const CHECKSUM_LENGTH: usize = 6;
+const MAX_LENGTH: usize = 90;
+
+/// Checksum variant of the encoding
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Variant {
+	/// BIP173, used by witness version 0
+	Bech32,
+	/// BIP350, used by witness versions 1 to 16
+	Bech32m,
+}
+
+impl Variant {
+	fn constant(&self) -> u32 {
+		match *self {
+			Variant::Bech32 => BECH32_CONST,
+			Variant::Bech32m => BECH32M_CONST,
+		}
+	}
+}
+
+fn polymod(values: &[u8]) -> u32 {
+	values.iter().fold(1u32, |chk, value| {
+		let top = chk >> 25;
+		let chk = (chk & 0x1ffffff) << 5 ^ *value as u32;
+		GENERATOR.iter().enumerate()
+			.filter(|&(i, _)| (top >> i) & 1 == 1)
+			.fold(chk, |chk, (_, g)| chk ^ g)
+	})
+}
+
+fn hrp_expand(hrp: &[u8]) -> Vec<u8> {
+	let mut result: Vec<u8> = hrp.iter().map(|c| c >> 5).collect();
+	result.push(0);
+	result.extend(hrp.iter().map(|c| c & 0x1f));
+	result
+}
+
+/// Encodes human readable part and 5-bit data values with given checksum variant.
+pub fn encode(hrp: &str, data: &[u8], variant: Variant) -> String {
+	let hrp = hrp.to_lowercase();
+	let mut values = hrp_expand(hrp.as_bytes());
+	values.extend_from_slice(data);
+	values.extend_from_slice(&[0u8; CHECKSUM_LENGTH]);
+	let checksum = polymod(&values) ^ variant.constant();
+
+	let mut result = hrp;
+	result.push('1');
+	result.extend(data.iter().map(|d| CHARSET[*d as usize] as char));
+	result.extend((0..CHECKSUM_LENGTH).map(|i| CHARSET[((checksum >> (5 * (5 - i))) & 0x1f) as usize] as char));
+	result
+}
+
+/// Decodes bech32 or bech32m string into lowercase human readable part,
+/// 5-bit data values (without checksum) and detected checksum variant.
+pub fn decode(s: &str) -> Result<(String, Vec<u8>, Variant), Error> {
+	if s.len() > MAX_LENGTH || !s.is_ascii() {
+		return Err(Error::InvalidAddress);
+	}
+
+	let has_lower = s.bytes().any(|c| c.is_ascii_lowercase());
+	let has_upper = s.bytes().any(|c| c.is_ascii_uppercase());
+	if has_lower && has_upper {
+		return Err(Error::InvalidAddress);
+	}
+
+	let s = s.to_lowercase();
+	let separator = try!(s.rfind('1').ok_or(Error::InvalidAddress));
+	if separator == 0 || separator + 1 + CHECKSUM_LENGTH > s.len() {
+		return Err(Error::InvalidAddress);
+	}
+
+	let (hrp, data) = (&s[..separator], &s[separator + 1..]);
+	if hrp.bytes().any(|c| c < 33 || c > 126) {
+		return Err(Error::InvalidAddress);
+	}
+
+	let data = try!(data.bytes()
+		.map(|c| CHARSET.iter().position(|x| *x == c).map(|p| p as u8).ok_or(Error::InvalidAddress))
+		.collect::<Result<Vec<u8>, _>>());
+
+	let mut values = hrp_expand(hrp.as_bytes());
+	values.extend_from_slice(&data);
+	let variant = match polymod(&values) {
+		BECH32_CONST => Variant::Bech32,
+		BECH32M_CONST => Variant::Bech32m,
+		_ => return Err(Error::InvalidChecksum),
+	};
+
+	let data_len = data.len() - CHECKSUM_LENGTH;
+	Ok((hrp.to_owned(), data[..data_len].to_vec(), variant))
+}
+
+/// Decodes segwit address into human readable part, witness version and witness program,
+/// checking the checksum variant and program length rules of BIP173 and BIP350.
+pub fn decode_segwit(s: &str) -> Result<(String, u8, Vec<u8>), Error> {
+	let (hrp, data, variant) = decode(s)?;
+	let version = data.first().cloned().ok_or(Error::InvalidAddress)?;
+	let expected_variant = if version == 0 { Variant::Bech32 } else { Variant::Bech32m };
+	if version > 16 || variant != expected_variant {
+		return Err(Error::InvalidAddress);
+	}
+
+	let program = convert_bits(&data[1..], 5, 8, false)?;
+	if program.len() < 2 || program.len() > 40 || (version == 0 && program.len() != 20 && program.len() != 32) {
+		return Err(Error::InvalidAddress);
+	}
+
+	Ok((hrp, version, program))
+}
+
+/// Regroups bits of `data` from `from` bits per value into `to` bits per value.
+pub fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>, Error> {
+	let mut acc = 0u32;
+	let mut bits = 0u32;
+	let max = (1u32 << to) - 1;
+	let mut result = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+	for value in data {
+		let value = *value as u32;
+		if value >> from != 0 {
+			return Err(Error::InvalidAddress);
+		}
+		acc = (acc << from) | value;
+		bits += from;
+		while bits >= to {
+			bits -= to;
+			result.push(((acc >> bits) & max) as u8);
+		}
+	}
+
+	if pad {
+		if bits > 0 {
+			result.push(((acc << (to - bits)) & max) as u8);
+		}
+	} else if bits >= from || ((acc << (to - bits)) & max) != 0 {
+		return Err(Error::InvalidAddress);
+	}
+
+	Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+	use Error;
+	use super::{encode, decode, decode_segwit, convert_bits, Variant};
+
+	#[test]
+	fn test_decode_valid() {
+		assert_eq!(decode("A12UEL5L").unwrap(), ("a".to_owned(), vec![], Variant::Bech32));
+		assert_eq!(decode("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw").unwrap().2, Variant::Bech32);
+		assert_eq!(decode("A1LQFN3A").unwrap(), ("a".to_owned(), vec![], Variant::Bech32m));
+		assert_eq!(decode("abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx").unwrap().2, Variant::Bech32m);
+	}
+
+	#[test]
+	fn test_decode_invalid() {
+		assert_eq!(decode("a12uel5m"), Err(Error::InvalidChecksum));
+		assert_eq!(decode("A12uEL5L"), Err(Error::InvalidAddress));
+		assert_eq!(decode("12uel5l"), Err(Error::InvalidAddress));
+		assert_eq!(decode("a1b2uel5l"), Err(Error::InvalidAddress));
+		assert_eq!(decode("a1qqqqq"), Err(Error::InvalidAddress));
+	}
+
+	#[test]
+	fn test_encode_segwit_program() {
+		let program: Vec<u8> = vec![
+			0x75, 0x1e, 0x76, 0xe8, 0x19, 0x91, 0x96, 0xd4, 0x54, 0x94,
+			0x1c, 0x45, 0xd1, 0xb3, 0xa3, 0x23, 0xf1, 0x43, 0x3b, 0xd6,
+		];
+		let mut data = vec![0];
+		data.extend(convert_bits(&program, 8, 5, true).unwrap());
+		let encoded = encode("bc", &data, Variant::Bech32);
+		assert_eq!(encoded, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+
+		let (hrp, decoded, variant) = decode(&encoded).unwrap();
+		assert_eq!(hrp, "bc");
+		assert_eq!(variant, Variant::Bech32);
+		assert_eq!(convert_bits(&decoded[1..], 5, 8, false).unwrap(), program);
+	}
+
+	#[test]
+	fn test_decode_segwit() {
+		let (hrp, version, program) = decode_segwit("BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4").unwrap();
+		assert_eq!((hrp.as_str(), version, program.len()), ("bc", 0, 20));
+
+		let (hrp, version, program) = decode_segwit("bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y").unwrap();
+		assert_eq!((hrp.as_str(), version, program.len()), ("bc", 1, 40));
+		assert_eq!(&program[..20], &program[20..]);
+
+		// v0 with bech32m checksum, v1 with bech32 checksum
+		assert_eq!(decode_segwit("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh"), Err(Error::InvalidAddress));
+		assert_eq!(decode_segwit("bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd"), Err(Error::InvalidAddress));
+		// v0 program of invalid length
+		assert_eq!(decode_segwit("BC1QR508D6QEJXTDG4Y5R3ZARVARYV98GJ9P"), Err(Error::InvalidAddress));
+	}
+}
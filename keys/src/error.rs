@@ -1,4 +1,5 @@
-use std::fmt;
+use std::{error, fmt};
+use base58::FromBase58Error;
 use secp256k1::Error as SecpError;
 
 #[derive(Debug, PartialEq)]
@@ -11,6 +12,8 @@ pub enum Error {
 	InvalidChecksum,
 	InvalidPrivate,
 	InvalidAddress,
+	InvalidMnemonic,
+	InvalidBase58,
 	FailedKeyGeneration,
 }
 
@@ -25,6 +28,8 @@ impl fmt::Display for Error {
 			Error::InvalidChecksum => "Invalid Checksum",
 			Error::InvalidPrivate => "Invalid Private",
 			Error::InvalidAddress => "Invalid Address",
+			Error::InvalidMnemonic => "Invalid Mnemonic",
+			Error::InvalidBase58 => "Invalid Base58",
 			Error::FailedKeyGeneration => "Key generation failed",
 		};
 
@@ -32,6 +37,8 @@ impl fmt::Display for Error {
 	}
 }
 
+impl error::Error for Error {}
+
 impl From<SecpError> for Error {
 	fn from(e: SecpError) -> Self {
 		match e {
@@ -42,3 +49,37 @@ impl From<SecpError> for Error {
 		}
 	}
 }
+
+/// Parsers of addresses and private keys report `InvalidAddress` and `InvalidPrivate` instead.
+impl From<FromBase58Error> for Error {
+	fn from(_: FromBase58Error) -> Self {
+		Error::InvalidBase58
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::error;
+	use base58::FromBase58;
+	use secp256k1::Error as SecpError;
+	use {Address, Private};
+	use super::Error;
+
+	#[test]
+	fn test_error_display() {
+		assert_eq!(Error::InvalidChecksum.to_string(), "Invalid Checksum");
+		assert_eq!(Error::FailedKeyGeneration.to_string(), "Key generation failed");
+
+		let boxed: Box<dyn error::Error> = Box::new(Error::InvalidAddress);
+		assert_eq!(boxed.to_string(), "Invalid Address");
+	}
+
+	#[test]
+	fn test_error_conversions() {
+		assert_eq!(Error::from(SecpError::InvalidSecretKey), Error::InvalidSecret);
+		assert_eq!(Error::from(SecpError::InvalidPublicKey), Error::InvalidPublic);
+		assert_eq!(Error::from("0OIl".from_base58().unwrap_err()), Error::InvalidBase58);
+		assert_eq!("0OIl".parse::<Address>(), Err(Error::InvalidAddress));
+		assert_eq!("0OIl".parse::<Private>(), Err(Error::InvalidPrivate));
+	}
+}
@@ -0,0 +1,230 @@
+//! Key pair generators.
+
+use rand::OsRng;
+use secp256k1::SecretKey;
+use crypto::{ChecksumType, sha256};
+use hash::H256;
+use {KeyPair, Error, Private, Secret, Address, Network};
+
+/// Generates new key pairs.
+pub trait Generator {
+	fn generate(&self) -> Result<KeyPair, Error>;
+}
+
+/// Generates random key pairs using os randomness.
+pub struct Random {
+	/// The network prefix of generated private keys.
+	pub prefix: u8,
+	/// True if generated key pairs should use compressed public keys.
+	pub compressed: bool,
+	/// Checksum type of generated private keys.
+	pub checksum_type: ChecksumType,
+}
+
+impl Random {
+	pub fn new(prefix: u8, compressed: bool) -> Self {
+		Random {
+			prefix,
+			compressed,
+			checksum_type: ChecksumType::DSHA256,
+		}
+	}
+
+	/// Generator of private keys with WIF prefix and checksum type of the network.
+	pub fn for_network(network: Network, compressed: bool) -> Self {
+		Random {
+			prefix: network.wif_prefix(),
+			compressed,
+			checksum_type: network.checksum_type(),
+		}
+	}
+}
+
+impl Generator for Random {
+	fn generate(&self) -> Result<KeyPair, Error> {
+		let mut rng = OsRng::new().map_err(|_| Error::FailedKeyGeneration)?;
+		let secret_key = SecretKey::random(&mut rng);
+		let mut secret = Secret::default();
+		secret.copy_from_slice(&secret_key.serialize());
+
+		let private = Private {
+			prefix: self.prefix,
+			secret,
+			compressed: self.compressed,
+			checksum_type: self.checksum_type,
+		};
+
+		KeyPair::from_private(private)
+	}
+}
+
+/// Derives key pairs from the seed, so the same seed always yields the same key pairs.
+/// Generated key pairs use compressed public keys.
+pub struct Deterministic {
+	/// The seed of generated key pairs.
+	pub seed: H256,
+	/// The network prefix of generated private keys.
+	pub prefix: u8,
+}
+
+impl Deterministic {
+	pub fn new(seed: H256, prefix: u8) -> Self {
+		Deterministic {
+			seed,
+			prefix,
+		}
+	}
+
+	/// Generates `n`-th key pair of the sequence, with secret `sha256(seed + n)`, `n` encoded as LE u32.
+	/// Fails in the unlikely case that the hash is not a valid secret.
+	pub fn generate_nth(&self, n: u32) -> Result<KeyPair, Error> {
+		let mut data = [0u8; 36];
+		data[..32].copy_from_slice(&*self.seed);
+		data[32..].copy_from_slice(&n.to_le_bytes());
+
+		let private = Private {
+			prefix: self.prefix,
+			secret: sha256(&data),
+			compressed: true,
+			checksum_type: ChecksumType::DSHA256,
+		};
+
+		KeyPair::from_private(private)
+	}
+}
+
+impl Generator for Deterministic {
+	fn generate(&self) -> Result<KeyPair, Error> {
+		self.generate_nth(0)
+	}
+}
+
+/// Generates random key pairs until the base58 P2PKH address matches given pattern.
+pub struct Vanity {
+	/// Network of the address and generated private keys.
+	pub network: Network,
+	/// Address must start with this pattern right after the leading characters fixed by the network,
+	/// e.g. "1" for Bitcoin or "t1" for Zcash.
+	pub pattern: String,
+	/// True if generated key pairs should use compressed public keys.
+	pub compressed: bool,
+	/// Maximum number of key pairs generated before giving up.
+	pub max_attempts: usize,
+}
+
+impl Vanity {
+	/// Generator of compressed key pairs, which gives up after `max_attempts`.
+	pub fn new(network: Network, pattern: &str, max_attempts: usize) -> Self {
+		Vanity {
+			network,
+			pattern: pattern.into(),
+			compressed: true,
+			max_attempts,
+		}
+	}
+
+	fn matches(&self, keypair: &KeyPair) -> bool {
+		let address = Address::from_public(keypair.public(), self.network).to_string();
+		// base58 addresses with the t addr prefix start with 2 fixed characters
+		let fixed_len = if self.network.t_addr_prefix() > 0 { 2 } else { 1 };
+		address[fixed_len..].starts_with(&self.pattern)
+	}
+}
+
+impl Generator for Vanity {
+	fn generate(&self) -> Result<KeyPair, Error> {
+		let random = Random::for_network(self.network, self.compressed);
+		for _ in 0..self.max_attempts {
+			let keypair = random.generate()?;
+			if self.matches(&keypair) {
+				return Ok(keypair);
+			}
+		}
+
+		Err(Error::FailedKeyGeneration)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crypto::ChecksumType;
+	use hash::H256;
+	use {Address, Error, Network, Private};
+	use super::{Deterministic, Generator, Random, Vanity};
+
+	#[test]
+	fn test_random_generator() {
+		let keypair = Random::new(128, true).generate().unwrap();
+		assert_eq!(keypair.private().prefix, 128);
+		assert!(keypair.private().compressed);
+		assert_eq!(keypair.public().len(), 33);
+	}
+
+	#[test]
+	fn test_random_generator_smart_cash() {
+		let keypair = Random::for_network(Network::SmartCash, true).generate().unwrap();
+		assert_eq!(keypair.private().checksum_type, ChecksumType::KECCAK256);
+
+		let wif = keypair.private().to_string();
+		assert!(wif.starts_with('V'));
+		let private: Private = wif.parse().unwrap();
+		assert_eq!(private.checksum_type, ChecksumType::KECCAK256);
+		assert_eq!(private.network(), Some(Network::SmartCash));
+		assert_eq!(&private, keypair.private());
+
+		let address = Address::from_public(keypair.public(), Network::SmartCash).to_string();
+		assert!(address.starts_with('S'));
+		let parsed: Address = address.parse().unwrap();
+		assert_eq!(parsed.checksum_type, ChecksumType::KECCAK256);
+		assert_eq!(parsed.hash, keypair.public().address_hash());
+	}
+
+	#[test]
+	fn test_deterministic_generator() {
+		let seed: H256 = "0101010101010101010101010101010101010101010101010101010101010101".into();
+		let first = Deterministic::new(seed.clone(), 128);
+		let second = Deterministic::new(seed, 128);
+
+		let keypair = first.generate().unwrap();
+		assert_eq!(keypair, second.generate().unwrap());
+		assert_eq!(keypair, first.generate_nth(0).unwrap());
+		assert_eq!(keypair.private().secret, "3f6c2724a21a3b29ef886a52aa414bec96c46f7af137c636065209ff892cee6c".into());
+		assert_eq!(keypair.private().prefix, 128);
+		assert!(keypair.private().compressed);
+
+		let nth = first.generate_nth(1).unwrap();
+		assert_eq!(nth, second.generate_nth(1).unwrap());
+		assert_eq!(nth.private().secret, "ec2fd806701a3f55808cbec3922c38dafaa3070c48c803e9043ee3642c660b46".into());
+		assert!(nth != keypair);
+
+		let other = Deterministic::new(Default::default(), 128);
+		assert!(other.generate().unwrap() != keypair);
+	}
+
+	#[test]
+	fn test_vanity_generator() {
+		let vanity = Vanity::new(Network::Mainnet, "A", 100_000);
+		let keypair = vanity.generate().unwrap();
+		assert_eq!(keypair.private().prefix, 128);
+		assert!(keypair.private().compressed);
+		assert!(Address::from_public(keypair.public(), Network::Mainnet).to_string().starts_with("1A"));
+	}
+
+	#[test]
+	fn test_vanity_generator_zcash() {
+		let vanity = Vanity::new(Network::Zcash, "R", 100_000);
+		let keypair = vanity.generate().unwrap();
+		assert!(Address::from_public(keypair.public(), Network::Zcash).to_string().starts_with("t1R"));
+	}
+
+	#[test]
+	fn test_vanity_generator_exhausted() {
+		// '0' is not part of base58 alphabet
+		let vanity = Vanity {
+			compressed: false,
+			..Vanity::new(Network::Mainnet, "0", 10)
+		};
+
+		assert_eq!(vanity.generate(), Err(Error::FailedKeyGeneration));
+	}
+}
@@ -1,30 +1,45 @@
 //! Bitcoin keys.
 
 extern crate rustc_hex as hex;
+extern crate rand;
 extern crate secp256k1;
 extern crate base58;
 extern crate bitcrypto as crypto;
 extern crate primitives;
+#[macro_use]
+extern crate lazy_static;
 
 mod address;
+pub mod bech32;
 mod display;
 mod keypair;
 mod error;
+mod generator;
+pub mod message;
+mod mnemonic;
 mod network;
 mod private;
 mod public;
+mod schnorr;
+pub mod secret;
+pub mod registry;
 mod signature;
+mod taproot;
 
 pub use primitives::{hash, bytes};
 
-pub use address::{Type, Address};
+pub use address::{Type, Address, WitnessProgram, is_valid_address, identify_network, wif_to_address, normalize_address_case};
 pub use display::DisplayLayout;
 pub use keypair::KeyPair;
 pub use error::Error;
+pub use generator::{Generator, Random, Vanity, Deterministic};
+pub use mnemonic::{Mnemonic, Language};
 pub use private::Private;
-pub use public::Public;
+pub use public::{Public, verify_batch};
+pub use schnorr::{verify_schnorr, verify_schnorr_batch};
 pub use signature::{Signature, CompactSignature};
 pub use network::Network;
+pub use taproot::{XOnlyPublic, taproot_output_key};
 
 use hash::{H160, H256};
 
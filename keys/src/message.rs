@@ -0,0 +1,102 @@
+//! Signed messages, as produced by `signmessage` RPC of Bitcoin Core.
+//!
+//! The signed digest is `dhash256(varint(magic.len()) + magic + varint(message.len()) + message)`.
+
+use crypto::dhash256;
+use {Address, CompactSignature, Error, Message, Private};
+
+/// Magic prefix of Bitcoin signed messages.
+pub const BITCOIN_MESSAGE_MAGIC: &str = "Bitcoin Signed Message:\n";
+/// Magic prefix of Komodo signed messages.
+pub const KOMODO_MESSAGE_MAGIC: &str = "Komodo Signed Message:\n";
+
+/// Returns digest of the message prefixed with the magic string.
+pub fn message_hash(magic: &str, message: &str) -> Message {
+	let mut data = Vec::with_capacity(magic.len() + message.len() + 10);
+	append_with_length(&mut data, magic.as_bytes());
+	append_with_length(&mut data, message.as_bytes());
+	dhash256(&data)
+}
+
+/// Signs message using Bitcoin magic prefix.
+pub fn sign_message(private: &Private, message: &str) -> Result<CompactSignature, Error> {
+	sign_message_with_magic(private, BITCOIN_MESSAGE_MAGIC, message)
+}
+
+/// Signs message using custom magic prefix, e.g. `KOMODO_MESSAGE_MAGIC`.
+pub fn sign_message_with_magic(private: &Private, magic: &str, message: &str) -> Result<CompactSignature, Error> {
+	private.sign_compact(&message_hash(magic, message))
+}
+
+/// Verifies that message with Bitcoin magic prefix was signed by the owner of P2PKH address.
+pub fn verify_message(address: &Address, message: &str, signature: &CompactSignature) -> Result<bool, Error> {
+	verify_message_with_magic(address, BITCOIN_MESSAGE_MAGIC, message, signature)
+}
+
+/// Verifies that message with custom magic prefix was signed by the owner of P2PKH address.
+pub fn verify_message_with_magic(address: &Address, magic: &str, message: &str, signature: &CompactSignature) -> Result<bool, Error> {
+	signature.verify_address(&message_hash(magic, message), address)
+}
+
+/// Appends data prefixed with its length encoded as Bitcoin compact size.
+fn append_with_length(buffer: &mut Vec<u8>, data: &[u8]) {
+	let len = data.len() as u64;
+	match len {
+		0..=0xfc => buffer.push(len as u8),
+		0xfd..=0xffff => {
+			buffer.push(0xfd);
+			buffer.extend_from_slice(&(len as u16).to_le_bytes());
+		},
+		0x10000..=0xffff_ffff => {
+			buffer.push(0xfe);
+			buffer.extend_from_slice(&(len as u32).to_le_bytes());
+		},
+		_ => {
+			buffer.push(0xff);
+			buffer.extend_from_slice(&len.to_le_bytes());
+		},
+	}
+	buffer.extend_from_slice(data);
+}
+
+#[cfg(test)]
+mod tests {
+	use {Address, CompactSignature, Error, Private};
+	use super::{sign_message, sign_message_with_magic, verify_message, verify_message_with_magic, KOMODO_MESSAGE_MAGIC};
+
+	// test/functional/rpc_signmessage.py of Bitcoin Core, signature
+	// INbVnW4e6PeRmsv2Qgu8NuopvrVjkcxob+sX8OcZG0SALhWybUjzMLPdAsXI46YZGb0KQTRii+wWIQzRpG/U+S0=
+	const CORE_PRIVATE: &str = "cUeKHd5orzT3mz8P9pxyREHfsWtVfgsfDjiZZBcjUBAaGk1BTj7N";
+	const CORE_ADDRESS: &str = "mpLQjfK79b7CCV4VMJWEWAj5Mpx8Up5zxB";
+	const CORE_MESSAGE: &str = "This is just a test message";
+	const CORE_SIGNATURE: &str = "20d6d59d6e1ee8f7919acbf6420bbc36ea29beb56391cc686feb17f0e7191b44802e15b26d48f330b3dd02c5c8e3a61919bd0a4134628bec16210cd1a46fd4f92d";
+
+	#[test]
+	fn test_verify_message_core() {
+		let address: Address = CORE_ADDRESS.parse().unwrap();
+		let signature: CompactSignature = CORE_SIGNATURE.into();
+		assert_eq!(verify_message(&address, CORE_MESSAGE, &signature), Ok(true));
+		assert_eq!(verify_message(&address, "This is just a test message!", &signature), Ok(false));
+		assert_eq!(verify_message_with_magic(&address, KOMODO_MESSAGE_MAGIC, CORE_MESSAGE, &signature), Ok(false));
+
+		let p2sh: Address = "3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN".parse().unwrap();
+		assert_eq!(verify_message(&p2sh, CORE_MESSAGE, &signature), Err(Error::InvalidAddress));
+	}
+
+	#[test]
+	fn test_sign_message_core() {
+		let private: Private = CORE_PRIVATE.into();
+		let signature = sign_message(&private, CORE_MESSAGE).unwrap();
+		assert_eq!(signature, CORE_SIGNATURE.into());
+	}
+
+	#[test]
+	fn test_sign_message_round_trip() {
+		let private: Private = "UwA3FpHWKfwrQ1DTiwbErpEnCEhvLuq1WnbfmqGBPSLNNvXtzYd5".into();
+		let address: Address = "RRztpP6Dub1RFFZFK9Dw43kigTYBhgzaVH".parse().unwrap();
+		let message = "komodo message";
+		let signature = sign_message_with_magic(&private, KOMODO_MESSAGE_MAGIC, message).unwrap();
+		assert_eq!(verify_message_with_magic(&address, KOMODO_MESSAGE_MAGIC, message, &signature), Ok(true));
+		assert_eq!(verify_message(&address, message, &signature), Ok(false));
+	}
+}
@@ -0,0 +1,243 @@
+//! BIP39 mnemonic phrases.
+//!
+//! https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki
+
+use std::fmt;
+use rand::{OsRng, Rng};
+use crypto::{sha256, pbkdf2_hmac_sha512};
+use Error;
+
+const ENGLISH_WORDLIST: &str = include_str!("wordlist/english.txt");
+const BITS_PER_WORD: usize = 11;
+const PBKDF2_ROUNDS: usize = 2048;
+
+lazy_static! {
+	static ref ENGLISH_WORDS: Vec<&'static str> = ENGLISH_WORDLIST.lines().collect();
+}
+
+/// Language of the mnemonic wordlist.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum Language {
+	#[default]
+	English,
+}
+
+impl Language {
+	/// Returns sorted list of 2048 words used by this language.
+	fn wordlist(&self) -> &'static [&'static str] {
+		match *self {
+			Language::English => &ENGLISH_WORDS,
+		}
+	}
+}
+
+/// BIP39 mnemonic phrase.
+#[derive(PartialEq, Clone)]
+pub struct Mnemonic {
+	phrase: String,
+	entropy: Vec<u8>,
+	lang: Language,
+}
+
+impl Mnemonic {
+	/// Generates new random mnemonic with given amount of entropy bits.
+	/// Entropy must be a multiple of 32 bits between 128 and 256.
+	pub fn generate(entropy_bits: usize) -> Result<Self, Error> {
+		if !(128..=256).contains(&entropy_bits) || !entropy_bits.is_multiple_of(32) {
+			return Err(Error::InvalidMnemonic);
+		}
+
+		let mut entropy = vec![0u8; entropy_bits / 8];
+		let mut rng = OsRng::new().map_err(|_| Error::FailedKeyGeneration)?;
+		rng.fill_bytes(&mut entropy);
+		Mnemonic::from_entropy(&entropy, Language::English)
+	}
+
+	/// Creates mnemonic encoding given entropy.
+	pub fn from_entropy(entropy: &[u8], lang: Language) -> Result<Self, Error> {
+		let entropy_bits = entropy.len() * 8;
+		if !(128..=256).contains(&entropy_bits) || !entropy_bits.is_multiple_of(32) {
+			return Err(Error::InvalidMnemonic);
+		}
+
+		let checksum_bits = entropy_bits / 32;
+		let checksum = sha256(entropy)[0];
+		let bit = |i: usize| if i < entropy_bits {
+			entropy[i / 8] & (0x80 >> (i % 8)) != 0
+		} else {
+			checksum & (0x80 >> (i - entropy_bits)) != 0
+		};
+
+		let wordlist = lang.wordlist();
+		let words = (0..(entropy_bits + checksum_bits) / BITS_PER_WORD)
+			.map(|word| {
+				let index = (0..BITS_PER_WORD)
+					.fold(0, |acc, i| (acc << 1) | bit(word * BITS_PER_WORD + i) as usize);
+				wordlist[index]
+			})
+			.collect::<Vec<_>>();
+
+		let mnemonic = Mnemonic {
+			phrase: words.join(" "),
+			entropy: entropy.to_vec(),
+			lang,
+		};
+
+		Ok(mnemonic)
+	}
+
+	/// Parses mnemonic phrase and validates its checksum.
+	pub fn from_phrase(words: &str, lang: Language) -> Result<Self, Error> {
+		let wordlist = lang.wordlist();
+		let indexes = words.split_whitespace()
+			.map(|word| wordlist.binary_search(&word).map_err(|_| Error::InvalidMnemonic))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		match indexes.len() {
+			12 | 15 | 18 | 21 | 24 => (),
+			_ => return Err(Error::InvalidMnemonic),
+		}
+
+		let total_bits = indexes.len() * BITS_PER_WORD;
+		let checksum_bits = total_bits / 33;
+		let entropy_bits = total_bits - checksum_bits;
+
+		let bit = |i: usize| indexes[i / BITS_PER_WORD] & (1 << (BITS_PER_WORD - 1 - i % BITS_PER_WORD)) != 0;
+
+		let mut entropy = vec![0u8; entropy_bits / 8];
+		for i in 0..entropy_bits {
+			if bit(i) {
+				entropy[i / 8] |= 0x80 >> (i % 8);
+			}
+		}
+
+		let checksum = (0..checksum_bits)
+			.fold(0u8, |acc, i| (acc << 1) | bit(entropy_bits + i) as u8);
+		if sha256(&entropy)[0] >> (8 - checksum_bits) != checksum {
+			return Err(Error::InvalidChecksum);
+		}
+
+		let mnemonic = Mnemonic {
+			phrase: indexes.iter().map(|i| wordlist[*i]).collect::<Vec<_>>().join(" "),
+			entropy,
+			lang,
+		};
+
+		Ok(mnemonic)
+	}
+
+	/// Returns normalized mnemonic phrase.
+	pub fn phrase(&self) -> &str {
+		&self.phrase
+	}
+
+	/// Returns entropy encoded by this mnemonic.
+	pub fn entropy(&self) -> &[u8] {
+		&self.entropy
+	}
+
+	/// Returns language of the mnemonic.
+	pub fn language(&self) -> Language {
+		self.lang
+	}
+
+	/// Derives 64 bytes long seed from mnemonic and optional passphrase.
+	/// Passphrase is expected to be already NFKD normalized.
+	pub fn to_seed(&self, passphrase: &str) -> [u8; 64] {
+		let salt = format!("mnemonic{}", passphrase);
+		let mut seed = [0u8; 64];
+		seed.copy_from_slice(&*pbkdf2_hmac_sha512(self.phrase.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS));
+		seed
+	}
+}
+
+impl fmt::Debug for Mnemonic {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Mnemonic")
+			.field("phrase", &format_args!("<redacted>"))
+			.field("entropy", &format_args!("<redacted>"))
+			.field("lang", &self.lang)
+			.finish()
+	}
+}
+
+impl fmt::Display for Mnemonic {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.phrase.fmt(f)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use hex::{FromHex, ToHex};
+	use Error;
+	use super::{Mnemonic, Language};
+
+	fn check_vector(entropy: &'static str, phrase: &'static str, seed: &'static str) {
+		let entropy: Vec<u8> = entropy.from_hex().unwrap();
+		let from_entropy = Mnemonic::from_entropy(&entropy, Language::English).unwrap();
+		assert_eq!(from_entropy.phrase(), phrase);
+
+		let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+		assert_eq!(mnemonic.entropy(), &entropy[..]);
+		assert_eq!(mnemonic.to_seed("TREZOR").to_hex::<String>(), seed);
+	}
+
+	#[test]
+	fn test_mnemonic_vectors() {
+		check_vector(
+			"00000000000000000000000000000000",
+			"abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+			"c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+		);
+		check_vector(
+			"7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+			"legal winner thank year wave sausage worth useful legal winner thank yellow",
+			"2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+		);
+		check_vector(
+			"80808080808080808080808080808080",
+			"letter advice cage absurd amount doctor acoustic avoid letter advice cage above",
+			"d71de856f81a8acc65e6fc851a38d4d7ec216fd0796d0a6827a3ad6ed5511a30fa280f12eb2e47ed2ac03b5c462a0358d18d69fe4f985ec81778c1b370b652a8",
+		);
+		check_vector(
+			"ffffffffffffffffffffffffffffffff",
+			"zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+			"ac27495480225222079d7be181583751e86f571027b0497b5b5d11218e0a8a13332572917f0f8e5a589620c6f15b11c61dee327651a14c34e18231052e48c069",
+		);
+		check_vector(
+			"0000000000000000000000000000000000000000000000000000000000000000",
+			"abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art",
+			"bda85446c68413707090a52022edd26a1c9462295029f2e60cd7c4f2bbd3097170af7a4d73245cafa9c3cca8d561a7c3de6f5d4a10be8ed2a5e608d68f92fcc8",
+		);
+	}
+
+	#[test]
+	fn test_mnemonic_invalid_phrase() {
+		let bad_checksum = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+		assert_eq!(Mnemonic::from_phrase(bad_checksum, Language::English), Err(Error::InvalidChecksum));
+		let unknown_word = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon foobar";
+		assert_eq!(Mnemonic::from_phrase(unknown_word, Language::English), Err(Error::InvalidMnemonic));
+		let bad_length = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+		assert_eq!(Mnemonic::from_phrase(bad_length, Language::English), Err(Error::InvalidMnemonic));
+	}
+
+	#[test]
+	fn test_mnemonic_generate() {
+		let mnemonic = Mnemonic::generate(256).unwrap();
+		assert_eq!(mnemonic.entropy().len(), 32);
+		assert_eq!(mnemonic.phrase().split(' ').count(), 24);
+		assert_eq!(Mnemonic::from_phrase(mnemonic.phrase(), Language::English), Ok(mnemonic));
+		assert_eq!(Mnemonic::generate(100), Err(Error::InvalidMnemonic));
+	}
+
+	#[test]
+	fn test_mnemonic_debug_redacted() {
+		let phrase = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+		let mnemonic = Mnemonic::from_phrase(phrase, Language::English).unwrap();
+		let debug = format!("{:?}", mnemonic);
+		assert!(!debug.contains("legal"));
+		assert!(!debug.contains("127"));
+		assert_eq!(debug, "Mnemonic { phrase: <redacted>, entropy: <redacted>, lang: English }");
+	}
+}
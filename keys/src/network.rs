@@ -1,6 +1,89 @@
+use crypto::ChecksumType;
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Network {
 	Mainnet,
 	Testnet,
-	Komodo
+	Regtest,
+	Komodo,
+	Zcash,
+	Groestlcoin,
+	SmartCash,
+}
+
+impl Network {
+	/// All networks with known prefixes. When prefixes are shared, e.g. by testnet and regtest,
+	/// the network listed first is preferred.
+	pub fn all() -> &'static [Network] {
+		&[
+			Network::Mainnet,
+			Network::Testnet,
+			Network::Regtest,
+			Network::Komodo,
+			Network::Zcash,
+			Network::Groestlcoin,
+			Network::SmartCash,
+		]
+	}
+
+	/// Prefix of pay to public key hash addresses.
+	pub fn p2pkh_prefix(&self) -> u8 {
+		match *self {
+			Network::Mainnet => 0,
+			Network::Testnet | Network::Regtest => 111,
+			Network::Komodo => 60,
+			Network::Zcash => 184,
+			Network::Groestlcoin => 36,
+			Network::SmartCash => 63,
+		}
+	}
+
+	/// Prefix of pay to script hash addresses.
+	pub fn p2sh_prefix(&self) -> u8 {
+		match *self {
+			Network::Mainnet | Network::Groestlcoin => 5,
+			Network::Testnet | Network::Regtest => 196,
+			Network::Komodo => 85,
+			Network::Zcash => 189,
+			Network::SmartCash => 18,
+		}
+	}
+
+	/// Prefix of WIF encoded private keys.
+	pub fn wif_prefix(&self) -> u8 {
+		match *self {
+			Network::Mainnet | Network::Zcash | Network::Groestlcoin => 128,
+			Network::Testnet | Network::Regtest => 239,
+			Network::Komodo => 188,
+			Network::SmartCash => 191,
+		}
+	}
+
+	/// Additional address prefix used by Zcash. 0 if not used.
+	pub fn t_addr_prefix(&self) -> u8 {
+		match *self {
+			Network::Zcash => 28,
+			_ => 0,
+		}
+	}
+
+	/// Checksum type used by base58 encoded addresses and private keys.
+	pub fn checksum_type(&self) -> ChecksumType {
+		match *self {
+			Network::Groestlcoin => ChecksumType::DGROESTL512,
+			Network::SmartCash => ChecksumType::KECCAK256,
+			_ => ChecksumType::DSHA256,
+		}
+	}
+
+	/// Human readable part of bech32 encoded segwit addresses. None if network doesn't support segwit.
+	pub fn bech32_hrp(&self) -> Option<&'static str> {
+		match *self {
+			Network::Mainnet => Some("bc"),
+			Network::Testnet => Some("tb"),
+			Network::Regtest => Some("bcrt"),
+			Network::Groestlcoin => Some("grs"),
+			_ => None,
+		}
+	}
 }
@@ -1,16 +1,21 @@
 //! Secret with additional network identifier and format type
 
 use address::detect_checksum;
-use std::fmt;
+use std::{fmt, ptr};
 use std::str::FromStr;
-use secp256k1::{Message as SecpMessage, SecretKey, sign};
+use std::sync::atomic::{compiler_fence, Ordering};
+use secp256k1::{Message as SecpMessage, SecretKey, Signature as SecpSignature, sign};
+use secp256k1::curve::{Scalar, ECMULT_GEN_CONTEXT};
 use hex::ToHex;
 use base58::{ToBase58, FromBase58};
-use crypto::{checksum, ChecksumType};
-use {Secret, DisplayLayout, Error, Message, Signature};
+use crypto::{checksum, hmac_sha256, sha256, ChecksumType};
+use hash::{H256, H520};
+use {Secret, DisplayLayout, Error, Message, Signature, CompactSignature, Network};
 
 /// Secret with additional network prefix and format type
-#[derive(Default, PartialEq, Clone)]
+///
+/// `PartialEq` is implemented manually, so that secrets are compared in constant time.
+#[derive(Default, Clone)]
 pub struct Private {
 	/// The network prefix on which this key should be used.
 	pub prefix: u8,
@@ -23,6 +28,79 @@ pub struct Private {
 }
 
 impl Private {
+	/// Creates private key using WIF prefix and checksum type of the network.
+	pub fn new(network: Network, secret: Secret, compressed: bool) -> Self {
+		Private {
+			prefix: network.wif_prefix(),
+			secret,
+			compressed,
+			checksum_type: network.checksum_type(),
+		}
+	}
+
+	/// Returns the first known network with WIF prefix and checksum type of the key.
+	/// Networks sharing the prefix, e.g. Bitcoin and Zcash, can't be told apart.
+	pub fn network(&self) -> Option<Network> {
+		Network::all().iter()
+			.find(|n| n.wif_prefix() == self.prefix && n.checksum_type() == self.checksum_type)
+			.cloned()
+	}
+
+	/// Returns copy of the key with WIF prefix and checksum type of the network,
+	/// keeping the secret and compression.
+	pub fn for_network(&self, network: Network) -> Private {
+		let mut private = self.clone();
+		private.prefix = network.wif_prefix();
+		private.checksum_type = network.checksum_type();
+		private
+	}
+
+	/// Encodes the key in Wallet Import Format, same as `Display`.
+	pub fn to_wif(&self) -> String {
+		self.to_string()
+	}
+
+	/// Parses Casascius mini private key, e.g. `S6c56bnXQiBjk9mqSYE7ykVQ7NzrRy`.
+	/// The key is valid if `sha256(key + "?")` starts with zero byte, the secret is `sha256(key)`.
+	/// https://en.bitcoin.it/wiki/Mini_private_key_format
+	pub fn from_mini(s: &str, prefix: u8, compressed: bool) -> Result<Self, Error> {
+		match s.len() {
+			22 | 26 | 30 => (),
+			_ => return Err(Error::InvalidPrivate),
+		}
+
+		if !s.starts_with('S') || s.from_base58().is_err() {
+			return Err(Error::InvalidPrivate);
+		}
+
+		if sha256(format!("{}?", s).as_bytes())[0] != 0 {
+			return Err(Error::InvalidChecksum);
+		}
+
+		let private = Private {
+			prefix,
+			secret: sha256(s.as_bytes()),
+			compressed,
+			checksum_type: ChecksumType::DSHA256,
+		};
+
+		Ok(private)
+	}
+
+	/// Compares private keys without leaking through timing which byte of the secret differs.
+	pub fn ct_eq(&self, other: &Private) -> bool {
+		let diff = self.secret.iter()
+			.zip(other.secret.iter())
+			.fold(0u8, |acc, (a, b)| acc | (a ^ b));
+
+		(diff == 0) &
+			(self.prefix == other.prefix) &
+			(self.compressed == other.compressed) &
+			(self.checksum_type == other.checksum_type)
+	}
+
+	/// Signs message in internal byte order, e.g. sighash as returned by `dhash256`.
+	/// Hashes shown in display form, like txids, should be parsed with `Message::from_display_hex`.
 	pub fn sign(&self, message: &Message) -> Result<Signature, Error> {
 		let secret = SecretKey::parse_slice(&*self.secret)?;
 		let message = SecpMessage::parse_slice(&**message)?;
@@ -30,6 +108,85 @@ impl Private {
 		let data = signature.serialize_der();
 		Ok(data.as_ref().to_vec().into())
 	}
+
+	/// Signs message and returns signature with recovery id in the header byte,
+	/// which also tells whether the public key is compressed.
+	pub fn sign_compact(&self, message: &Message) -> Result<CompactSignature, Error> {
+		let secret = SecretKey::parse_slice(&*self.secret)?;
+		let message = SecpMessage::parse_slice(&**message)?;
+		let (signature, recovery_id) = sign(&message, &secret)?;
+		let header = 27 + recovery_id.serialize() + if self.compressed { 4 } else { 0 };
+		compact_signature(header, &signature.serialize())
+	}
+
+	/// Signs message using RFC6979 nonce with additional entropy mixed in, as libsecp256k1 does.
+	pub fn sign_with_nonce(&self, message: &Message, extra_entropy: &H256) -> Result<Signature, Error> {
+		let signature = self.sign_raw(message, Some(extra_entropy))?;
+		Ok(signature.serialize_der().as_ref().to_vec().into())
+	}
+
+	/// Signs message grinding the nonce until R is low, so DER encoded signature is at most 70 bytes long.
+	/// Uses the same nonce counter as Bitcoin Core, so signatures match the reference implementation.
+	pub fn sign_low_r(&self, message: &Message) -> Result<Signature, Error> {
+		let mut signature = self.sign_raw(message, None)?;
+		let mut counter = 0u32;
+		while signature.r.b32()[0] >= 0x80 {
+			counter += 1;
+			let mut extra_entropy = H256::default();
+			extra_entropy[..4].copy_from_slice(&counter.to_le_bytes());
+			signature = self.sign_raw(message, Some(&extra_entropy))?;
+		}
+
+		Ok(signature.serialize_der().as_ref().to_vec().into())
+	}
+
+	fn sign_raw(&self, message: &Message, extra_entropy: Option<&H256>) -> Result<SecpSignature, Error> {
+		let secret: Scalar = SecretKey::parse_slice(&*self.secret)?.into();
+		let message = SecpMessage::parse_slice(&**message)?;
+		let nonce = rfc6979_nonce(&*self.secret, &message.0.b32(), extra_entropy);
+		let (r, s, _) = ECMULT_GEN_CONTEXT.sign_raw(&secret, &message.0, &nonce)?;
+		Ok(SecpSignature { r, s })
+	}
+}
+
+/// Builds compact signature from the header byte and 64 bytes of serialized `r` and `s`.
+fn compact_signature(header: u8, signature: &[u8]) -> Result<CompactSignature, Error> {
+	if signature.len() != 64 {
+		return Err(Error::InvalidSignature);
+	}
+
+	let mut data = H520::default();
+	data[0] = header;
+	data[1..].copy_from_slice(signature);
+	Ok(data.into())
+}
+
+/// RFC6979 HMAC-SHA256 nonce, with optional extra entropy appended to the seed.
+fn rfc6979_nonce(secret: &[u8], message: &[u8], extra_entropy: Option<&H256>) -> Scalar {
+	let mut seed = secret.to_vec();
+	seed.extend_from_slice(message);
+	if let Some(extra) = extra_entropy {
+		seed.extend_from_slice(&**extra);
+	}
+
+	let mut k = H256::default();
+	let mut v = H256::from([1u8; 32]);
+	k = hmac_sha256(&*k, &[&*v, &[0u8][..], &seed].concat());
+	v = hmac_sha256(&*k, &*v);
+	k = hmac_sha256(&*k, &[&*v, &[1u8][..], &seed].concat());
+	v = hmac_sha256(&*k, &*v);
+
+	loop {
+		v = hmac_sha256(&*k, &*v);
+		let mut nonce = Scalar::default();
+		let overflow = nonce.set_b32(&v.clone().take());
+		if !overflow && !nonce.is_zero() {
+			return nonce;
+		}
+
+		k = hmac_sha256(&*k, &[&*v, &[0u8][..]].concat());
+		v = hmac_sha256(&*k, &*v);
+	}
 }
 
 impl DisplayLayout for Private {
@@ -48,21 +205,22 @@ impl DisplayLayout for Private {
 	}
 
 	fn from_layout(data: &[u8]) -> Result<Self, Error> where Self: Sized {
-		let compressed = match data.len() {
-			37 => false,
-			38 => true,
-			_ => return Err(Error::InvalidPrivate),
-		};
-
-		if compressed && data[data.len() - 5] != 1 {
+		if data.len() != 37 && data.len() != 38 {
 			return Err(Error::InvalidPrivate);
 		}
 
-		let sum_type = detect_checksum(&data[0..data.len() - 4], &data[data.len() - 4..])?;
-		let prefix = data[0];
+		// every checksum type is 4 bytes long, the compression flag follows the secret in the payload
+		let (payload, sum) = data.split_at(data.len() - 4);
+		let sum_type = detect_checksum(payload, sum)?;
+		let compressed = match payload.len() {
+			33 => false,
+			_ if payload[33] == 1 => true,
+			_ => return Err(Error::InvalidPrivate),
+		};
 
+		let prefix = payload[0];
 		let mut secret = Secret::default();
-		secret.copy_from_slice(&data[1..33]);
+		secret.copy_from_slice(&payload[1..33]);
 
 		let private = Private {
 			prefix,
@@ -75,6 +233,23 @@ impl DisplayLayout for Private {
 	}
 }
 
+/// Overwrites the secret, so it doesn't linger in freed memory. Best-effort only,
+/// copies made before the drop (e.g. by moves) are not cleared.
+impl Drop for Private {
+	fn drop(&mut self) {
+		for byte in self.secret.iter_mut() {
+			unsafe { ptr::write_volatile(byte, 0) };
+		}
+		compiler_fence(Ordering::SeqCst);
+	}
+}
+
+impl PartialEq for Private {
+	fn eq(&self, other: &Self) -> bool {
+		self.ct_eq(other)
+	}
+}
+
 impl fmt::Debug for Private {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		try!(writeln!(f, "prefix: {:?}", self.prefix));
@@ -106,8 +281,11 @@ impl From<&'static str> for Private {
 
 #[cfg(test)]
 mod tests {
+	use std::mem::ManuallyDrop;
+	use crypto::{checksum, sha256};
 	use hash::H256;
-	use super::{ChecksumType, Private};
+	use {DisplayLayout, KeyPair, Message, Network, Error};
+	use super::{ChecksumType, Private, compact_signature};
 
 	#[test]
 	fn test_private_to_string() {
@@ -133,6 +311,95 @@ mod tests {
 		assert_eq!("UwA3FpHWKfwrQ1DTiwbErpEnCEhvLuq1WnbfmqGBPSLNNvXtzYd5".to_owned(), private.to_string());
 	}
 
+	#[test]
+	fn test_private_new() {
+		let secret = H256::from_reversed_str("063377054c25f98bc538ac8dd2cf9064dd5d253a725ece0628a34e2f84803bd5");
+		let private = Private::new(Network::Mainnet, secret.clone(), false);
+		assert_eq!("5KSCKP8NUyBZPCCQusxRwgmz9sfvJQEgbGukmmHepWw5Bzp95mu".to_owned(), private.to_string());
+		let private = Private::new(Network::Komodo, secret, true);
+		assert_eq!("UwA3FpHWKfwrQ1DTiwbErpEnCEhvLuq1WnbfmqGBPSLNNvXtzYd5".to_owned(), private.to_string());
+	}
+
+	#[test]
+	fn test_private_ct_eq() {
+		let secret = H256::from_reversed_str("063377054c25f98bc538ac8dd2cf9064dd5d253a725ece0628a34e2f84803bd5");
+		let private = Private::new(Network::Mainnet, secret.clone(), true);
+		assert!(private.ct_eq(&private.clone()));
+		assert_eq!(private, Private::new(Network::Mainnet, secret.clone(), true));
+
+		let mut other_secret = secret.clone();
+		other_secret[31] ^= 1;
+		assert!(!private.ct_eq(&Private::new(Network::Mainnet, other_secret, true)));
+		assert!(!private.ct_eq(&Private::new(Network::Komodo, secret.clone(), true)));
+		assert_ne!(private, Private::new(Network::Mainnet, secret, false));
+	}
+
+	#[test]
+	fn test_private_for_network() {
+		let mainnet: Private = "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617".into();
+
+		// Zcash testnet uses the same WIF prefix 0xef as Bitcoin testnet
+		let testnet = mainnet.for_network(Network::Testnet);
+		assert_eq!(testnet.to_wif(), "cMzLdeGd5vEqxB8B6VFQoRopQ3sLAAvEzDAoQgvX54xwofSWj1fx");
+		assert_eq!(testnet.secret, mainnet.secret);
+		assert!(testnet.compressed);
+		assert_eq!(testnet.for_network(Network::Mainnet), mainnet);
+
+		let komodo = mainnet.for_network(Network::Komodo);
+		assert_eq!(komodo.to_wif(), "UpRBUQtkA5WqFnSztd7sCYyyhtd4aq6AggQ9sXFh2fXeSnLHtd3Z");
+		assert_eq!(komodo.network(), Some(Network::Komodo));
+
+		let smart_cash = mainnet.for_network(Network::SmartCash);
+		assert_eq!(smart_cash.checksum_type, ChecksumType::KECCAK256);
+		assert_eq!(smart_cash.network(), Some(Network::SmartCash));
+		assert_eq!(smart_cash.to_wif().parse::<Private>().unwrap(), smart_cash);
+	}
+
+	#[test]
+	fn test_private_network() {
+		let private: Private = "5KSCKP8NUyBZPCCQusxRwgmz9sfvJQEgbGukmmHepWw5Bzp95mu".into();
+		assert_eq!(private.network(), Some(Network::Mainnet));
+		assert_eq!(private.to_wif(), "5KSCKP8NUyBZPCCQusxRwgmz9sfvJQEgbGukmmHepWw5Bzp95mu");
+
+		let private: Private = "UwA3FpHWKfwrQ1DTiwbErpEnCEhvLuq1WnbfmqGBPSLNNvXtzYd5".into();
+		assert_eq!(private.network(), Some(Network::Komodo));
+		assert_eq!(private.to_wif(), "UwA3FpHWKfwrQ1DTiwbErpEnCEhvLuq1WnbfmqGBPSLNNvXtzYd5");
+
+		let private: Private = "cUjCR3fPFWfs6PtdvoinTh4ctPxBvFf5pKNKJzw1RqmfjogL7GuU".into();
+		assert_eq!(private.network(), Some(Network::Testnet));
+		assert_eq!(private.to_wif(), "cUjCR3fPFWfs6PtdvoinTh4ctPxBvFf5pKNKJzw1RqmfjogL7GuU");
+
+		let private: Private = "L196QUb5fAcBVvZizvx66ABsU7iVTS4iAz15YEgB8QWY35KfD6ox".into();
+		assert_eq!(private.network(), Some(Network::Groestlcoin));
+
+		let mut unknown = private.clone();
+		unknown.prefix = 1;
+		assert_eq!(unknown.network(), None);
+	}
+
+	#[test]
+	fn test_private_from_mini() {
+		let private = Private::from_mini("S6c56bnXQiBjk9mqSYE7ykVQ7NzrRy", 128, false).unwrap();
+		assert_eq!(private.secret, "4c7a9640c72dc2099f23715d0c8a0d8a35f8906e3cab61dd3f78b67bf887c9ab".into());
+		assert_eq!(private.prefix, 128);
+		assert!(!private.compressed);
+
+		assert_eq!(Private::from_mini("S6c56bnXQiBjk9mqSYE7ykVQ7NzrRz", 128, false), Err(Error::InvalidChecksum));
+		assert_eq!(Private::from_mini("S6c56bnXQiBjk9mqSYE7ykVQ7NzrR", 128, false), Err(Error::InvalidPrivate));
+		assert_eq!(Private::from_mini("T6c56bnXQiBjk9mqSYE7ykVQ7NzrRy", 128, false), Err(Error::InvalidPrivate));
+	}
+
+	#[test]
+	fn test_private_zeroized_on_drop() {
+		let secret = H256::from_reversed_str("063377054c25f98bc538ac8dd2cf9064dd5d253a725ece0628a34e2f84803bd5");
+		let mut private = ManuallyDrop::new(Private::new(Network::Mainnet, secret, true));
+		let secret_ptr = &private.secret as *const H256;
+		unsafe {
+			ManuallyDrop::drop(&mut private);
+			assert_eq!(*secret_ptr, H256::default());
+		}
+	}
+
     #[test]
 	fn test_private_to_string_zec_testnet() {
 		let private = Private {
@@ -194,6 +461,41 @@ mod tests {
 		assert_eq!(private.to_string(), "L196QUb5fAcBVvZizvx66ABsU7iVTS4iAz15YEgB8QWY35KfD6ox".to_owned());
 	}
 
+	#[test]
+	fn test_private_from_layout_compression() {
+		let private: Private = "L196QUb5fAcBVvZizvx66ABsU7iVTS4iAz15YEgB8QWY35KfD6ox".into();
+		assert!(private.compressed);
+		assert_eq!(private.checksum_type, ChecksumType::DGROESTL512);
+
+		let private: Private = "5KSCKP8NUyBZPCCQusxRwgmz9sfvJQEgbGukmmHepWw5Bzp95mu".into();
+		assert!(!private.compressed);
+		assert_eq!(private.checksum_type, ChecksumType::DSHA256);
+
+		for &checksum_type in &[ChecksumType::DSHA256, ChecksumType::DGROESTL512, ChecksumType::KECCAK256] {
+			for &compressed in &[false, true] {
+				let mut private = Private::new(Network::Groestlcoin, H256::from(7), compressed);
+				private.checksum_type = checksum_type;
+				let parsed = Private::from_layout(&private.layout()).unwrap();
+				assert_eq!(parsed.compressed, compressed);
+				assert_eq!(parsed.checksum_type, checksum_type);
+			}
+		}
+
+		// compression flag other than 1 with a valid checksum
+		let private = Private::new(Network::Groestlcoin, H256::from(7), true);
+		let mut layout = private.layout();
+		layout.truncate(34);
+		layout[33] = 2;
+		let sum = checksum(&layout, &ChecksumType::DGROESTL512);
+		layout.extend_from_slice(&*sum);
+		assert_eq!(Private::from_layout(&layout), Err(Error::InvalidPrivate));
+
+		// broken checksum is reported before the compression flag
+		let mut layout = private.layout();
+		layout[33] = 2;
+		assert_eq!(Private::from_layout(&layout), Err(Error::InvalidChecksum));
+	}
+
 	#[test]
 	fn test_private_from_str_smart_cash() {
 		let private = Private {
@@ -206,4 +508,59 @@ mod tests {
 		assert_eq!(private, "VFqZrZNzkJEk29Kzp87J7eXDuQFMh1UsqYcMmi9bfdAZ522nz1mv".into());
 		assert_eq!(private.to_string(), "VFqZrZNzkJEk29Kzp87J7eXDuQFMh1UsqYcMmi9bfdAZ522nz1mv".to_owned());
 	}
+
+	#[test]
+	fn test_compact_signature_length() {
+		let signature = compact_signature(31, &[1u8; 64]).unwrap();
+		assert_eq!(signature[0], 31);
+		assert_eq!(&signature[1..], &[1u8; 64][..]);
+		assert_eq!(compact_signature(31, &[1u8; 63]), Err(Error::InvalidSignature));
+		assert_eq!(compact_signature(31, &[1u8; 65]), Err(Error::InvalidSignature));
+	}
+
+	#[test]
+	fn test_private_sign_low_r() {
+		let private: Private = "5HxWvvfubhXpYYpS3tJkw6fq9jE9j18THftkZjHHfmFiWtmAbrj".into();
+		let keypair = KeyPair::from_private(private.clone()).unwrap();
+		let mut ground = 0;
+		for i in 0u8..16 {
+			let message = sha256(&[i]);
+			let signature = private.sign(&message).unwrap();
+			let low_r = private.sign_low_r(&message).unwrap();
+			assert!(low_r.len() <= 70);
+			assert!(low_r.is_valid_der());
+			assert!(keypair.public().verify(&message, &low_r).unwrap());
+			assert_eq!(private.sign_low_r(&message).unwrap(), low_r);
+			// without grinding the nonce is the same as the one of `sign`
+			if signature.has_low_r() {
+				assert_eq!(signature, low_r);
+			} else {
+				ground += 1;
+			}
+		}
+		assert!(ground > 0);
+
+		let message = sha256(b"message");
+		let with_nonce = private.sign_with_nonce(&message, &H256::from(1)).unwrap();
+		assert!(keypair.public().verify(&message, &with_nonce).unwrap());
+		assert!(with_nonce != private.sign(&message).unwrap());
+	}
+
+	#[test]
+	fn test_private_sign_display_hex_message() {
+		let private: Private = "5HxWvvfubhXpYYpS3tJkw6fq9jE9j18THftkZjHHfmFiWtmAbrj".into();
+		let keypair = KeyPair::from_private(private.clone()).unwrap();
+		// txid of the BTC genesis coinbase, as shown by block explorers
+		let display = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+		let message = Message::from_display_hex(display).unwrap();
+		let internal: Message = display.into();
+		assert_eq!(message.to_display_hex(), display);
+		assert_eq!(message, internal.reversed());
+		assert!(Message::from_display_hex("4a5e").is_err());
+
+		let signature = private.sign(&message).unwrap();
+		assert!(keypair.public().verify(&message, &signature).unwrap());
+		assert!(!keypair.public().verify(&internal, &signature).unwrap());
+		assert!(private.sign(&internal).unwrap() != signature);
+	}
 }
@@ -1,9 +1,10 @@
 use std::{fmt, ops};
-use secp256k1::{Message as SecpMessage, PublicKey, PublicKeyFormat, Signature as SecpSignature, verify};
+use secp256k1::{Error as SecpError, Message as SecpMessage, PublicKey, PublicKeyFormat, RecoveryId, SecretKey,
+	Signature as SecpSignature, recover, verify};
 use hex::ToHex;
 use crypto::dhash160;
-use hash::{H264, H520};
-use {AddressHash, Error, Signature, Message};
+use hash::{H256, H264, H520};
+use {Address, AddressHash, CompactSignature, Error, Network, Secret, Signature, Message};
 
 /// Secret public key
 #[derive(Clone)]
@@ -21,6 +22,7 @@ impl Default for Public {
 }
 
 impl Public {
+	/// Checks only the length of the key, use `validate` to check that it is a valid curve point.
 	pub fn from_slice(data: &[u8]) -> Result<Self, Error> {
 		match data.len() {
 			33 => {
@@ -41,11 +43,77 @@ impl Public {
 		dhash160(self)
 	}
 
-	pub fn verify(&self, message: &Message, signature: &Signature) -> Result<bool, Error> {
+	/// Recovers public key from compact signature of the message.
+	/// The header byte tells the recovery id and whether the key is compressed.
+	pub fn recover_compact(message: &Message, signature: &CompactSignature) -> Result<Self, Error> {
+		let recovery_id = RecoveryId::parse(signature.recovery_id()?)?;
+		let compressed = signature.is_compressed()?;
+		let signature = SecpSignature::parse_slice(&signature[1..65])?;
+		let message = SecpMessage::parse_slice(&**message)?;
+		let public = recover(&message, &signature, &recovery_id)?;
+		if compressed {
+			Public::from_slice(&public.serialize_compressed())
+		} else {
+			Public::from_slice(&public.serialize())
+		}
+	}
+
+	fn to_secp(&self) -> Result<PublicKey, Error> {
 		let public = match self {
 			Public::Compressed(public) => PublicKey::parse_slice(&**public, Some(PublicKeyFormat::Compressed))?,
 			Public::Normal(public) => PublicKey::parse_slice(&**public, Some(PublicKeyFormat::Full))?,
 		};
+		Ok(public)
+	}
+
+	/// Checks that the key has a valid prefix byte and encodes a point on the curve.
+	pub fn validate(&self) -> Result<(), Error> {
+		self.to_secp().map(|_| ()).map_err(|_| Error::InvalidPublic)
+	}
+
+	/// Adds `tweak * G` to the public key point, keeping the compression of the key.
+	/// This is the core of BIP32 non-hardened public derivation.
+	pub fn add_tweak(&self, tweak: &Secret) -> Result<Self, Error> {
+		let mut public = self.to_secp()?;
+		let tweak = SecretKey::parse_slice(&**tweak)?;
+		public.tweak_add_assign(&tweak).map_err(|e| match e {
+			SecpError::TweakOutOfRange => Error::InvalidPublic,
+			e => e.into(),
+		})?;
+
+		match *self {
+			Public::Compressed(_) => Public::from_slice(&public.serialize_compressed()),
+			Public::Normal(_) => Public::from_slice(&public.serialize()),
+		}
+	}
+
+	/// Returns x coordinate of the public key and true if its y coordinate is odd.
+	pub fn xonly_parts(&self) -> Result<(H256, bool), Error> {
+		let compressed = self.to_secp()?.serialize_compressed();
+		let mut x = H256::default();
+		x.copy_from_slice(&compressed[1..]);
+		Ok((x, compressed[0] == 0x03))
+	}
+
+	/// Returns all standard addresses of the key: P2PKH, P2SH-P2WPKH and P2WPKH.
+	/// Segwit addresses are skipped for uncompressed keys and networks without segwit support.
+	pub fn all_addresses(&self, network: &Network) -> Result<Vec<Address>, Error> {
+		self.to_secp()?;
+		let hash = self.address_hash();
+		let mut addresses = vec![Address::from_public(self, *network)];
+		if let (&Public::Compressed(_), Some(hrp)) = (self, network.bech32_hrp()) {
+			// redeem script: OP_0 <20 bytes public key hash>
+			let mut redeem_script = vec![0x00, 0x14];
+			redeem_script.extend_from_slice(&*hash);
+			addresses.push(Address::from_script_hash(&dhash160(&redeem_script), *network));
+			addresses.push(Address::p2wpkh(hrp, hash));
+		}
+
+		Ok(addresses)
+	}
+
+	pub fn verify(&self, message: &Message, signature: &Signature) -> Result<bool, Error> {
+		let public = self.to_secp()?;
 		let mut signature = SecpSignature::parse_der_lax(signature)?;
 		signature.normalize_s();
 		let message = SecpMessage::parse_slice(&**message)?;
@@ -53,6 +121,18 @@ impl Public {
 	}
 }
 
+/// Verifies ECDSA signatures one by one, stopping at the first invalid one.
+/// Returns `Ok(true)` only if all signatures are valid.
+pub fn verify_batch(items: &[(Public, Message, Signature)]) -> Result<bool, Error> {
+	for (public, message, signature) in items {
+		if !public.verify(message, signature)? {
+			return Ok(false);
+		}
+	}
+
+	Ok(true)
+}
+
 impl ops::Deref for Public {
 	type Target = [u8];
 
@@ -86,3 +166,107 @@ impl fmt::Display for Public {
 		self.to_hex::<String>().fmt(f)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use bytes::Bytes;
+	use hash::H256;
+	use secp256k1::SecretKey;
+	use {Error, KeyPair, Message, Network, Private, Secret};
+	use super::{Public, verify_batch};
+
+	fn public(hex: &'static str) -> Public {
+		Public::from_slice(&Bytes::from(hex)).unwrap()
+	}
+
+	#[test]
+	fn test_public_xonly_parts() {
+		let x: H256 = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798".into();
+		assert_eq!(public("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").xonly_parts(), Ok((x.clone(), false)));
+		assert_eq!(public("0379be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").xonly_parts(), Ok((x.clone(), true)));
+		let normal = public("0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8");
+		assert_eq!(normal.xonly_parts(), Ok((x, false)));
+		let invalid = public("02ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+		assert_eq!(invalid.xonly_parts(), Err(Error::InvalidPublic));
+	}
+
+	#[test]
+	fn test_public_validate() {
+		assert_eq!(public("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").validate(), Ok(()));
+		assert_eq!(public("0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8").validate(), Ok(()));
+		// compressed length with uncompressed prefix
+		assert_eq!(public("0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").validate(), Err(Error::InvalidPublic));
+		// x = 5 is not on the curve
+		assert_eq!(public("020000000000000000000000000000000000000000000000000000000000000005").validate(), Err(Error::InvalidPublic));
+		// (1, 1) is not on the curve
+		assert_eq!(public("0400000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000001").validate(), Err(Error::InvalidPublic));
+	}
+
+	#[test]
+	fn test_public_all_addresses() {
+		let compressed = public("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+		let addresses: Vec<String> = compressed.all_addresses(&Network::Mainnet).unwrap().iter().map(ToString::to_string).collect();
+		assert_eq!(addresses, vec![
+			"1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH",
+			"3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN",
+			"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+		]);
+
+		let normal = public("0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8");
+		let addresses: Vec<String> = normal.all_addresses(&Network::Mainnet).unwrap().iter().map(ToString::to_string).collect();
+		assert_eq!(addresses, vec!["1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm"]);
+
+		assert_eq!(compressed.all_addresses(&Network::Komodo).unwrap().len(), 1);
+		let invalid = public("02ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+		assert_eq!(invalid.all_addresses(&Network::Mainnet), Err(Error::InvalidPublic));
+	}
+
+	#[test]
+	fn test_public_add_tweak() {
+		let tweak: Secret = "f3bf3cbcbd5c9d2a6ab0ae2b8fd3bca6b2fa4a3cce6b27e82c7c8de1eae2d7f5".into();
+		for &wif in &["5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ", "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617"] {
+			let private: Private = wif.into();
+			let public = KeyPair::from_private(private.clone()).unwrap().public().clone();
+
+			let mut secret = SecretKey::parse_slice(&*private.secret).unwrap();
+			secret.tweak_add_assign(&SecretKey::parse_slice(&*tweak).unwrap()).unwrap();
+			let mut tweaked = private.clone();
+			tweaked.secret = Secret::from(&secret.serialize()[..]);
+			let expected = KeyPair::from_private(tweaked).unwrap().public().clone();
+
+			assert_eq!(public.add_tweak(&tweak), Ok(expected));
+		}
+	}
+
+	#[test]
+	fn test_public_add_tweak_infinity() {
+		let generator = public("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+		// n - 1
+		let tweak: Secret = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140".into();
+		assert_eq!(generator.add_tweak(&tweak), Err(Error::InvalidPublic));
+		// n
+		let tweak: Secret = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141".into();
+		assert_eq!(generator.add_tweak(&tweak), Err(Error::InvalidSecret));
+	}
+
+	#[test]
+	fn test_verify_batch() {
+		let keypairs: Vec<KeyPair> = (1u8..4)
+			.map(|i| KeyPair::from_private(Private::new(Network::Mainnet, Secret::from(i), true)).unwrap())
+			.collect();
+		let mut items: Vec<_> = keypairs.iter().enumerate().map(|(i, keypair)| {
+			let message = Message::from(i as u8 + 10);
+			let signature = keypair.private().sign(&message).unwrap();
+			(keypair.public().clone(), message, signature)
+		}).collect();
+
+		assert_eq!(verify_batch(&[]), Ok(true));
+		assert_eq!(verify_batch(&items), Ok(true));
+
+		items[1].1 = Message::from(100);
+		assert_eq!(verify_batch(&items), Ok(false));
+
+		items[1].0 = public("02ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+		assert_eq!(verify_batch(&items[1..]), Err(Error::InvalidPublic));
+	}
+}
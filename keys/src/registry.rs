@@ -0,0 +1,159 @@
+//! Runtime registry of network address and private key prefixes.
+//!
+//! Allows downstream crates to add their own coins without modifying `keys`.
+
+use std::sync::RwLock;
+use crypto::ChecksumType;
+use {Address, Private, Error, Network};
+
+/// Prefixes used by a single network.
+#[derive(Debug, PartialEq, Clone)]
+pub struct NetworkParams {
+	/// Unique name of the network.
+	pub name: String,
+	/// Prefix of pay to public key hash addresses.
+	pub pubkey_prefix: u8,
+	/// Prefix of pay to script hash addresses.
+	pub script_prefix: u8,
+	/// T addr prefix, additional prefix used by Zcash and some forks. 0 if not used.
+	pub t_addr_prefix: u8,
+	/// Prefix of WIF encoded private keys.
+	pub wif_prefix: u8,
+	/// Checksum type used by base58 encoding.
+	pub checksum_type: ChecksumType,
+}
+
+impl NetworkParams {
+	fn new(name: &str, network: Network) -> Self {
+		NetworkParams {
+			name: name.into(),
+			pubkey_prefix: network.p2pkh_prefix(),
+			script_prefix: network.p2sh_prefix(),
+			t_addr_prefix: network.t_addr_prefix(),
+			wif_prefix: network.wif_prefix(),
+			checksum_type: network.checksum_type(),
+		}
+	}
+
+	fn matches_address_prefix(&self, t_addr_prefix: u8, prefix: u8) -> bool {
+		(prefix == self.pubkey_prefix || prefix == self.script_prefix) && t_addr_prefix == self.t_addr_prefix
+	}
+
+	fn matches_address(&self, address: &Address) -> bool {
+		self.matches_address_prefix(address.t_addr_prefix, address.prefix) && address.checksum_type == self.checksum_type
+	}
+
+	fn matches_private(&self, private: &Private) -> bool {
+		private.prefix == self.wif_prefix && private.checksum_type == self.checksum_type
+	}
+}
+
+lazy_static! {
+	// regtest is not listed, as it shares prefixes with testnet
+	static ref REGISTRY: RwLock<Vec<NetworkParams>> = RwLock::new(vec![
+		NetworkParams::new("bitcoin", Network::Mainnet),
+		NetworkParams::new("testnet", Network::Testnet),
+		NetworkParams::new("komodo", Network::Komodo),
+		NetworkParams::new("zcash", Network::Zcash),
+		// only transparent P2PKH addresses, which use different t addr prefix than P2SH ones
+		NetworkParams {
+			name: "zcash-testnet".into(),
+			pubkey_prefix: 37,
+			script_prefix: 37,
+			t_addr_prefix: 29,
+			wif_prefix: 239,
+			checksum_type: ChecksumType::DSHA256,
+		},
+		NetworkParams::new("groestlcoin", Network::Groestlcoin),
+		NetworkParams::new("smartcash", Network::SmartCash),
+	]);
+}
+
+/// Registers new network. Replaces previously registered network with the same name.
+/// Networks registered later take precedence when detecting network of a parsed string.
+pub fn register_network(params: NetworkParams) {
+	let mut registry = REGISTRY.write().expect("registry lock is never poisoned");
+	registry.retain(|n| n.name != params.name);
+	registry.push(params);
+}
+
+/// Returns network registered with given name.
+pub fn network_by_name(name: &str) -> Option<NetworkParams> {
+	let registry = REGISTRY.read().expect("registry lock is never poisoned");
+	registry.iter().find(|n| n.name == name).cloned()
+}
+
+/// Returns true if address prefixes belong to any registered network.
+pub fn is_known_address_prefix(t_addr_prefix: u8, prefix: u8) -> bool {
+	let registry = REGISTRY.read().expect("registry lock is never poisoned");
+	registry.iter().any(|n| n.matches_address_prefix(t_addr_prefix, prefix))
+}
+
+/// Parses base58 address and detects its network.
+pub fn parse_address(s: &str) -> Result<(Address, NetworkParams), Error> {
+	let address: Address = s.parse()?;
+	let registry = REGISTRY.read().expect("registry lock is never poisoned");
+	let network = registry.iter().rev().find(|n| n.matches_address(&address)).cloned().ok_or(Error::InvalidNetwork)?;
+	Ok((address, network))
+}
+
+/// Parses WIF encoded private key and detects its network.
+pub fn parse_private(s: &str) -> Result<(Private, NetworkParams), Error> {
+	let private: Private = s.parse()?;
+	let registry = REGISTRY.read().expect("registry lock is never poisoned");
+	let network = registry.iter().rev().find(|n| n.matches_private(&private)).cloned().ok_or(Error::InvalidNetwork)?;
+	Ok((private, network))
+}
+
+#[cfg(test)]
+mod tests {
+	use crypto::ChecksumType;
+	use {Address, Private, Error};
+	use super::{NetworkParams, register_network, network_by_name, parse_address, parse_private};
+
+	#[test]
+	fn test_registry_builtin_networks() {
+		let (_, network) = parse_address("16meyfSoQV6twkAAxPe51RtMVz7PGRmWna").unwrap();
+		assert_eq!(network.name, "bitcoin");
+		let (_, network) = parse_address("R9o9xTocqr6CeEDGDH6mEYpwLoMz6jNjMW").unwrap();
+		assert_eq!(network.name, "komodo");
+		let (_, network) = parse_private("UwA3FpHWKfwrQ1DTiwbErpEnCEhvLuq1WnbfmqGBPSLNNvXtzYd5").unwrap();
+		assert_eq!(network.name, "komodo");
+	}
+
+	#[test]
+	fn test_registry_custom_network() {
+		let params = NetworkParams {
+			name: "registry-test-coin".into(),
+			pubkey_prefix: 30,
+			script_prefix: 22,
+			t_addr_prefix: 0,
+			wif_prefix: 158,
+			checksum_type: ChecksumType::DSHA256,
+		};
+
+		let address = Address {
+			prefix: 30,
+			t_addr_prefix: 0,
+			hash: "3f4aa1fedf1f54eeb03b759deadb36676b184911".into(),
+			checksum_type: ChecksumType::DSHA256,
+			..Default::default()
+		};
+		let private = Private {
+			prefix: 158,
+			secret: "063377054c25f98bc538ac8dd2cf9064dd5d253a725ece0628a34e2f84803bd5".into(),
+			compressed: true,
+			checksum_type: ChecksumType::DSHA256,
+		};
+
+		assert_eq!(network_by_name("registry-test-coin"), None);
+		assert_eq!(address.to_string().parse::<Address>(), Err(Error::InvalidAddress));
+		assert_eq!(parse_address(&address.to_string()).unwrap_err(), Error::InvalidAddress);
+		assert_eq!(parse_private(&private.to_string()).unwrap_err(), Error::InvalidNetwork);
+
+		register_network(params.clone());
+		assert_eq!(network_by_name("registry-test-coin"), Some(params.clone()));
+		assert_eq!(parse_address(&address.to_string()), Ok((address, params.clone())));
+		assert_eq!(parse_private(&private.to_string()), Ok((private, params)));
+	}
+}
@@ -0,0 +1,178 @@
+//! BIP340 Schnorr signatures verification.
+//!
+//! https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki
+
+use rand::{OsRng, Rng};
+use secp256k1::curve::{Affine, Field, Jacobian, Scalar, ECMULT_CONTEXT, ECMULT_GEN_CONTEXT};
+use crypto::tagged_hash;
+use hash::{H256, H512};
+use {Error, Message, XOnlyPublic};
+
+/// Point with given x coordinate and even y coordinate.
+fn lift_x(x: &[u8]) -> Option<Affine> {
+	let mut bytes = [0u8; 32];
+	bytes.copy_from_slice(x);
+	let mut field = Field::default();
+	if !field.set_b32(&bytes) {
+		return None;
+	}
+
+	let mut point = Affine::default();
+	if !point.set_xo_var(&field, false) {
+		return None;
+	}
+
+	Some(point)
+}
+
+fn scalar_from(bytes: &[u8]) -> (Scalar, bool) {
+	let mut b32 = [0u8; 32];
+	b32.copy_from_slice(bytes);
+	let mut scalar = Scalar::default();
+	let overflow = scalar.set_b32(&b32);
+	(scalar, overflow)
+}
+
+/// Signature parts: point `R` and scalar `s`, or `None` if `r >= p` or `s >= n`.
+fn parse_signature(signature: &H512) -> Option<(Affine, Scalar)> {
+	let r = lift_x(&signature[..32])?;
+	match scalar_from(&signature[32..]) {
+		(_, true) => None,
+		(s, false) => Some((r, s)),
+	}
+}
+
+/// `e = int(hashBIP0340/challenge(r || P || m)) mod n`
+fn challenge(r: &[u8], public: &XOnlyPublic, message: &Message) -> Scalar {
+	let mut data = Vec::with_capacity(96);
+	data.extend_from_slice(r);
+	data.extend_from_slice(public);
+	data.extend_from_slice(&**message);
+	scalar_from(&*tagged_hash(b"BIP0340/challenge", &data)).0
+}
+
+/// Verifies BIP340 signature of the message.
+pub fn verify_schnorr(public: &XOnlyPublic, message: &Message, signature: &H512) -> Result<bool, Error> {
+	let p = lift_x(public).ok_or(Error::InvalidPublic)?;
+	let s = match scalar_from(&signature[32..]) {
+		(_, true) => return Ok(false),
+		(s, false) => s,
+	};
+
+	let e = challenge(&signature[..32], public, message);
+	// R = s * G - e * P
+	let mut r = Jacobian::default();
+	ECMULT_CONTEXT.ecmult(&mut r, &Jacobian::from_ge(&p), &e.neg(), &s);
+	let mut r = Affine::from_gej(&r);
+	if r.is_infinity() {
+		return Ok(false);
+	}
+
+	r.x.normalize_var();
+	r.y.normalize_var();
+	Ok(!r.y.is_odd() && r.x.b32() == signature[..32])
+}
+
+/// Verifies multiple BIP340 signatures at once using random linear combination.
+/// Returns false if any of the signatures is invalid.
+pub fn verify_schnorr_batch(items: &[(XOnlyPublic, Message, H512)]) -> Result<bool, Error> {
+	let mut rng = OsRng::new().map_err(|_| Error::FailedKeyGeneration)?;
+	// sum of a_i * s_i
+	let mut s_sum = Scalar::default();
+	// sum of a_i * R_i + a_i * e_i * P_i
+	let mut point_sum = Jacobian::default();
+	point_sum.set_infinity();
+
+	for (i, (public, message, signature)) in items.iter().enumerate() {
+		let p = lift_x(public).ok_or(Error::InvalidPublic)?;
+		let (r, s) = match parse_signature(signature) {
+			Some(parts) => parts,
+			None => return Ok(false),
+		};
+
+		// a_1 = 1, others are random
+		let a = if i == 0 {
+			Scalar::from_int(1)
+		} else {
+			let mut random = H256::default();
+			rng.fill_bytes(&mut *random);
+			scalar_from(&*random).0
+		};
+
+		let e = challenge(&signature[..32], public, message);
+		let mut r_term = Jacobian::default();
+		ECMULT_CONTEXT.ecmult(&mut r_term, &Jacobian::from_ge(&r), &a, &Scalar::default());
+		let mut p_term = Jacobian::default();
+		ECMULT_CONTEXT.ecmult(&mut p_term, &Jacobian::from_ge(&p), &(&a * &e), &Scalar::default());
+		point_sum = point_sum.add_var(&r_term, None).add_var(&p_term, None);
+
+		s_sum += &a * &s;
+	}
+
+	let mut s_g = Jacobian::default();
+	ECMULT_GEN_CONTEXT.ecmult_gen(&mut s_g, &s_sum);
+	Ok(s_g.add_var(&point_sum.neg(), None).is_infinity())
+}
+
+#[cfg(test)]
+mod tests {
+	use hash::{H256, H512};
+	use {Error, XOnlyPublic};
+	use super::{verify_schnorr, verify_schnorr_batch};
+
+	// https://github.com/bitcoin/bips/blob/master/bip-0340/test-vectors.csv
+	fn vectors() -> Vec<(XOnlyPublic, H256, H512)> {
+		vec![
+			(
+				H256::from("f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9").into(),
+				"0000000000000000000000000000000000000000000000000000000000000000".into(),
+				"e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca821525f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0".into(),
+			),
+			(
+				H256::from("dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659").into(),
+				"243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89".into(),
+				"6896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de33418906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a".into(),
+			),
+			(
+				H256::from("dd308afec5777e13121fa72b9cc1b7cc0139715309b086c960e18fd969774eb8").into(),
+				"7e2d58d8b3bcdf1abadec7829054f90dda9805aab56c77333024b9d0a508b75c".into(),
+				"5831aaeed7b44bb74e5eab94ba9d4294c49bcf2a60728d8b4c200f50dd313c1bab745879a5ad954a72c45a91c3a51d3c7adea98d82f8481e0e1e03674a6f3fb7".into(),
+			),
+			(
+				H256::from("25d1dff95105f5253c4022f628a996ad3a0d95fbf21d468a1b33f8c160d8f517").into(),
+				"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff".into(),
+				"7eb0509757e246f19449885651611cb965ecc1a187dd51b64fda1edc9637d5ec97582b9cb13db3933705b32ba982af5af25fd78881ebb32771fc5922efc66ea3".into(),
+			),
+		]
+	}
+
+	#[test]
+	fn test_verify_schnorr() {
+		for (public, message, signature) in vectors() {
+			assert_eq!(verify_schnorr(&public, &message, &signature), Ok(true));
+			let mut invalid = signature.clone();
+			invalid[63] ^= 1;
+			assert_eq!(verify_schnorr(&public, &message, &invalid), Ok(false));
+		}
+
+		let (_, message, signature) = vectors().remove(0);
+		let invalid_public: XOnlyPublic = H256::from("eefdea4cdb677750a420fee807eacf21eb9898ae79b9768766e4faa04a2d4a34").into();
+		assert_eq!(verify_schnorr(&invalid_public, &message, &signature), Err(Error::InvalidPublic));
+	}
+
+	#[test]
+	fn test_verify_schnorr_batch() {
+		let mut items = vectors();
+		assert_eq!(verify_schnorr_batch(&items), Ok(true));
+		assert_eq!(verify_schnorr_batch(&[]), Ok(true));
+
+		items[2].2[40] ^= 1;
+		assert_eq!(verify_schnorr_batch(&items), Ok(false));
+
+		// valid signatures for other messages
+		let mut items = vectors();
+		let message = items[0].1.clone();
+		items[1].1 = message;
+		assert_eq!(verify_schnorr_batch(&items), Ok(false));
+	}
+}
@@ -0,0 +1,62 @@
+//! Arithmetic of secrets modulo the secp256k1 curve order.
+
+use secp256k1::{Error as SecpError, SecretKey};
+use {Error, Secret};
+
+/// Returns `(a + b) mod n`. Fails if the result is zero.
+pub fn add_secret(a: &Secret, b: &Secret) -> Result<Secret, Error> {
+	let mut result = SecretKey::parse_slice(&**a)?;
+	result.tweak_add_assign(&SecretKey::parse_slice(&**b)?).map_err(secret_error)?;
+	Ok(result.serialize().into())
+}
+
+/// Returns `(a * b) mod n`. Fails if the result is zero.
+pub fn multiply_secret(a: &Secret, b: &Secret) -> Result<Secret, Error> {
+	let mut result = SecretKey::parse_slice(&**a)?;
+	result.tweak_mul_assign(&SecretKey::parse_slice(&**b)?).map_err(secret_error)?;
+	Ok(result.serialize().into())
+}
+
+fn secret_error(e: SecpError) -> Error {
+	match e {
+		SecpError::TweakOutOfRange => Error::InvalidSecret,
+		e => e.into(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use {Error, Secret};
+	use super::{add_secret, multiply_secret};
+
+	fn secret(hex: &'static str) -> Secret {
+		hex.into()
+	}
+
+	const N_MINUS_1: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140";
+	const N_MINUS_2: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd036413f";
+	const N: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+	const ONE: &str = "0000000000000000000000000000000000000000000000000000000000000001";
+	const TWO: &str = "0000000000000000000000000000000000000000000000000000000000000002";
+	const THREE: &str = "0000000000000000000000000000000000000000000000000000000000000003";
+	const SIX: &str = "0000000000000000000000000000000000000000000000000000000000000006";
+
+	#[test]
+	fn test_add_secret() {
+		assert_eq!(add_secret(&secret(ONE), &secret(TWO)), Ok(secret(THREE)));
+		assert_eq!(add_secret(&secret(N_MINUS_1), &secret(THREE)), Ok(secret(TWO)));
+		assert_eq!(add_secret(&secret(N_MINUS_1), &secret(N_MINUS_1)), Ok(secret(N_MINUS_2)));
+		assert_eq!(add_secret(&secret(N_MINUS_1), &secret(ONE)), Err(Error::InvalidSecret));
+		assert_eq!(add_secret(&secret(N), &secret(ONE)), Err(Error::InvalidSecret));
+		assert_eq!(add_secret(&Secret::default(), &secret(ONE)), Err(Error::InvalidSecret));
+	}
+
+	#[test]
+	fn test_multiply_secret() {
+		assert_eq!(multiply_secret(&secret(TWO), &secret(THREE)), Ok(secret(SIX)));
+		assert_eq!(multiply_secret(&secret(N_MINUS_1), &secret(N_MINUS_1)), Ok(secret(ONE)));
+		assert_eq!(multiply_secret(&secret(N_MINUS_1), &secret(TWO)), Ok(secret(N_MINUS_2)));
+		assert_eq!(multiply_secret(&secret(TWO), &Secret::default()), Err(Error::InvalidSecret));
+		assert_eq!(multiply_secret(&secret(N), &secret(TWO)), Err(Error::InvalidSecret));
+	}
+}
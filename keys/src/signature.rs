@@ -5,7 +5,7 @@
 use std::{fmt, ops, str};
 use hex::{ToHex, FromHex};
 use hash::H520;
-use Error;
+use {Address, Error, Message, Network, Public, Type};
 
 #[derive(PartialEq, Clone)]
 pub struct Signature(Vec<u8>);
@@ -57,10 +57,94 @@ impl From<Signature> for Vec<u8> {
 	}
 }
 
+/// Half of the secp256k1 curve order.
+const HALF_ORDER: [u8; 32] = [
+	0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+	0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
 impl Signature {
+	/// Splits signature pushed by a script sig into DER encoded signature and the trailing sighash type byte.
+	pub fn split_sighash(raw: &[u8]) -> Result<(Signature, u32), Error> {
+		let (sighash, der) = raw.split_last().ok_or(Error::InvalidSignature)?;
+		let signature = Signature(der.to_vec());
+		if !signature.is_valid_der() {
+			return Err(Error::InvalidSignature);
+		}
+
+		Ok((signature, u32::from(*sighash)))
+	}
+
+	/// Returns true if S value of the signature is not greater than half of the curve order.
 	pub fn check_low_s(&self) -> bool {
-		unimplemented!();
+		let s = match self.s_bytes() {
+			Some(s) => s,
+			None => return false,
+		};
+
+		let start = s.iter().position(|b| *b != 0).unwrap_or(s.len());
+		let s = &s[start..];
+		if s.len() > 32 {
+			return false;
+		}
+
+		let mut padded = [0u8; 32];
+		padded[32 - s.len()..].copy_from_slice(s);
+		padded <= HALF_ORDER
+	}
+
+	/// Returns true if R value of the signature is below 2^255, so it's encoded without padding in at most 32 bytes.
+	pub fn has_low_r(&self) -> bool {
+		self.0.get(3).is_some_and(|len_r| *len_r <= 32)
 	}
+
+	/// Returns true if the signature is strictly DER encoded (BIP66) and has low S value.
+	pub fn check_low_der(&self) -> bool {
+		self.is_valid_der() && self.check_low_s()
+	}
+
+	/// Returns true if the signature is strictly DER encoded, as required by BIP66:
+	/// `0x30 <total len> 0x02 <len R> <R> 0x02 <len S> <S>`, where R and S are
+	/// positive integers encoded without excessive padding.
+	pub fn is_valid_der(&self) -> bool {
+		let sig = &self.0;
+		if sig.len() < 8 || sig.len() > 72 {
+			return false;
+		}
+
+		if sig[0] != 0x30 || sig[1] as usize != sig.len() - 2 {
+			return false;
+		}
+
+		let len_r = sig[3] as usize;
+		if len_r + 5 >= sig.len() {
+			return false;
+		}
+
+		let len_s = sig[len_r + 5] as usize;
+		if len_r + len_s + 6 != sig.len() {
+			return false;
+		}
+
+		is_valid_der_integer(sig[2], &sig[4..4 + len_r]) && is_valid_der_integer(sig[len_r + 4], &sig[len_r + 6..])
+	}
+
+	/// S value of the signature, without validating the encoding.
+	fn s_bytes(&self) -> Option<&[u8]> {
+		let len_r = *self.0.get(3)? as usize;
+		let len_s = *self.0.get(len_r + 5)? as usize;
+		self.0.get(len_r + 6..len_r + 6 + len_s)
+	}
+}
+
+/// Integers must be positive and must not start with a null byte,
+/// unless it would be interpreted as a negative number otherwise.
+fn is_valid_der_integer(tag: u8, value: &[u8]) -> bool {
+	if tag != 2 || value.is_empty() || value[0] & 0x80 != 0 {
+		return false;
+	}
+
+	!(value.len() > 1 && value[0] == 0 && value[1] & 0x80 == 0)
 }
 
 impl<'a> From<&'a [u8]> for Signature {
@@ -114,3 +198,162 @@ impl From<H520> for CompactSignature {
 		CompactSignature(h)
 	}
 }
+
+impl CompactSignature {
+	/// Returns the header byte, `27 + recovery_id`, plus 4 if the public key is compressed.
+	fn header(&self) -> Result<u8, Error> {
+		match self.0[0] {
+			header @ 27..=34 => Ok(header),
+			_ => Err(Error::InvalidSignature),
+		}
+	}
+
+	/// Returns recovery id of the signature, encoded in the header byte.
+	pub fn recovery_id(&self) -> Result<u8, Error> {
+		Ok((self.header()? - 27) & 3)
+	}
+
+	/// Returns true if the signer public key is compressed, as encoded in the header byte.
+	pub fn is_compressed(&self) -> Result<bool, Error> {
+		Ok(self.header()? >= 31)
+	}
+
+	/// Recovers the signer public key and returns its P2PKH address on the network.
+	pub fn recover_address(&self, message: &Message, network: &Network) -> Result<Address, Error> {
+		let public = Public::recover_compact(message, self)?;
+		Ok(Address::from_public(&public, *network))
+	}
+
+	/// Checks that the message was signed by the owner of P2PKH address.
+	/// The key is hashed compressed or uncompressed, as the signature header tells.
+	pub fn verify_address(&self, message: &Message, address: &Address) -> Result<bool, Error> {
+		if address.kind() != Type::P2PKH {
+			return Err(Error::InvalidAddress);
+		}
+
+		let public = Public::recover_compact(message, self)?;
+		Ok(public.address_hash() == address.hash)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use hex::FromHex;
+	use {Address, Error, Network};
+	use super::{Signature, CompactSignature};
+
+	const R: &str = "5dbbddda71772d95ce91cd2d14b592cfbc1dd0aabd6a394b6c2d377bbe59d31d";
+	const S: &str = "14ddda21494a4e221f0824f0b8b924c43fa43c0ad57dccdaa11f81a6bd4582f6";
+
+	fn signature(parts: &[&str]) -> Signature {
+		parts.concat().parse().unwrap()
+	}
+
+	#[test]
+	fn test_signature_is_valid_der() {
+		let valid = signature(&["3044", "0220", R, "0220", S]);
+		assert!(valid.is_valid_der());
+		assert!(valid.check_low_der());
+		assert!(valid.has_low_r());
+		assert!(!signature(&["3045", "022100", "dd", &R[2..], "0220", S]).has_low_r());
+
+		// over-long total length
+		assert!(!signature(&["3045", "0220", R, "0220", S]).is_valid_der());
+		// negative R
+		assert!(!signature(&["3044", "0220", "dd", &R[2..], "0220", S]).is_valid_der());
+		// R padded with unnecessary null byte
+		assert!(!signature(&["3045", "022100", R, "0220", S]).is_valid_der());
+		// trailing bytes
+		assert!(!signature(&["3044", "0220", R, "0220", S, "01"]).is_valid_der());
+		// zero-length S
+		assert!(!signature(&["3024", "0220", R, "0200"]).is_valid_der());
+	}
+
+	#[test]
+	fn test_signature_check_low_der() {
+		let high_s = "7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a1";
+		let half_order = "7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0";
+		assert!(signature(&["3044", "0220", R, "0220", half_order]).check_low_der());
+		assert!(signature(&["3045", "0221", "00", "ff", &R[2..], "0220", S]).check_low_der());
+
+		let high = signature(&["3044", "0220", R, "0220", high_s]);
+		assert!(high.is_valid_der());
+		assert!(!high.check_low_s());
+		assert!(!high.check_low_der());
+	}
+
+	#[test]
+	fn test_signature_split_sighash() {
+		// script sig pushes of BTC transaction 4ab5828480046524afa3fac5eb7f93f768c3eeeaeb5d4d6b6ff22801d3dc521e
+		let der = "3045022100a8fdfac02ecba2cfa25d74f76dcfba41791563d9aac29063dab7f9865009212002200a79c035e48f675c0527f33926ebdbb8dbae89c0a77f1e7ba229126b9fa97cc6";
+		let raw: Vec<u8> = format!("{}01", der).from_hex().unwrap();
+		assert_eq!(Signature::split_sighash(&raw), Ok((der.parse().unwrap(), 1)));
+
+		let der = "30440220103bac3e985912b388f48cc979f82821cb637f690fdd497efe4fceb86e00122f022026173b0e6a5e5eef7483b94f7589e78810eae8f8249ff7b03876f6ae24faa19b";
+		let raw: Vec<u8> = format!("{}83", der).from_hex().unwrap();
+		assert_eq!(Signature::split_sighash(&raw), Ok((der.parse().unwrap(), 0x83)));
+
+		assert_eq!(Signature::split_sighash(&raw[..raw.len() - 1]), Err(Error::InvalidSignature));
+		assert_eq!(Signature::split_sighash(&[]), Err(Error::InvalidSignature));
+		assert_eq!(Signature::split_sighash(&[1]), Err(Error::InvalidSignature));
+	}
+
+	#[test]
+	fn test_compact_signature_recover_address() {
+		// signed message "hello world" by the secret 1
+		let message = "0b6b6ce07bc55ee4aeba0098a5e5d2c8986cab228a54199723f9962316633733".into();
+		let compressed: CompactSignature = "2085d59f55d9d6ab3e50f00cc78016d563528a2c03ae958eeec4f4e53a8ca5ad0c123edb331b9581179b70b993a70d4a711f2e2d09df6a06dfa0a4e4f40b681e44".into();
+		let address = compressed.recover_address(&message, &Network::Mainnet).unwrap();
+		assert_eq!(address.to_string(), "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH");
+
+		let normal: CompactSignature = "1c85d59f55d9d6ab3e50f00cc78016d563528a2c03ae958eeec4f4e53a8ca5ad0c123edb331b9581179b70b993a70d4a711f2e2d09df6a06dfa0a4e4f40b681e44".into();
+		let address = normal.recover_address(&message, &Network::Mainnet).unwrap();
+		assert_eq!(address.to_string(), "1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm");
+
+		let other_message = "0b6b6ce07bc55ee4aeba0098a5e5d2c8986cab228a54199723f9962316633734".into();
+		assert!(compressed.recover_address(&other_message, &Network::Mainnet).unwrap().to_string() != "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH");
+
+		let invalid: CompactSignature = "2385d59f55d9d6ab3e50f00cc78016d563528a2c03ae958eeec4f4e53a8ca5ad0c123edb331b9581179b70b993a70d4a711f2e2d09df6a06dfa0a4e4f40b681e44".into();
+		assert_eq!(invalid.recover_address(&message, &Network::Mainnet), Err(Error::InvalidSignature));
+	}
+
+	#[test]
+	fn test_compact_signature_verify_address() {
+		let message = "0b6b6ce07bc55ee4aeba0098a5e5d2c8986cab228a54199723f9962316633733".into();
+		let compressed_address: Address = "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH".parse().unwrap();
+		let normal_address: Address = "1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm".parse().unwrap();
+
+		let compressed: CompactSignature = "2085d59f55d9d6ab3e50f00cc78016d563528a2c03ae958eeec4f4e53a8ca5ad0c123edb331b9581179b70b993a70d4a711f2e2d09df6a06dfa0a4e4f40b681e44".into();
+		assert_eq!(compressed.verify_address(&message, &compressed_address), Ok(true));
+		assert_eq!(compressed.verify_address(&message, &normal_address), Ok(false));
+
+		let normal: CompactSignature = "1c85d59f55d9d6ab3e50f00cc78016d563528a2c03ae958eeec4f4e53a8ca5ad0c123edb331b9581179b70b993a70d4a711f2e2d09df6a06dfa0a4e4f40b681e44".into();
+		assert_eq!(normal.verify_address(&message, &normal_address), Ok(true));
+		assert_eq!(normal.verify_address(&message, &compressed_address), Ok(false));
+
+		let p2sh: Address = "3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN".parse().unwrap();
+		assert_eq!(compressed.verify_address(&message, &p2sh), Err(Error::InvalidAddress));
+	}
+
+	#[test]
+	fn test_compact_signature_header() {
+		let signature = |header: u8| {
+			let mut signature: CompactSignature = "2085d59f55d9d6ab3e50f00cc78016d563528a2c03ae958eeec4f4e53a8ca5ad0c123edb331b9581179b70b993a70d4a711f2e2d09df6a06dfa0a4e4f40b681e44".into();
+			signature.0[0] = header;
+			signature
+		};
+
+		for header in 27..31 {
+			assert_eq!(signature(header).recovery_id(), Ok(header - 27));
+			assert_eq!(signature(header).is_compressed(), Ok(false));
+		}
+		for header in 31..35 {
+			assert_eq!(signature(header).recovery_id(), Ok(header - 31));
+			assert_eq!(signature(header).is_compressed(), Ok(true));
+		}
+		for header in &[0, 26, 35, 0xff] {
+			assert_eq!(signature(*header).recovery_id(), Err(Error::InvalidSignature));
+			assert_eq!(signature(*header).is_compressed(), Err(Error::InvalidSignature));
+		}
+	}
+}
@@ -0,0 +1,105 @@
+//! BIP341 taproot output keys.
+//!
+//! https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki
+
+use std::{fmt, ops};
+use secp256k1::{PublicKey, PublicKeyFormat, SecretKey};
+use hex::ToHex;
+use crypto::tagged_hash;
+use hash::H256;
+use Error;
+
+/// 32 bytes long BIP340 public key, the x coordinate of a point with even y coordinate.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct XOnlyPublic(H256);
+
+impl XOnlyPublic {
+	pub fn from_slice(data: &[u8]) -> Result<Self, Error> {
+		if data.len() != 32 {
+			return Err(Error::InvalidPublic);
+		}
+
+		let mut x = H256::default();
+		x.copy_from_slice(data);
+		Ok(XOnlyPublic(x))
+	}
+
+	/// Returns the point with even y coordinate.
+	fn lift_x(&self) -> Result<PublicKey, Error> {
+		let mut compressed = [0u8; 33];
+		compressed[0] = 0x02;
+		compressed[1..].copy_from_slice(&*self.0);
+		Ok(PublicKey::parse_slice(&compressed, Some(PublicKeyFormat::Compressed))?)
+	}
+}
+
+impl From<H256> for XOnlyPublic {
+	fn from(x: H256) -> Self {
+		XOnlyPublic(x)
+	}
+}
+
+impl ops::Deref for XOnlyPublic {
+	type Target = [u8];
+
+	fn deref(&self) -> &Self::Target {
+		&*self.0
+	}
+}
+
+impl fmt::Display for XOnlyPublic {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.to_hex::<String>().fmt(f)
+	}
+}
+
+/// Tweaks internal key with the merkle root of the script tree: `Q = P + int(hashTapTweak(P || merkle_root))G`.
+/// Outputs spendable only by the key path should use `None` as the merkle root.
+pub fn taproot_output_key(internal: &XOnlyPublic, merkle_root: Option<H256>) -> Result<XOnlyPublic, Error> {
+	let mut data = internal.to_vec();
+	if let Some(root) = merkle_root {
+		data.extend_from_slice(&*root);
+	}
+
+	let tweak = SecretKey::parse_slice(&*tagged_hash(b"TapTweak", &data)).map_err(|_| Error::InvalidPublic)?;
+	let mut output = internal.lift_x()?;
+	output.tweak_add_assign(&tweak)?;
+	XOnlyPublic::from_slice(&output.serialize_compressed()[1..])
+}
+
+#[cfg(test)]
+mod tests {
+	use hash::H256;
+	use {bech32, Error};
+	use super::{XOnlyPublic, taproot_output_key};
+
+	fn segwit_v1_address(key: &XOnlyPublic) -> String {
+		let mut data = vec![1];
+		data.extend(bech32::convert_bits(key, 8, 5, true).unwrap());
+		bech32::encode("bc", &data, bech32::Variant::Bech32m)
+	}
+
+	// https://github.com/bitcoin/bips/blob/master/bip-0341/wallet-test-vectors.json
+	#[test]
+	fn test_taproot_output_key_without_scripts() {
+		let internal: XOnlyPublic = H256::from("d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d").into();
+		let output = taproot_output_key(&internal, None).unwrap();
+		assert_eq!(output.to_string(), "53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343");
+		assert_eq!(segwit_v1_address(&output), "bc1p2wsldez5mud2yam29q22wgfh9439spgduvct83k3pm50fcxa5dps59h4z5");
+	}
+
+	#[test]
+	fn test_taproot_output_key_with_merkle_root() {
+		let internal: XOnlyPublic = H256::from("187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27").into();
+		let merkle_root = "5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21".into();
+		let output = taproot_output_key(&internal, Some(merkle_root)).unwrap();
+		assert_eq!(output.to_string(), "147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3");
+		assert_eq!(segwit_v1_address(&output), "bc1pz37fc4cn9ah8anwm4xqqhvxygjf9rjf2resrw8h8w4tmvcs0863sa2e586");
+	}
+
+	#[test]
+	fn test_taproot_output_key_invalid_internal_key() {
+		let internal: XOnlyPublic = H256::from("ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff").into();
+		assert_eq!(taproot_output_key(&internal, None), Err(Error::InvalidPublic));
+	}
+}
@@ -178,4 +178,15 @@ impl H256 {
 	pub fn to_reversed_str(&self) -> String {
 		self.reversed().to_string()
 	}
+
+	/// Parses hash from its display form, e.g. txid as shown by block explorers,
+	/// which is the reversed internal byte order.
+	pub fn from_display_hex(s: &str) -> Result<Self, FromHexError> {
+		s.parse::<H256>().map(|hash| hash.reversed())
+	}
+
+	/// Returns display form of the hash, which is the reversed internal byte order.
+	pub fn to_display_hex(&self) -> String {
+		self.to_reversed_str()
+	}
 }
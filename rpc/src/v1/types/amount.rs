@@ -0,0 +1,184 @@
+//! Conversions between BTC amounts used by RPC and satoshis.
+use std::fmt;
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::de::{self, Visitor};
+
+/// Number of satoshis in one BTC.
+pub const SATOSHIS_PER_BTC: u64 = 100_000_000;
+/// Maximal amount of money, 21 million BTC.
+pub const MAX_MONEY: u64 = 21_000_000 * SATOSHIS_PER_BTC;
+
+/// Amount conversion error
+#[derive(Debug, PartialEq)]
+pub enum Error {
+	/// Amount is not a finite number or has more than 8 decimal places
+	InvalidAmount,
+	/// Amount is negative or exceeds `MAX_MONEY`
+	OutOfRange,
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			Error::InvalidAmount => "Invalid amount".fmt(f),
+			Error::OutOfRange => "Amount out of range".fmt(f),
+		}
+	}
+}
+
+/// Converts BTC amount to satoshis, rounding to the nearest satoshi.
+pub fn btc_to_sat(btc: f64) -> Result<u64, Error> {
+	if !btc.is_finite() {
+		return Err(Error::InvalidAmount);
+	}
+
+	let sat = (btc * SATOSHIS_PER_BTC as f64).round();
+	if sat < 0.0 || sat > MAX_MONEY as f64 {
+		return Err(Error::OutOfRange);
+	}
+
+	Ok(sat as u64)
+}
+
+/// Converts satoshis to BTC amount.
+pub fn sat_to_btc(sat: u64) -> f64 {
+	sat as f64 / SATOSHIS_PER_BTC as f64
+}
+
+/// Parses decimal BTC amount with at most 8 decimal places, without floating point rounding.
+fn parse_btc(s: &str) -> Result<u64, Error> {
+	let (whole, fraction) = match s.find('.') {
+		Some(pos) => (&s[..pos], &s[pos + 1..]),
+		None => (s, ""),
+	};
+
+	let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+	if whole.is_empty() || fraction.len() > 8 || !is_digits(whole) || !is_digits(fraction) {
+		return Err(Error::InvalidAmount);
+	}
+
+	let whole: u64 = whole.parse().map_err(|_| Error::OutOfRange)?;
+	let fraction: u64 = format!("{:0<8}", fraction).parse().map_err(|_| Error::InvalidAmount)?;
+	let sat = whole.checked_mul(SATOSHIS_PER_BTC)
+		.and_then(|sat| sat.checked_add(fraction))
+		.ok_or(Error::OutOfRange)?;
+	if sat > MAX_MONEY {
+		return Err(Error::OutOfRange);
+	}
+
+	Ok(sat)
+}
+
+/// Amount in satoshis, which serializes as BTC string with 8 decimal places.
+/// Deserializes from such string or from a number of BTC.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct SatoshiAmount(pub u64);
+
+impl SatoshiAmount {
+	/// Amount in BTC.
+	pub fn to_btc(&self) -> f64 {
+		sat_to_btc(self.0)
+	}
+}
+
+impl fmt::Display for SatoshiAmount {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}.{:08}", self.0 / SATOSHIS_PER_BTC, self.0 % SATOSHIS_PER_BTC)
+	}
+}
+
+impl Serialize for SatoshiAmount {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+		serializer.serialize_str(&self.to_string())
+	}
+}
+
+impl<'a> Deserialize<'a> for SatoshiAmount {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'a> {
+		struct SatoshiAmountVisitor;
+
+		impl<'b> Visitor<'b> for SatoshiAmountVisitor {
+			type Value = SatoshiAmount;
+
+			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+				formatter.write_str("a BTC amount")
+			}
+
+			fn visit_str<E>(self, value: &str) -> Result<Self::Value, E> where E: de::Error {
+				parse_btc(value).map(SatoshiAmount).map_err(E::custom)
+			}
+
+			fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E> where E: de::Error {
+				btc_to_sat(value).map(SatoshiAmount).map_err(E::custom)
+			}
+
+			fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E> where E: de::Error {
+				value.checked_mul(SATOSHIS_PER_BTC)
+					.filter(|sat| *sat <= MAX_MONEY)
+					.map(SatoshiAmount)
+					.ok_or_else(|| E::custom(Error::OutOfRange))
+			}
+
+			fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E> where E: de::Error {
+				if value < 0 {
+					return Err(E::custom(Error::OutOfRange));
+				}
+
+				self.visit_u64(value as u64)
+			}
+		}
+
+		deserializer.deserialize_any(SatoshiAmountVisitor)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::f64;
+	use serde_json;
+	use super::{btc_to_sat, sat_to_btc, Error, SatoshiAmount, MAX_MONEY};
+
+	#[test]
+	fn test_btc_to_sat() {
+		assert_eq!(btc_to_sat(0.00000001), Ok(1));
+		assert_eq!(btc_to_sat(21000000.0), Ok(MAX_MONEY));
+		assert_eq!(btc_to_sat(0.1 + 0.2), Ok(30_000_000));
+		assert_eq!(btc_to_sat(123.45), Ok(12_345_000_000));
+		assert_eq!(btc_to_sat(0.0), Ok(0));
+		assert_eq!(btc_to_sat(-0.00000001), Err(Error::OutOfRange));
+		assert_eq!(btc_to_sat(21000000.00000001), Err(Error::OutOfRange));
+		assert_eq!(btc_to_sat(f64::NAN), Err(Error::InvalidAmount));
+		assert_eq!(btc_to_sat(f64::INFINITY), Err(Error::InvalidAmount));
+	}
+
+	#[test]
+	fn test_sat_to_btc() {
+		assert_eq!(sat_to_btc(1), 0.00000001);
+		assert_eq!(sat_to_btc(MAX_MONEY), 21000000.0);
+		assert_eq!(btc_to_sat(sat_to_btc(12_345_678_901)), Ok(12_345_678_901));
+	}
+
+	#[test]
+	fn test_satoshi_amount_serialize() {
+		assert_eq!(serde_json::to_string(&SatoshiAmount(1)).unwrap(), r#""0.00000001""#);
+		assert_eq!(serde_json::to_string(&SatoshiAmount(MAX_MONEY)).unwrap(), r#""21000000.00000000""#);
+		assert_eq!(serde_json::to_string(&SatoshiAmount(12_345_000_000)).unwrap(), r#""123.45000000""#);
+	}
+
+	#[test]
+	fn test_satoshi_amount_deserialize() {
+		assert_eq!(serde_json::from_str::<SatoshiAmount>(r#""0.00000001""#).unwrap(), SatoshiAmount(1));
+		assert_eq!(serde_json::from_str::<SatoshiAmount>(r#""21000000""#).unwrap(), SatoshiAmount(MAX_MONEY));
+		assert_eq!(serde_json::from_str::<SatoshiAmount>(r#""123.45""#).unwrap(), SatoshiAmount(12_345_000_000));
+		assert_eq!(serde_json::from_str::<SatoshiAmount>("0.00000001").unwrap(), SatoshiAmount(1));
+		assert_eq!(serde_json::from_str::<SatoshiAmount>("21000000.0").unwrap(), SatoshiAmount(MAX_MONEY));
+		assert_eq!(serde_json::from_str::<SatoshiAmount>("21000000").unwrap(), SatoshiAmount(MAX_MONEY));
+
+		assert!(serde_json::from_str::<SatoshiAmount>(r#""0.000000001""#).is_err());
+		assert!(serde_json::from_str::<SatoshiAmount>(r#""21000000.00000001""#).is_err());
+		assert!(serde_json::from_str::<SatoshiAmount>(r#""-1""#).is_err());
+		assert!(serde_json::from_str::<SatoshiAmount>(r#"".5""#).is_err());
+		assert!(serde_json::from_str::<SatoshiAmount>("-1").is_err());
+		assert!(serde_json::from_str::<SatoshiAmount>("21000001").is_err());
+	}
+}
@@ -1,4 +1,5 @@
 pub mod address;
+pub mod amount;
 mod block;
 mod block_template_request;
 mod bytes;
@@ -19,7 +20,8 @@ pub use self::get_tx_out_set_info_response::GetTxOutSetInfoResponse;
 pub use self::hash::{H160, H256, H264};
 pub use self::script::ScriptType;
 pub use self::transaction::{RawTransaction, Transaction, TransactionInput, TransactionOutput,
-	TransactionOutputWithAddress, TransactionOutputWithScriptData, TransactionInputScript,
+	TransactionOutputWithAddress, TransactionOutputWithPublicKey, TransactionOutputWithScript,
+	TransactionOutputWithScriptData, TransactionInputScript,
 	TransactionOutputScript, SignedTransactionInput, GetRawTransactionResponse,
 	SignedTransactionOutput, TransactionOutputs};
 pub use self::uint::U256;
@@ -1,7 +1,10 @@
 use std::fmt;
 use serde::{Serialize, Serializer, Deserialize, Deserializer};
 use serde::ser::SerializeMap;
-use keys::Address;
+use serde::de::{self, MapAccess, SeqAccess, Visitor};
+use hex::{FromHex, ToHex};
+use keys::{Address, Public};
+use global_script::Script as GlobalScript;
 use v1::types;
 use super::bytes::Bytes;
 use super::hash::H256;
@@ -30,6 +33,24 @@ pub struct TransactionOutputWithAddress {
 	pub amount: f64,
 }
 
+/// Transaction output of form "pubkey:hex": amount
+#[derive(Debug, PartialEq)]
+pub struct TransactionOutputWithPublicKey {
+	/// Receiver' public key
+	pub public: Public,
+	/// Amount in BTC
+	pub amount: f64,
+}
+
+/// Transaction output of form "script:hex": amount
+#[derive(Debug, PartialEq)]
+pub struct TransactionOutputWithScript {
+	/// Serialized output script
+	pub script: Bytes,
+	/// Amount in BTC
+	pub amount: f64,
+}
+
 /// Trasaction output of form "data": serialized(output script data)
 #[derive(Debug, PartialEq)]
 pub struct TransactionOutputWithScriptData {
@@ -42,6 +63,10 @@ pub struct TransactionOutputWithScriptData {
 pub enum TransactionOutput {
 	/// Of form address: amount
 	Address(TransactionOutputWithAddress),
+	/// Of form pubkey:hex: amount
+	PublicKey(TransactionOutputWithPublicKey),
+	/// Of form script:hex: amount
+	Script(TransactionOutputWithScript),
 	/// Of form data: script_data_bytes
 	ScriptData(TransactionOutputWithScriptData),
 }
@@ -184,59 +209,145 @@ impl Serialize for GetRawTransactionResponse {
 	}
 }
 
+impl<'a> From<&'a GlobalScript> for TransactionInputScript {
+	fn from(script: &'a GlobalScript) -> Self {
+		TransactionInputScript {
+			asm: script.to_asm_string_with_sighash(),
+			hex: script.to_bytes().into(),
+		}
+	}
+}
+
 impl TransactionOutputs {
 	pub fn len(&self) -> usize {
 		self.outputs.len()
 	}
 }
 
-impl Serialize for TransactionOutputs {
+/// Prefix of the output key, holding hex-encoded public key.
+const PUBKEY_KEY_PREFIX: &'static str = "pubkey:";
+/// Prefix of the output key, holding hex-encoded output script.
+const SCRIPT_KEY_PREFIX: &'static str = "script:";
+
+/// Serializes as a single entry object: `{"address": amount}`, `{"pubkey:hex": amount}`,
+/// `{"script:hex": amount}` or `{"data": "hex"}`.
+impl Serialize for TransactionOutput {
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
-		let mut state = serializer.serialize_map(Some(self.len()))?;
-		for output in &self.outputs {
-			match output {
-				&TransactionOutput::Address(ref address_output) => {
-					state.serialize_entry(&address_output.address.to_string(), &address_output.amount)?;
-				},
-				&TransactionOutput::ScriptData(ref script_output) => {
-					state.serialize_entry("data", &script_output.script_data)?;
-				},
-			}
+		let mut state = serializer.serialize_map(Some(1))?;
+		match self {
+			&TransactionOutput::Address(ref address_output) => {
+				state.serialize_entry(&address_output.address.to_string(), &address_output.amount)?;
+			},
+			&TransactionOutput::PublicKey(ref public_output) => {
+				let key = format!("{}{}", PUBKEY_KEY_PREFIX, public_output.public);
+				state.serialize_entry(&key, &public_output.amount)?;
+			},
+			&TransactionOutput::Script(ref script_output) => {
+				let key = format!("{}{}", SCRIPT_KEY_PREFIX, script_output.script.to_hex::<String>());
+				state.serialize_entry(&key, &script_output.amount)?;
+			},
+			&TransactionOutput::ScriptData(ref script_output) => {
+				state.serialize_entry("data", &script_output.script_data)?;
+			},
 		}
 		state.end()
 	}
 }
 
-impl<'a> Deserialize<'a> for TransactionOutputs {
+impl<'a> Deserialize<'a> for TransactionOutput {
 	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'a> {
-		use serde::de::{Visitor, MapAccess};
+		struct TransactionOutputVisitor;
+
+		impl<'b> Visitor<'b> for TransactionOutputVisitor {
+			type Value = TransactionOutput;
+
+			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+				formatter.write_str("a transaction output object with single entry")
+			}
+
+			fn visit_map<V>(self, mut visitor: V) -> Result<TransactionOutput, V::Error> where V: MapAccess<'b> {
+				let key: String = visitor.next_key()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+				let output = next_output(key, &mut visitor)?;
+				if visitor.next_key::<String>()?.is_some() {
+					return Err(de::Error::invalid_length(2, &self));
+				}
+
+				Ok(output)
+			}
+		}
+
+		deserializer.deserialize_map(TransactionOutputVisitor)
+	}
+}
+
+/// Reads value of the output entry with given key.
+fn next_output<'a, V>(key: String, visitor: &mut V) -> Result<TransactionOutput, V::Error> where V: MapAccess<'a> {
+	if &key == "data" {
+		let value: Bytes = visitor.next_value()?;
+		Ok(TransactionOutput::ScriptData(TransactionOutputWithScriptData {
+			script_data: value,
+		}))
+	} else if key.starts_with(PUBKEY_KEY_PREFIX) {
+		let public = key[PUBKEY_KEY_PREFIX.len()..].from_hex::<Vec<u8>>().ok()
+			.and_then(|bytes| Public::from_slice(&bytes).ok())
+			.ok_or_else(|| de::Error::custom("invalid public key"))?;
+		let amount: f64 = visitor.next_value()?;
+		Ok(TransactionOutput::PublicKey(TransactionOutputWithPublicKey {
+			public: public,
+			amount: amount,
+		}))
+	} else if key.starts_with(SCRIPT_KEY_PREFIX) {
+		let script = key[SCRIPT_KEY_PREFIX.len()..].from_hex::<Vec<u8>>()
+			.map_err(|_| de::Error::custom("invalid script hex"))?;
+		let amount: f64 = visitor.next_value()?;
+		Ok(TransactionOutput::Script(TransactionOutputWithScript {
+			script: Bytes::new(script),
+			amount: amount,
+		}))
+	} else {
+		let address = types::address::AddressVisitor::default().visit_str(&key)?;
+		let amount: f64 = visitor.next_value()?;
+		Ok(TransactionOutput::Address(TransactionOutputWithAddress {
+			address: address,
+			amount: amount,
+		}))
+	}
+}
 
+/// Serializes as an array of single entry objects, so multiple data outputs don't produce duplicate keys.
+impl Serialize for TransactionOutputs {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+		serializer.collect_seq(&self.outputs)
+	}
+}
+
+/// Accepts an array of single entry objects, or the legacy object of form `{"address": amount, "data": "hex"}`.
+impl<'a> Deserialize<'a> for TransactionOutputs {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'a> {
 		struct TransactionOutputsVisitor;
 
 		impl<'b> Visitor<'b> for TransactionOutputsVisitor {
 			type Value = TransactionOutputs;
 
 			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-				formatter.write_str("a transaction output object")
+				formatter.write_str("a transaction outputs array or object")
 			}
 
-			fn visit_map<V>(self, mut visitor: V) -> Result<TransactionOutputs, V::Error> where V: MapAccess<'b> {
+			fn visit_seq<V>(self, mut visitor: V) -> Result<TransactionOutputs, V::Error> where V: SeqAccess<'b> {
 				let mut outputs: Vec<TransactionOutput> = Vec::with_capacity(visitor.size_hint().unwrap_or(0));
+				while let Some(output) = visitor.next_element()? {
+					outputs.push(output);
+				}
 
-				while let Some(key) = try!(visitor.next_key::<String>()) {
-					if &key == "data" {
-						let value: Bytes = try!(visitor.next_value());
-						outputs.push(TransactionOutput::ScriptData(TransactionOutputWithScriptData {
-							script_data: value,
-						}));
-					} else {
-						let address = types::address::AddressVisitor::default().visit_str(&key)?;
-						let amount: f64 = try!(visitor.next_value());
-						outputs.push(TransactionOutput::Address(TransactionOutputWithAddress {
-							address: address,
-							amount: amount,
-						}));
-					}
+				Ok(TransactionOutputs {
+					outputs: outputs,
+				})
+			}
+
+			fn visit_map<V>(self, mut visitor: V) -> Result<TransactionOutputs, V::Error> where V: MapAccess<'b> {
+				let mut outputs: Vec<TransactionOutput> = Vec::with_capacity(visitor.size_hint().unwrap_or(0));
+				while let Some(key) = visitor.next_key::<String>()? {
+					outputs.push(next_output(key, &mut visitor)?);
 				}
 
 				Ok(TransactionOutputs {
@@ -245,7 +356,7 @@ impl<'a> Deserialize<'a> for TransactionOutputs {
 			}
 		}
 
-		deserializer.deserialize_identifier(TransactionOutputsVisitor)
+		deserializer.deserialize_any(TransactionOutputsVisitor)
 	}
 }
 
@@ -255,8 +366,28 @@ mod tests {
 	use super::super::bytes::Bytes;
 	use super::super::hash::H256;
 	use super::super::script::ScriptType;
+	use global_script::{Script as GlobalScript, extract_addresses};
+	use keys::Network;
 	use super::*;
 
+	fn assert_scripts_match_hex(tx: &Transaction, network: Network) {
+		for input in &tx.vin {
+			if let TransactionInputEnum::Signed(ref input) = *input {
+				let script: GlobalScript = input.script_sig.hex.clone().to_vec().into();
+				assert_eq!(TransactionInputScript::from(&script), input.script_sig);
+			}
+		}
+
+		for output in &tx.vout {
+			let script: GlobalScript = output.script.hex.clone().to_vec().into();
+			assert_eq!(script.to_asm_string(), output.script.asm);
+			let (script_type, addresses) = extract_addresses(&script, network);
+			assert_eq!(ScriptType::from(script_type), output.script.script_type);
+			let addresses: Vec<String> = addresses.iter().map(ToString::to_string).collect();
+			assert_eq!(addresses, output.script.addresses);
+		}
+	}
+
 	#[test]
 	fn transaction_input_serialize() {
 		let txinput = TransactionInput {
@@ -300,7 +431,7 @@ mod tests {
 				}),
 			]
 		};
-		assert_eq!(serde_json::to_string(&txout).unwrap(), r#"{"1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa":123.45,"1H5m1XzvHsjWX3wwU781ubctznEpNACrNC":67.89,"data":"01020304","data":"05060708"}"#);
+		assert_eq!(serde_json::to_string(&txout).unwrap(), r#"[{"1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa":123.45},{"1H5m1XzvHsjWX3wwU781ubctznEpNACrNC":67.89},{"data":"01020304"},{"data":"05060708"}]"#);
 	}
 
 	#[test]
@@ -323,9 +454,70 @@ mod tests {
 				}),
 			]
 		};
+		assert_eq!(
+			serde_json::from_str::<TransactionOutputs>(r#"[{"1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa":123.45},{"1H5m1XzvHsjWX3wwU781ubctznEpNACrNC":67.89},{"data":"01020304"},{"data":"05060708"}]"#).unwrap(),
+			txout);
+		// legacy form
 		assert_eq!(
 			serde_json::from_str::<TransactionOutputs>(r#"{"1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa":123.45,"1H5m1XzvHsjWX3wwU781ubctznEpNACrNC":67.89,"data":"01020304","data":"05060708"}"#).unwrap(),
 			txout);
+
+		assert!(serde_json::from_str::<TransactionOutputs>(r#"[{}]"#).is_err());
+		assert!(serde_json::from_str::<TransactionOutputs>(r#"[{"data":"01020304","1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa":123.45}]"#).is_err());
+	}
+
+	#[test]
+	fn transaction_outputs_round_trip_data_outputs() {
+		let txout = TransactionOutputs {
+			outputs: vec![
+				TransactionOutput::ScriptData(TransactionOutputWithScriptData {
+					script_data: Bytes::new(vec![1, 2, 3, 4]),
+				}),
+				TransactionOutput::Address(TransactionOutputWithAddress {
+					address: "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa".into(),
+					amount: 0.1,
+				}),
+				TransactionOutput::ScriptData(TransactionOutputWithScriptData {
+					script_data: Bytes::new(vec![5, 6, 7, 8]),
+				}),
+			]
+		};
+
+		let json = serde_json::to_string(&txout).unwrap();
+		let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+		assert_eq!(value.as_array().map(Vec::len), Some(3));
+		assert_eq!(serde_json::from_str::<TransactionOutputs>(&json).unwrap(), txout);
+	}
+
+	#[test]
+	fn transaction_outputs_deserialize_pubkey_and_script() {
+		let public = "02632b12f4ac5b1d1b72b2a3b508c19172de44f6f46bcee50ba33f3f9291e47ed0";
+		let txout = TransactionOutputs {
+			outputs: vec![
+				TransactionOutput::Address(TransactionOutputWithAddress {
+					address: "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa".into(),
+					amount: 123.45,
+				}),
+				TransactionOutput::PublicKey(TransactionOutputWithPublicKey {
+					public: Public::from_slice(&public.from_hex::<Vec<u8>>().unwrap()).unwrap(),
+					amount: 67.89,
+				}),
+				TransactionOutput::Script(TransactionOutputWithScript {
+					script: Bytes::new(vec![0x51]),
+					amount: 0.5,
+				}),
+				TransactionOutput::ScriptData(TransactionOutputWithScriptData {
+					script_data: Bytes::new(vec![1, 2, 3, 4]),
+				}),
+			]
+		};
+
+		let json = r#"{"1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa":123.45,"pubkey:02632b12f4ac5b1d1b72b2a3b508c19172de44f6f46bcee50ba33f3f9291e47ed0":67.89,"script:51":0.5,"data":"01020304"}"#;
+		assert_eq!(serde_json::from_str::<TransactionOutputs>(json).unwrap(), txout);
+		assert_eq!(serde_json::to_string(&txout).unwrap(), r#"[{"1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa":123.45},{"pubkey:02632b12f4ac5b1d1b72b2a3b508c19172de44f6f46bcee50ba33f3f9291e47ed0":67.89},{"script:51":0.5},{"data":"01020304"}]"#);
+
+		assert!(serde_json::from_str::<TransactionOutputs>(r#"{"pubkey:02632b":1.0}"#).is_err());
+		assert!(serde_json::from_str::<TransactionOutputs>(r#"{"script:5":1.0}"#).is_err());
 	}
 
 	#[test]
@@ -562,7 +754,8 @@ mod tests {
 			"blocktime":1528215344
 		}"#;
 
-		let _tx: Transaction = serde_json::from_str(tx_str).unwrap();
+		let tx: Transaction = serde_json::from_str(tx_str).unwrap();
+		assert_scripts_match_hex(&tx, Network::Komodo);
 	}
 
 	#[test]
@@ -1700,7 +1893,8 @@ mod tests {
 			"blocktime":1561311885
 		}"#;
 
-		let _tx: Transaction = serde_json::from_str(tx_str).unwrap();
+		let tx: Transaction = serde_json::from_str(tx_str).unwrap();
+		assert_scripts_match_hex(&tx, Network::Mainnet);
 	}
 
 	fn test_kmd_raw_confirmations() {
@@ -0,0 +1,83 @@
+//! Zcash consensus branch ids, used in the signature hash personalization of overwintered transactions.
+//!
+//! https://github.com/zcash/zips/blob/master/zip-0200.rst
+
+use keys::Network;
+
+/// Consensus branch id lookup.
+pub struct ConsensusBranchId;
+
+impl ConsensusBranchId {
+	pub const SPROUT: u32 = 0;
+	pub const OVERWINTER: u32 = 0x5ba8_1b19;
+	pub const SAPLING: u32 = 0x76b8_09bb;
+	pub const BLOSSOM: u32 = 0x2bb4_0e60;
+	pub const HEARTWOOD: u32 = 0xf5b9_230b;
+	pub const CANOPY: u32 = 0xe9ff_75a6;
+	pub const NU5: u32 = 0xc2d6_d0b4;
+	pub const NU6: u32 = 0xc8e7_1055;
+	pub const NU6_1: u32 = 0x4dec_4df0;
+
+	/// Returns branch id of the network upgrade active at given height.
+	/// Networks without Zcash network upgrades use `SPROUT`.
+	pub fn for_height(network: Network, height: u32) -> u32 {
+		let upgrades: &[(u32, u32)] = match network {
+			Network::Zcash => &[
+				(3_146_400, Self::NU6_1),
+				(2_726_400, Self::NU6),
+				(1_687_104, Self::NU5),
+				(1_046_400, Self::CANOPY),
+				(903_000, Self::HEARTWOOD),
+				(653_600, Self::BLOSSOM),
+				(419_200, Self::SAPLING),
+				(347_500, Self::OVERWINTER),
+			],
+			// Komodo activated Overwinter and Sapling at the same height and stayed on Sapling
+			Network::Komodo => &[(1_140_409, Self::SAPLING)],
+			_ => &[],
+		};
+
+		upgrades.iter()
+			.find(|&&(activation_height, _)| height >= activation_height)
+			.map_or(Self::SPROUT, |&(_, branch_id)| branch_id)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use keys::Network;
+	use super::ConsensusBranchId;
+
+	#[test]
+	fn test_consensus_branch_id_for_height() {
+		assert_eq!(ConsensusBranchId::for_height(Network::Zcash, 0), ConsensusBranchId::SPROUT);
+		assert_eq!(ConsensusBranchId::for_height(Network::Zcash, 347_499), ConsensusBranchId::SPROUT);
+		assert_eq!(ConsensusBranchId::for_height(Network::Zcash, 347_500), ConsensusBranchId::OVERWINTER);
+		assert_eq!(ConsensusBranchId::for_height(Network::Zcash, 419_200), ConsensusBranchId::SAPLING);
+		assert_eq!(ConsensusBranchId::for_height(Network::Zcash, 700_000), ConsensusBranchId::BLOSSOM);
+		assert_eq!(ConsensusBranchId::for_height(Network::Zcash, 1_000_000), ConsensusBranchId::HEARTWOOD);
+		assert_eq!(ConsensusBranchId::for_height(Network::Zcash, 1_046_400), ConsensusBranchId::CANOPY);
+		assert_eq!(ConsensusBranchId::for_height(Network::Zcash, 2_000_000), ConsensusBranchId::NU5);
+		assert_eq!(ConsensusBranchId::for_height(Network::Zcash, u32::MAX), ConsensusBranchId::NU6_1);
+
+		let activations = [
+			(347_500, ConsensusBranchId::SPROUT, ConsensusBranchId::OVERWINTER),
+			(419_200, ConsensusBranchId::OVERWINTER, ConsensusBranchId::SAPLING),
+			(653_600, ConsensusBranchId::SAPLING, ConsensusBranchId::BLOSSOM),
+			(903_000, ConsensusBranchId::BLOSSOM, ConsensusBranchId::HEARTWOOD),
+			(1_046_400, ConsensusBranchId::HEARTWOOD, ConsensusBranchId::CANOPY),
+			(1_687_104, ConsensusBranchId::CANOPY, ConsensusBranchId::NU5),
+			(2_726_400, ConsensusBranchId::NU5, ConsensusBranchId::NU6),
+			(3_146_400, ConsensusBranchId::NU6, ConsensusBranchId::NU6_1),
+		];
+		for &(height, before, after) in &activations {
+			assert_eq!(ConsensusBranchId::for_height(Network::Zcash, height - 1), before);
+			assert_eq!(ConsensusBranchId::for_height(Network::Zcash, height), after);
+		}
+
+		assert_eq!(ConsensusBranchId::for_height(Network::Komodo, 1_140_408), ConsensusBranchId::SPROUT);
+		assert_eq!(ConsensusBranchId::for_height(Network::Komodo, 1_140_409), 0x76b809bb);
+		assert_eq!(ConsensusBranchId::for_height(Network::Komodo, 3_000_000), ConsensusBranchId::SAPLING);
+		assert_eq!(ConsensusBranchId::for_height(Network::Mainnet, 3_000_000), ConsensusBranchId::SPROUT);
+	}
+}
@@ -0,0 +1,121 @@
+//! Standard scriptPubKey templates
+
+use hash::H256;
+use keys::{Address, AddressHash, Type};
+use {Builder, Error, Opcode, Script};
+
+/// Builds `OP_DUP OP_HASH160 <hash> OP_EQUALVERIFY OP_CHECKSIG` script pubkey.
+pub fn pay_to_pubkey_hash(hash: &AddressHash) -> Script {
+	Builder::build_p2pkh(hash)
+}
+
+/// Builds `OP_HASH160 <hash> OP_EQUAL` script pubkey.
+pub fn pay_to_script_hash(hash: &AddressHash) -> Script {
+	Builder::build_p2sh(hash)
+}
+
+/// Builds `OP_0 <hash>` version 0 witness script pubkey.
+pub fn pay_to_witness_pubkey_hash(hash: &AddressHash) -> Script {
+	Builder::default()
+		.push_opcode(Opcode::OP_0)
+		.push_bytes(&**hash)
+		.into_script()
+}
+
+/// Builds `OP_n <program>` witness script pubkey of the witness version `n`.
+/// Fails if the version is above 16.
+pub fn pay_to_witness_program(version: u8, program: &[u8]) -> Result<Script, Error> {
+	let opcode = match version {
+		0 => Opcode::OP_0,
+		1..=16 => Opcode::from_u8(Opcode::OP_1 as u8 + version - 1).expect("OP_1 to OP_16 are consecutive opcodes; qed"),
+		_ => return Err(Error::WitnessProgramMismatch),
+	};
+
+	let script = Builder::default()
+		.push_opcode(opcode)
+		.push_bytes(program)
+		.into_script();
+
+	Ok(script)
+}
+
+/// Builds script pubkey paying to the address, the inverse of `extract_addresses`.
+/// Segwit addresses pay to their witness program.
+pub fn pay_to_address(address: &Address) -> Result<Script, Error> {
+	if let Some((version, program)) = address.witness_program() {
+		return pay_to_witness_program(version, &program);
+	}
+
+	let script = match address.kind() {
+		Type::P2SH => pay_to_script_hash(&address.hash),
+		_ => pay_to_pubkey_hash(&address.hash),
+	};
+
+	Ok(script)
+}
+
+/// Electrum protocol script hash of the address script pubkey.
+pub fn electrum_scripthash(address: &Address) -> Result<H256, Error> {
+	pay_to_address(address).map(|script| script.electrum_hash())
+}
+
+#[cfg(test)]
+mod tests {
+	use keys::{Address, Network, Type};
+	use {Script, ScriptType, extract_addresses};
+	use super::{pay_to_pubkey_hash, pay_to_script_hash, pay_to_witness_pubkey_hash, pay_to_address, electrum_scripthash};
+
+	#[test]
+	fn test_pay_to_pubkey_hash() {
+		let script = pay_to_pubkey_hash(&"73122bcec852f394e51496e39fca5111c3d7ae56".into());
+		assert_eq!(script, Script::from("76a91473122bcec852f394e51496e39fca5111c3d7ae5688ac"));
+		assert_eq!(script.script_type(), ScriptType::PubKeyHash);
+	}
+
+	#[test]
+	fn test_pay_to_script_hash() {
+		let script = pay_to_script_hash(&"fef59ae800bb89050d25f67be432b231097e1849".into());
+		assert_eq!(script, Script::from("a914fef59ae800bb89050d25f67be432b231097e184987"));
+		assert_eq!(script.script_type(), ScriptType::ScriptHash);
+	}
+
+	#[test]
+	fn test_pay_to_witness_pubkey_hash() {
+		let script = pay_to_witness_pubkey_hash(&"751e76e8199196d454941c45d1b3a323f1433bd6".into());
+		assert_eq!(script, Script::from("0014751e76e8199196d454941c45d1b3a323f1433bd6"));
+		assert_eq!(script.script_type(), ScriptType::WitnessKey);
+	}
+
+	#[test]
+	fn test_pay_to_address() {
+		let address: Address = "RKmdZ8QA7XbJ4JGUAvtHtWEogKxfgaQuqv".parse().unwrap();
+		let script = pay_to_address(&address).unwrap();
+		assert_eq!(script, Script::from("76a91473122bcec852f394e51496e39fca5111c3d7ae5688ac"));
+		assert_eq!(extract_addresses(&script, Network::Komodo), (ScriptType::PubKeyHash, vec![address]));
+
+		let address: Address = "bbyNYu11Qs3PowiPr1Su4ozQk7hsVmv821".parse().unwrap();
+		let script = pay_to_address(&address).unwrap();
+		assert_eq!(script, Script::from("a914fef59ae800bb89050d25f67be432b231097e184987"));
+		assert_eq!(extract_addresses(&script, Network::Komodo), (ScriptType::ScriptHash, vec![address]));
+
+		let address = Address::new(Network::Mainnet, Type::P2WPKH, "751e76e8199196d454941c45d1b3a323f1433bd6".into()).unwrap();
+		let script = pay_to_address(&address).unwrap();
+		assert_eq!(script, Script::from("0014751e76e8199196d454941c45d1b3a323f1433bd6"));
+
+		let address: Address = "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3".parse().unwrap();
+		let script = pay_to_address(&address).unwrap();
+		assert_eq!(script, Script::from("00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262"));
+		assert_eq!(script.script_type(), ScriptType::WitnessScript);
+
+		let address: Address = "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y".parse().unwrap();
+		let script = pay_to_address(&address).unwrap();
+		assert_eq!(script, Script::from("5128751e76e8199196d454941c45d1b3a323f1433bd6751e76e8199196d454941c45d1b3a323f1433bd6"));
+		assert_eq!(script.parse_witness_program().map(|(version, _)| version), Some(1));
+	}
+
+	#[test]
+	fn test_electrum_scripthash() {
+		let address: Address = "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa".into();
+		assert_eq!(electrum_scripthash(&address).unwrap().to_string(), "8b01df4e368ea28f8dc0423bcf7a4923e3a12d307c875e47a0cfbf90b5c39161");
+	}
+}
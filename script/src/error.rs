@@ -28,6 +28,7 @@ pub enum Error {
 
 	// Logical/Format/Canonical errors.
 	BadOpcode,
+	TruncatedPush { offset: usize, expected: usize, available: usize },
 	DisabledOpcode(Opcode),
 	InvalidStackOperation,
 	InvalidAltstackOperation,
@@ -92,6 +93,8 @@ impl fmt::Display for Error {
 
 			// Logical/Format/Canonical errors.
 			Error::BadOpcode => "Bad Opcode".fmt(f),
+			Error::TruncatedPush { offset, expected, available } =>
+				write!(f, "Truncated push at offset {}: expected {} bytes, {} available", offset, expected, available),
 			Error::DisabledOpcode(ref opcode) => writeln!(f, "Disabled Opcode: {:?}", opcode),
 			Error::InvalidStackOperation => "Invalid stack operation".fmt(f),
 			Error::InvalidAltstackOperation => "Invalid altstack operation".fmt(f),
@@ -48,7 +48,7 @@ fn is_public_key(v: &[u8]) -> bool {
 /// See https://bitcointalk.org/index.php?topic=8392.msg127623#msg127623
 ///
 /// This function is consensus-critical since BIP66.
-fn is_valid_signature_encoding(sig: &[u8]) -> bool {
+pub fn is_valid_signature_encoding(sig: &[u8]) -> bool {
 	// Format: 0x30 [total-length] 0x02 [R-length] [R] 0x02 [S-length] [S] [sighash]
 	// * total-length: 1-byte length descriptor of everything that follows,
 	//   excluding the sighash byte.
@@ -346,6 +346,19 @@ pub fn verify_script(
 	Ok(())
 }
 
+/// Checks that the witness script hashes to the 32 bytes P2WSH witness program.
+pub fn check_p2wsh_commitment(witness_script: &Script, program: &[u8]) -> Result<(), Error> {
+	if program.len() != 32 {
+		return Err(Error::WitnessProgramWrongLength);
+	}
+
+	if *sha256(witness_script) != *program {
+		return Err(Error::WitnessProgramMismatch);
+	}
+
+	Ok(())
+}
+
 fn verify_witness_program(
 	witness: &ScriptWitness,
 	witness_version: u8,
@@ -369,15 +382,11 @@ fn verify_witness_program(
 				return Err(Error::WitnessProgramWitnessEmpty);
 			}
 
-			let script_pubkey = &witness_stack[witness_stack_len - 1];
+			let script_pubkey = Script::new(witness_stack[witness_stack_len - 1].clone());
 			let stack = &witness_stack[0..witness_stack_len - 1];
-			let script_pubkey_hash = sha256(script_pubkey);
+			check_p2wsh_commitment(&script_pubkey, witness_program)?;
 
-			if script_pubkey_hash != witness_program[0..32].into() {
-				return Err(Error::WitnessProgramMismatch);
-			}
-
-			(stack.iter().cloned().collect::<Vec<_>>().into(), Script::new(script_pubkey.clone()))
+			(stack.to_vec().into(), script_pubkey)
 		},
 		20 => {
 			if witness_stack_len != 2 {
@@ -436,7 +445,7 @@ pub fn eval_script(
 		let executing = exec_stack.iter().all(|x| *x);
 		let instruction = match script.get_instruction(pc) {
 			Ok(i) => i,
-			Err(Error::BadOpcode) if !executing => {
+			Err(Error::BadOpcode) | Err(Error::TruncatedPush { .. }) if !executing => {
 				pc += 1;
 				continue;
 			},
@@ -1147,7 +1156,7 @@ mod tests {
 		Opcode, Script, ScriptWitness, VerificationFlags, Builder, Error, Num, TransactionInputSigner,
 		NoopSignatureChecker, TransactionSignatureChecker, Stack
 	};
-	use super::{eval_script, verify_script, is_public_key};
+	use super::{eval_script, verify_script, is_public_key, check_p2wsh_commitment};
 
 	#[test]
 	fn tests_is_public_key() {
@@ -2257,10 +2266,7 @@ mod tests {
 			compressed: false,
 			checksum_type: ChecksumType::DSHA256,
 		}).unwrap();
-		let redeem_script = Builder::default()
-			.push_data(key_pair.public())
-			.push_opcode(Opcode::OP_CHECKSIG)
-			.into_script();
+		let redeem_script = Builder::build_p2pkh(&key_pair.public().address_hash());
 
 
 		let amount = 12345000000000;
@@ -2284,6 +2290,7 @@ mod tests {
 						},
 						sequence: 0xffffffff,
                         amount: 0,
+                        sighash: None,
                     },
 				],
 				outputs: vec![
@@ -3888,4 +3895,15 @@ mod tests {
 			.verify_split(true);
 		basic_test_with_flags(&script, &flags, Ok(true), vec![vec![0x01].into()].into());
 	}
+
+	#[test]
+	fn test_check_p2wsh_commitment() {
+		let witness_script: Script = "51".into();
+		let program: Bytes = "4ae81572f06e1b88fd5ced7a1a000945432e83e1551e6f721ee9c00b8cc33260".into();
+		assert_eq!(check_p2wsh_commitment(&witness_script, &program), Ok(()));
+
+		let other_script: Script = "52".into();
+		assert_eq!(check_p2wsh_commitment(&other_script, &program), Err(Error::WitnessProgramMismatch));
+		assert_eq!(check_p2wsh_commitment(&witness_script, &program[..20]), Err(Error::WitnessProgramWrongLength));
+	}
 }
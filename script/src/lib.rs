@@ -6,27 +6,35 @@ extern crate log;
 extern crate primitives;
 extern crate serialization as ser;
 
+mod branch_id;
+pub mod build;
 mod builder;
 mod error;
 mod flags;
 mod interpreter;
+pub mod multisig;
 mod num;
 mod opcode;
 mod script;
 mod sign;
 mod stack;
+mod transaction_builder;
 mod verify;
 
 pub use primitives::{bytes, hash};
 
+pub use self::branch_id::ConsensusBranchId;
 pub use self::builder::Builder;
 pub use self::error::Error;
 pub use self::flags::VerificationFlags;
-pub use self::interpreter::{eval_script, verify_script};
+pub use self::interpreter::{eval_script, verify_script, check_p2wsh_commitment};
 pub use self::opcode::Opcode;
 pub use self::num::Num;
-pub use self::script::{Script, ScriptType, ScriptAddress, ScriptWitness, Instruction, is_witness_commitment_script};
-pub use self::sign::{TransactionInputSigner, UnsignedTransactionInput, SignatureVersion};
+pub use self::script::{Script, ScriptType, ScriptAddress, ScriptWitness, Instruction, is_witness_commitment_script,
+	is_witness_commitment, parse_multisig, extract_addresses, classify, p2pk_address};
+pub use self::sign::{TransactionInputSigner, UnsignedTransactionInput, SignatureVersion, SighashCache,
+	LegacySighashCache, SigningOptions, SignData, MissingKeyPolicy, SignError, sign_transaction};
 pub use self::stack::Stack;
+pub use self::transaction_builder::{TransactionBuilder, BuilderError, dust_threshold};
 pub use self::verify::{SignatureChecker, NoopSignatureChecker, TransactionSignatureChecker};
 
@@ -0,0 +1,81 @@
+//! Multisig redeem scripts
+
+use keys::Public;
+use {Builder, Error, Opcode, Script};
+
+/// Maximal number of public keys, which can be pushed with `OP_1`..`OP_16`
+pub const MAX_STANDARD_MULTISIG_PUBKEYS: usize = 16;
+
+/// Builds m-of-n `OP_m <pubkey>... OP_n OP_CHECKMULTISIG` redeem script.
+/// Requires `1 <= m <= n <= 16`.
+pub fn build_redeem_script(m: usize, pubkeys: &[Public]) -> Result<Script, Error> {
+	let n = pubkeys.len();
+	if n == 0 || n > MAX_STANDARD_MULTISIG_PUBKEYS {
+		return Err(Error::PubkeyCount);
+	}
+
+	if m == 0 || m > n {
+		return Err(Error::SigCount);
+	}
+
+	let script = pubkeys.iter()
+		.fold(Builder::default().push_opcode(small_int(m)), |builder, public| builder.push_bytes(public))
+		.push_opcode(small_int(n))
+		.push_opcode(Opcode::OP_CHECKMULTISIG)
+		.into_script();
+
+	Ok(script)
+}
+
+fn small_int(n: usize) -> Opcode {
+	Opcode::from_u8(Opcode::OP_1 as u8 + n as u8 - 1)
+		.expect("n is within [1; 16] interval; qed")
+}
+
+#[cfg(test)]
+mod tests {
+	use std::slice;
+	use keys::{Address, Network, Public};
+	use bytes::Bytes;
+	use {Error, Script};
+	use super::build_redeem_script;
+
+	fn public(hex: &'static str) -> Public {
+		Public::from_slice(&Bytes::from(hex)).unwrap()
+	}
+
+	// test vectors from BIP67
+	#[test]
+	fn test_build_redeem_script() {
+		let pubkeys = vec![
+			public("02632b12f4ac5b1d1b72b2a3b508c19172de44f6f46bcee50ba33f3f9291e47ed0"),
+			public("027735a29bae7780a9755fae7a1c4374c656ac6a69ea9f3697fda61bb99a4f3e77"),
+			public("02e2cc6bd5f45edd43bebe7cb9b675f0ce9ed3efe613b177588290ad188d11b404"),
+		];
+		let script = build_redeem_script(2, &pubkeys).unwrap();
+		let expected: Script = "522102632b12f4ac5b1d1b72b2a3b508c19172de44f6f46bcee50ba33f3f9291e47ed021027735a29bae7780a9755fae7a1c4374c656ac6a69ea9f3697fda61bb99a4f3e772102e2cc6bd5f45edd43bebe7cb9b675f0ce9ed3efe613b177588290ad188d11b40453ae".into();
+		assert_eq!(script, expected);
+		assert!(script.is_multisig_script());
+		assert_eq!(Address::p2sh_from_script(&script, Network::Mainnet).to_string(), "3CKHTjBKxCARLzwABMu9yD85kvtm7WnMfH");
+
+		let pubkeys = vec![
+			public("02fe6f0a5a297eb38c391581c4413e084773ea23954d93f7753db7dc0adc188b2f"),
+			public("02ff12471208c14bd580709cb2358d98975247d8765f92bc25eab3b2763ed605f8"),
+		];
+		let script = build_redeem_script(2, &pubkeys).unwrap();
+		assert_eq!(Address::p2sh_from_script(&script, Network::Mainnet).to_string(), "39bgKC7RFbpoCRbtD5KEdkYKtNyhpsNa3Z");
+	}
+
+	#[test]
+	fn test_build_redeem_script_invalid_counts() {
+		let pubkey = public("02fe6f0a5a297eb38c391581c4413e084773ea23954d93f7753db7dc0adc188b2f");
+		assert_eq!(build_redeem_script(1, &[]), Err(Error::PubkeyCount));
+		assert_eq!(build_redeem_script(1, &vec![pubkey.clone(); 17]), Err(Error::PubkeyCount));
+		assert_eq!(build_redeem_script(0, slice::from_ref(&pubkey)), Err(Error::SigCount));
+		assert_eq!(build_redeem_script(2, slice::from_ref(&pubkey)), Err(Error::SigCount));
+
+		let script = build_redeem_script(16, &vec![pubkey; 16]).unwrap();
+		assert_eq!(script[0], 0x60);
+		assert_eq!(script[script.len() - 2], 0x60);
+	}
+}
@@ -2,8 +2,11 @@
 
 use std::{fmt, ops};
 use bytes::Bytes;
-use keys::{self, AddressHash, Public};
-use {Opcode, Error};
+use crypto::sha256;
+use hash::H256;
+use keys::{self, Address, AddressHash, Network, Public};
+use interpreter::is_valid_signature_encoding;
+use {Opcode, Error, Num};
 
 /// Maximum number of bytes pushable to the stack
 pub const MAX_SCRIPT_ELEMENT_SIZE: usize = 520;
@@ -99,6 +102,12 @@ impl Script {
 		self.data.clone()
 	}
 
+	/// Electrum protocol script hash: `sha256` of the script in reversed byte order.
+	/// `to_string()` of the result gives the hex expected by Electrum servers.
+	pub fn electrum_hash(&self) -> H256 {
+		sha256(&self.data).reversed()
+	}
+
 	/// Is empty script
 	pub fn is_empty(&self) -> bool {
 		self.data.len() == 0
@@ -129,6 +138,16 @@ impl Script {
 		self.data.len() == len && self.data[len - 1] == Opcode::OP_CHECKSIG as u8
 	}
 
+	/// Returns public key of pay-to-public-key (P2PK) script, compressed or uncompressed.
+	/// Returns `None` if the script is not P2PK.
+	pub fn pay_to_public_key(&self) -> Option<Public> {
+		if !self.is_pay_to_public_key() {
+			return None;
+		}
+
+		Public::from_slice(&self.data[1..self.data.len() - 1]).ok()
+	}
+
 	/// Extra-fast test for pay-to-script-hash (P2SH) scripts.
 	pub fn is_pay_to_script_hash(&self) -> bool {
 		self.data.len() == 23 &&
@@ -165,6 +184,22 @@ impl Script {
 			self.data[1] == Opcode::OP_PUSHBYTES_32 as u8
 	}
 
+	/// Returns witness program redeem script, if this is the script_sig spending P2SH-wrapped
+	/// (nested) segwit output, i.e. a single direct push of the witness program.
+	/// The P2SH output itself can't be told apart from any other P2SH output.
+	pub fn wrapped_witness_program(&self) -> Option<Script> {
+		if self.data.len() < 2 || self.data[0] as usize != self.data.len() - 1 {
+			return None;
+		}
+
+		let redeem_script: Script = self.data[1..].to_vec().into();
+		if redeem_script.parse_witness_program().is_some() {
+			Some(redeem_script)
+		} else {
+			None
+		}
+	}
+
 	/// Extra-fast test for multisig scripts.
 	pub fn is_multisig_script(&self) -> bool {
 		if self.data.len() < 3 {
@@ -245,6 +280,61 @@ impl Script {
 		result.into()
 	}
 
+	/// Disassembles the script the way `scriptPubKey.asm` of bitcoind RPC does,
+	/// e.g. `OP_DUP OP_HASH160 <hex> OP_EQUALVERIFY OP_CHECKSIG`.
+	pub fn to_asm_string(&self) -> String {
+		self.to_asm(false)
+	}
+
+	/// Disassembles the script the way `scriptSig.asm` of bitcoind RPC does,
+	/// i.e. signatures are shown with decoded sighash type, e.g. `<hex>[ALL]`.
+	pub fn to_asm_string_with_sighash(&self) -> String {
+		self.to_asm(true)
+	}
+
+	fn to_asm(&self, decode_sighash: bool) -> String {
+		let is_unspendable = self.data.len() > MAX_SCRIPT_SIZE ||
+			self.data.first() == Some(&(Opcode::OP_RETURN as u8));
+		let decode_sighash = decode_sighash && !is_unspendable;
+
+		let mut parts = Vec::new();
+		let mut pc = 0;
+		while pc < self.len() {
+			if Opcode::from_u8(self.data[pc]).is_none() {
+				parts.push("OP_UNKNOWN".to_owned());
+				pc += 1;
+				continue;
+			}
+
+			let instruction = match self.get_instruction(pc) {
+				Ok(i) => i,
+				Err(_) => {
+					parts.push("[error]".to_owned());
+					break;
+				},
+			};
+
+			let part = match instruction.data {
+				Some(data) if data.len() <= 4 => {
+					let num = Num::from_slice(data, false, 4).expect("data is at most 4 bytes long; qed");
+					i64::from(num).to_string()
+				},
+				Some(data) => match sighash_name(data) {
+					Some(name) if decode_sighash => format!("{:?}[{}]", Bytes::from(&data[..data.len() - 1]), name),
+					_ => format!("{:?}", Bytes::from(data)),
+				},
+				None if instruction.opcode == Opcode::OP_1NEGATE => "-1".to_owned(),
+				None if instruction.opcode.is_within_op_n() => instruction.opcode.decode_op_n().to_string(),
+				None => instruction.opcode.to_string(),
+			};
+
+			parts.push(part);
+			pc += instruction.step;
+		}
+
+		parts.join(" ")
+	}
+
 	pub fn get_opcode(&self, position: usize) -> Result<Opcode, Error> {
 		Opcode::from_u8(self.data[position]).ok_or(Error::BadOpcode)
 	}
@@ -288,10 +378,16 @@ impl Script {
 		Ok(instruction)
 	}
 
+	/// Returns `len` bytes starting at `offset`, or `Error::TruncatedPush`
+	/// if the script ends before all of them are available.
 	#[inline]
 	pub fn take(&self, offset: usize, len: usize) -> Result<&[u8], Error> {
 		if offset + len > self.data.len() {
-			Err(Error::BadOpcode)
+			Err(Error::TruncatedPush {
+				offset,
+				expected: len,
+				available: self.data.len().saturating_sub(offset),
+			})
 		} else {
 			Ok(&self.data[offset..offset + len])
 		}
@@ -340,23 +436,7 @@ impl Script {
 	}
 
 	pub fn script_type(&self) -> ScriptType {
-		if self.is_pay_to_public_key() {
-			ScriptType::PubKey
-		} else if self.is_pay_to_public_key_hash() {
-			ScriptType::PubKeyHash
-		} else if self.is_pay_to_script_hash() {
-			ScriptType::ScriptHash
-		} else if self.is_multisig_script() {
-			ScriptType::Multisig
-		} else if self.is_null_data_script() {
-			ScriptType::NullData
-		} else if self.is_pay_to_witness_key_hash() {
-			ScriptType::WitnessKey
-		} else if self.is_pay_to_witness_script_hash() {
-			ScriptType::WitnessScript
-		} else {
-			ScriptType::NonStandard
-		}
+		classify(self)
 	}
 
 	pub fn iter(&self) -> Instructions {
@@ -413,12 +493,8 @@ impl Script {
 				Ok(vec![])
 			},
 			ScriptType::PubKey => {
-				Public::from_slice(match self.data[0] {
-					x if x == Opcode::OP_PUSHBYTES_33 as u8 => &self.data[1..34],
-					x if x == Opcode::OP_PUSHBYTES_65 as u8 => &self.data[1..66],
-					_ => unreachable!(), // because we are relying on script_type() checks here
-				})
-				.map(|public| vec![ScriptAddress::new_p2pkh(public.address_hash())])
+				let public = self.pay_to_public_key().expect("this method depends on previous check in script_type()");
+				Ok(vec![ScriptAddress::new_p2pkh(public.address_hash())])
 			},
 			ScriptType::PubKeyHash => {
 				Ok(vec![
@@ -570,6 +646,64 @@ impl fmt::Display for Script {
 	}
 }
 
+/// Returns type of the standard output script template.
+/// `OP_0 <20 bytes>` is `WitnessKey` (witness_v0_keyhash), `OP_0 <32 bytes>` is `WitnessScript` (witness_v0_scripthash).
+pub fn classify(script: &Script) -> ScriptType {
+	if script.is_pay_to_public_key() {
+		ScriptType::PubKey
+	} else if script.is_pay_to_public_key_hash() {
+		ScriptType::PubKeyHash
+	} else if script.is_pay_to_script_hash() {
+		ScriptType::ScriptHash
+	} else if script.is_multisig_script() {
+		ScriptType::Multisig
+	} else if script.is_null_data_script() {
+		ScriptType::NullData
+	} else if script.is_pay_to_witness_key_hash() {
+		ScriptType::WitnessKey
+	} else if script.is_pay_to_witness_script_hash() {
+		ScriptType::WitnessScript
+	} else {
+		ScriptType::NonStandard
+	}
+}
+
+/// Returns P2PKH address of the public key paid by P2PK script, as explorers show it for "pubkey" outputs.
+/// Returns `None` if the script is not P2PK.
+pub fn p2pk_address(script: &Script, network: Network) -> Option<Address> {
+	script.pay_to_public_key().map(|public| Address::from_public(&public, network))
+}
+
+/// Returns type of the output script and addresses it pays to, as shown in `scriptPubKey` of RPC responses.
+/// Multisig scripts give addresses of all signers, P2PK scripts give P2PKH address of the public key.
+/// Nulldata, witness and non-standard scripts, as well as scripts with invalid public keys, give no addresses.
+pub fn extract_addresses(script: &Script, network: Network) -> (ScriptType, Vec<Address>) {
+	let addresses = script.extract_destinations()
+		.unwrap_or_default()
+		.into_iter()
+		.filter_map(|address| Address::new(network, address.kind, address.hash).ok())
+		.collect();
+
+	(classify(script), addresses)
+}
+
+/// Name of the sighash type of strictly encoded signature, as printed in the script asm.
+fn sighash_name(signature: &[u8]) -> Option<&'static str> {
+	if !is_valid_signature_encoding(signature) {
+		return None;
+	}
+
+	match signature[signature.len() - 1] {
+		0x01 => Some("ALL"),
+		0x81 => Some("ALL|ANYONECANPAY"),
+		0x02 => Some("NONE"),
+		0x82 => Some("NONE|ANYONECANPAY"),
+		0x03 => Some("SINGLE"),
+		0x83 => Some("SINGLE|ANYONECANPAY"),
+		_ => None,
+	}
+}
+
 pub type ScriptWitness = Vec<Bytes>;
 
 /// Passed bytes array is a commitment script?
@@ -584,11 +718,58 @@ pub fn is_witness_commitment_script(script: &[u8]) -> bool {
 		script[5] == 0xED
 }
 
+/// Is `OP_RETURN OP_PUSHBYTES_36 aa21a9ed <32 bytes commitment>` output script?
+/// Such outputs are provably unspendable and may be skipped when building the UTXO set.
+pub fn is_witness_commitment(script: &Script) -> bool {
+	script.len() >= 38 && is_witness_commitment_script(script)
+}
+
+/// Parses `OP_m <pubkey...> OP_n OP_CHECKMULTISIG` redeem script.
+/// Returns number of required signatures and public keys or `None` if script is not a multisig.
+pub fn parse_multisig(script: &Script) -> Option<(u8, Vec<Public>)> {
+	if !script.is_multisig_script() {
+		return None;
+	}
+
+	let small_int = |position| match script.get_opcode(position) {
+		Ok(Opcode::OP_0) => Some(0),
+		Ok(o) if o >= Opcode::OP_1 && o <= Opcode::OP_16 => Some(o as u8 - (Opcode::OP_1 as u8 - 1)),
+		_ => None,
+	};
+
+	let m = small_int(0)?;
+	let n = small_int(script.len() - 2)?;
+
+	let mut pubkeys = Vec::with_capacity(n as usize);
+	let mut pc = 1;
+	while pc < script.len() - 2 {
+		let instruction = script.get_instruction(pc).ok()?;
+		pubkeys.push(Public::from_slice(instruction.data?).ok()?);
+		pc += instruction.step;
+	}
+
+	if pubkeys.len() != n as usize || m > n {
+		return None;
+	}
+
+	Some((m, pubkeys))
+}
+
 #[cfg(test)]
 mod tests {
-	use {Builder, Opcode};
-	use super::{Script, ScriptType, ScriptAddress, MAX_SCRIPT_ELEMENT_SIZE};
-	use keys::{Address, Public};
+	use {Builder, Opcode, Error};
+	use bytes::Bytes;
+	use crypto::dhash160;
+	use super::{Script, ScriptType, ScriptAddress, MAX_SCRIPT_ELEMENT_SIZE, parse_multisig,
+		is_witness_commitment, extract_addresses, classify, p2pk_address};
+	use keys::{Address, Network, Public};
+
+	#[test]
+	fn test_electrum_hash() {
+		// https://electrumx.readthedocs.io/en/latest/protocol-basics.html#script-hashes
+		let script: Script = "76a91462e907b15cbf27d5425399ebf6f0fb50ebb88f1888ac".into();
+		assert_eq!(script.electrum_hash().to_string(), "8b01df4e368ea28f8dc0423bcf7a4923e3a12d307c875e47a0cfbf90b5c39161");
+	}
 
 	#[test]
 	fn test_is_pay_to_script_hash() {
@@ -614,6 +795,35 @@ mod tests {
 		assert!(!script2.is_pay_to_witness_script_hash());
 	}
 
+	// https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki#p2sh-p2wpkh
+	#[test]
+	fn test_wrapped_witness_program() {
+		let script_pubkey: Script = "a9144733f37cf4db86fbc2efed2500b4f4e49f31202387".into();
+		let script_sig: Script = "16001479091972186c449eb1ded22b78e40d009bdf0089".into();
+		assert!(script_pubkey.is_pay_to_script_hash());
+		assert!(!script_pubkey.is_pay_to_witness_key_hash());
+		assert_eq!(script_pubkey.wrapped_witness_program(), None);
+
+		let redeem_script = script_sig.wrapped_witness_program().unwrap();
+		assert!(redeem_script.is_pay_to_witness_key_hash());
+		assert!(!redeem_script.is_pay_to_witness_script_hash());
+		assert_eq!(Builder::build_p2sh(&dhash160(&redeem_script)), script_pubkey);
+
+		// https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki#native-p2wsh
+		let witness_script_hash: Script = "00205d1b56b63d714eebe542309525f484b7e9d6f686b3781b6f61ef925d66d6f6a0".into();
+		assert!(witness_script_hash.is_pay_to_witness_script_hash());
+		assert!(!witness_script_hash.is_pay_to_witness_key_hash());
+		assert!(!witness_script_hash.is_pay_to_script_hash());
+		let script_sig: Script = "2200205d1b56b63d714eebe542309525f484b7e9d6f686b3781b6f61ef925d66d6f6a0".into();
+		assert_eq!(script_sig.wrapped_witness_program(), Some(witness_script_hash));
+
+		// P2PKH spend pushes signature and public key
+		let script_sig: Script = "47304402202cb265bf10707bf49346c3515dd3d16fc454618c58ec0a0ff448a676c54ff71302206c6624d762a1fcef4618284ead8f08678ac05b13c84235f1654e6ad168233e8201410414e301b2328f17442c0b8310d787bf3d8a404cfbd0704f135b6ad4b2d3ee751310f981926e53a6e8c39bd7d3fefd576c543cce493cbac06388f2651d1aacbfcd".into();
+		assert_eq!(script_sig.wrapped_witness_program(), None);
+		assert_eq!(Script::from("1600").wrapped_witness_program(), None);
+		assert_eq!(Script::from("").wrapped_witness_program(), None);
+	}
+
 	#[test]
 	fn test_script_debug() {
 		use std::fmt::Write;
@@ -643,6 +853,57 @@ OP_ADD
 		assert_eq!(script.to_string(), s.to_string());
 	}
 
+	#[test]
+	fn test_parse_multisig() {
+		let pubkeys: Vec<Public> = [
+			"0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+			"02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5",
+			"02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+		].iter().map(|hex| Public::from_slice(&Bytes::from(*hex)).unwrap()).collect();
+		let script = Builder::default()
+			.push_opcode(Opcode::OP_2)
+			.push_data(&pubkeys[0])
+			.push_data(&pubkeys[1])
+			.push_data(&pubkeys[2])
+			.push_opcode(Opcode::OP_3)
+			.push_opcode(Opcode::OP_CHECKMULTISIG)
+			.into_script();
+		assert_eq!(parse_multisig(&script), Some((2, pubkeys)));
+
+		let p2pkh: Script = "76a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac".into();
+		assert_eq!(parse_multisig(&p2pkh), None);
+	}
+
+	#[test]
+	fn test_is_witness_commitment() {
+		// coinbase output of block 481824
+		let commitment: Script = "6a24aa21a9ede2f61c3f71d1defd3fa999dfa36953755c690689799962b48bebd836974e8cf9".into();
+		assert!(is_witness_commitment(&commitment));
+		let truncated: Script = "6a24aa21a9ede2f61c3f71d1defd3fa999dfa36953755c690689799962b48bebd836974e8c".into();
+		assert!(!is_witness_commitment(&truncated));
+		let null_data: Script = "6a24bb21a9ede2f61c3f71d1defd3fa999dfa36953755c690689799962b48bebd836974e8cf9".into();
+		assert!(!is_witness_commitment(&null_data));
+	}
+
+	#[test]
+	fn test_script_truncated_push() {
+		let script: Script = "4b010203".into();
+		let error = Error::TruncatedPush { offset: 1, expected: 75, available: 3 };
+		assert_eq!(script.get_instruction(0).err(), Some(error));
+		let error = Error::TruncatedPush { offset: 1, expected: 75, available: 3 };
+		assert_eq!(script.iter().next().unwrap().err(), Some(error));
+
+		// truncated length of OP_PUSHDATA2
+		let script: Script = "4d01".into();
+		let error = Error::TruncatedPush { offset: 1, expected: 2, available: 1 };
+		assert_eq!(script.get_instruction(0).err(), Some(error));
+
+		// truncated data of OP_PUSHDATA2
+		let script: Script = "4d0200ff".into();
+		let error = Error::TruncatedPush { offset: 3, expected: 2, available: 1 };
+		assert_eq!(script.get_instruction(0).err(), Some(error));
+	}
+
 	#[test]
 	fn test_script_without_op_codeseparator() {
 		let script: Script = "ab00270025512102e485fdaa062387c0bbb5ab711a093b6635299ec155b7b852fce6b992d5adbfec51ae".into();
@@ -802,4 +1063,99 @@ OP_ADD
 		assert_eq!(script.script_type(), ScriptType::ScriptHash);
 		assert_eq!(script.num_signatures_required(), 1);
 	}
+
+	// https://kmdexplorer.io/tx/88893f05764f5a781f2e555a5b492c064f2269a4a44c51afdbe98fab54361bb5
+	#[test]
+	fn test_to_asm_string() {
+		let script: Script = "a914fef59ae800bb89050d25f67be432b231097e184987".into();
+		assert_eq!(script.to_asm_string(), "OP_HASH160 fef59ae800bb89050d25f67be432b231097e1849 OP_EQUAL");
+		let script: Script = "76a91473122bcec852f394e51496e39fca5111c3d7ae5688ac".into();
+		assert_eq!(script.to_asm_string(), "OP_DUP OP_HASH160 73122bcec852f394e51496e39fca5111c3d7ae56 OP_EQUALVERIFY OP_CHECKSIG");
+		let script: Script = "6a083037646431356334".into();
+		assert_eq!(script.to_asm_string(), "OP_RETURN 3037646431356334");
+		let script: Script = "210388392e0885e449ea9745ce7ad2631fdca5288f9d790cee1b696e67c75ad54a2dac".into();
+		assert_eq!(script.to_asm_string(), "0388392e0885e449ea9745ce7ad2631fdca5288f9d790cee1b696e67c75ad54a2d OP_CHECKSIG");
+
+		let script: Script = "4730440220070963b9460d9bafe7865563574594fc3f823e5cdf7c49a5642dade76502547f022023fd90d41e34e514237f4b5967f83c9af27673d6de2eae3d88079a988fa5be3e012103668e3368c9fb67d8fc808a5fe74d5a8d21b6eed726838122d5f7716fb3328998".into();
+		assert_eq!(script.to_asm_string_with_sighash(), "30440220070963b9460d9bafe7865563574594fc3f823e5cdf7c49a5642dade76502547f022023fd90d41e34e514237f4b5967f83c9af27673d6de2eae3d88079a988fa5be3e[ALL] 03668e3368c9fb67d8fc808a5fe74d5a8d21b6eed726838122d5f7716fb3328998");
+		assert_eq!(script.to_asm_string(), "30440220070963b9460d9bafe7865563574594fc3f823e5cdf7c49a5642dade76502547f022023fd90d41e34e514237f4b5967f83c9af27673d6de2eae3d88079a988fa5be3e01 03668e3368c9fb67d8fc808a5fe74d5a8d21b6eed726838122d5f7716fb3328998");
+	}
+
+	#[test]
+	fn test_to_asm_string_numbers_and_errors() {
+		let script: Script = "004f516004010203040281000181b1".into();
+		assert_eq!(script.to_asm_string(), "0 -1 1 16 67305985 129 -1 OP_CHECKLOCKTIMEVERIFY");
+		let script: Script = "51ff".into();
+		assert_eq!(script.to_asm_string(), "1 OP_UNKNOWN");
+		let script: Script = "514c05".into();
+		assert_eq!(script.to_asm_string(), "1 [error]");
+		assert_eq!(Script::from(Bytes::new()).to_asm_string(), "");
+	}
+
+	// https://kmdexplorer.io/tx/88893f05764f5a781f2e555a5b492c064f2269a4a44c51afdbe98fab54361bb5
+	#[test]
+	fn test_extract_addresses() {
+		let (script_type, addresses) = extract_addresses(&"76a91473122bcec852f394e51496e39fca5111c3d7ae5688ac".into(), Network::Komodo);
+		assert_eq!(script_type, ScriptType::PubKeyHash);
+		assert_eq!(addresses, vec!["RKmdZ8QA7XbJ4JGUAvtHtWEogKxfgaQuqv".parse().unwrap()]);
+
+		let (script_type, addresses) = extract_addresses(&"a914fef59ae800bb89050d25f67be432b231097e184987".into(), Network::Komodo);
+		assert_eq!(script_type, ScriptType::ScriptHash);
+		assert_eq!(addresses, vec!["bbyNYu11Qs3PowiPr1Su4ozQk7hsVmv821".parse().unwrap()]);
+
+		let (script_type, addresses) = extract_addresses(&"210388392e0885e449ea9745ce7ad2631fdca5288f9d790cee1b696e67c75ad54a2dac".into(), Network::Komodo);
+		assert_eq!(script_type, ScriptType::PubKey);
+		assert_eq!(addresses, vec!["RM5wffThEVKQdG98uLa2gc8Nk4CzX9Fq4q".parse().unwrap()]);
+
+		let (script_type, addresses) = extract_addresses(&"6a083037646431356334".into(), Network::Komodo);
+		assert_eq!(script_type, ScriptType::NullData);
+		assert!(addresses.is_empty());
+
+		// 1-of-2 multisig of uncompressed and compressed keys of secret 1
+		let script: Script = "51410479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f8179852ae".into();
+		let (script_type, addresses) = extract_addresses(&script, Network::Mainnet);
+		assert_eq!(script_type, ScriptType::Multisig);
+		let addresses: Vec<String> = addresses.iter().map(ToString::to_string).collect();
+		assert_eq!(addresses, vec!["1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm", "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH"]);
+	}
+
+	#[test]
+	fn test_p2pk_address() {
+		// Komodo coinbase output
+		let script: Script = "210388392e0885e449ea9745ce7ad2631fdca5288f9d790cee1b696e67c75ad54a2dac".into();
+		assert_eq!(script.pay_to_public_key(), Some(Public::from_slice(&script[1..34]).unwrap()));
+		assert_eq!(p2pk_address(&script, Network::Komodo), Some("RM5wffThEVKQdG98uLa2gc8Nk4CzX9Fq4q".parse().unwrap()));
+
+		let script: Script = "410479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8ac".into();
+		assert_eq!(p2pk_address(&script, Network::Mainnet).unwrap().to_string(), "1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm");
+
+		let script: Script = "76a91473122bcec852f394e51496e39fca5111c3d7ae5688ac".into();
+		assert_eq!(script.pay_to_public_key(), None);
+		assert_eq!(p2pk_address(&script, Network::Komodo), None);
+	}
+
+	#[test]
+	fn test_classify() {
+		let cases: &[(&'static str, ScriptType)] = &[
+			("210388392e0885e449ea9745ce7ad2631fdca5288f9d790cee1b696e67c75ad54a2dac", ScriptType::PubKey),
+			("410479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8ac", ScriptType::PubKey),
+			("76a91473122bcec852f394e51496e39fca5111c3d7ae5688ac", ScriptType::PubKeyHash),
+			("a914fef59ae800bb89050d25f67be432b231097e184987", ScriptType::ScriptHash),
+			("51210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f8179851ae", ScriptType::Multisig),
+			("6a083037646431356334", ScriptType::NullData),
+			("6a", ScriptType::NullData),
+			("0014751e76e8199196d454941c45d1b3a323f1433bd6", ScriptType::WitnessKey),
+			("00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262", ScriptType::WitnessScript),
+			("5114751e76e8199196d454941c45d1b3a323f1433bd6", ScriptType::NonStandard),
+			("0015751e76e8199196d454941c45d1b3a323f1433bd600", ScriptType::NonStandard),
+			("51", ScriptType::NonStandard),
+			("", ScriptType::NonStandard),
+		];
+
+		for &(hex, ref expected) in cases {
+			let script: Script = hex.into();
+			assert_eq!(&classify(&script), expected, "{}", hex);
+			assert_eq!(&script.script_type(), expected);
+		}
+	}
 }
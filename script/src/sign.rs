@@ -1,12 +1,13 @@
 //! Transaction signer
 
+use std::collections::HashMap;
 use blake2b_simd::{Params as Blake2b};
 use bytes::Bytes;
 use chain::{Transaction, TransactionOutput, OutPoint, TransactionInput, JoinSplit, ShieldedSpend, ShieldedOutput};
 use crypto::dhash256;
 use hash::{H256, H512};
-use keys::KeyPair;
-use ser::{Stream};
+use keys::{Error as KeysError, KeyPair};
+use ser::{CompactInteger, Stream};
 use {Script, Builder};
 
 const ZCASH_PREVOUTS_HASH_PERSONALIZATION: &[u8] = b"ZcashPrevoutHash";
@@ -102,17 +103,21 @@ impl Sighash {
 	}
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct UnsignedTransactionInput {
 	pub previous_output: OutPoint,
 	pub sequence: u32,
 	pub amount: u64,
+	/// Sighash type of the original signature, if the input was already signed.
+	/// `sign_transaction` re-signs the input with the same sighash type.
+	pub sighash: Option<u32>,
 }
 
 /// Used for resigning and loading test transactions
 impl From<TransactionInput> for UnsignedTransactionInput {
 	fn from(i: TransactionInput) -> Self {
 		UnsignedTransactionInput {
+			sighash: i.detected_sighash(),
 			previous_output: i.previous_output,
 			sequence: i.sequence,
 			amount: 0,
@@ -120,6 +125,16 @@ impl From<TransactionInput> for UnsignedTransactionInput {
 	}
 }
 
+/// Signature creation options, for matching reference signatures byte by byte.
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct SigningOptions {
+	/// Only produce signatures with low R, at most 71 bytes long including the sighash type.
+	pub low_r: bool,
+	/// Grind the nonce until R is low, as Bitcoin Core does.
+	/// Without grinding, signing with `low_r` fails if the RFC6979 nonce gives high R.
+	pub grind: bool,
+}
+
 #[derive(Clone, Debug)]
 pub struct TransactionInputSigner {
 	pub version: i32,
@@ -203,6 +218,12 @@ impl TransactionInputSigner {
 		}
 	}
 
+	/// Sighash type the input is signed with when none is given explicitly:
+	/// the sighash type of its original signature, `SIGHASH_ALL` if it wasn't signed.
+	pub fn default_sighash(&self, input_index: usize) -> u32 {
+		self.inputs[input_index].sighash.unwrap_or_else(|| SighashBase::All.into())
+	}
+
 	/// input_index - index of input to sign
 	/// script_pubkey - script_pubkey of input's previous_output pubkey
 	pub fn signed_input(
@@ -214,22 +235,89 @@ impl TransactionInputSigner {
 		sigversion: SignatureVersion,
 		sighash: u32,
 	) -> TransactionInput {
+		self.signed_input_with_options(keypair, input_index, input_amount, script_pubkey, sigversion, sighash, SigningOptions::default())
+			.expect("signing without low R requirement never fails for a valid keypair; qed")
+	}
+
+	/// Same as `signed_input`, but creates the signature according to the `options`.
+	#[allow(clippy::too_many_arguments)]
+	pub fn signed_input_with_options(
+		&self,
+		keypair: &KeyPair,
+		input_index: usize,
+		input_amount: u64,
+		script_pubkey: &Script,
+		sigversion: SignatureVersion,
+		sighash: u32,
+		options: SigningOptions,
+	) -> Result<TransactionInput, KeysError> {
 		let hash = self.signature_hash(input_index, input_amount, script_pubkey, sigversion, sighash);
+		self.signed_input_with_hash(keypair, input_index, &hash, sighash, options)
+	}
+
+	/// Creates `script_sig` of the input signing already computed signature `hash`.
+	fn signed_input_with_hash(
+		&self,
+		keypair: &KeyPair,
+		input_index: usize,
+		hash: &H256,
+		sighash: u32,
+		options: SigningOptions,
+	) -> Result<TransactionInput, KeysError> {
+		let signature = match options {
+			SigningOptions { low_r: true, grind: true } => keypair.private().sign_low_r(hash)?,
+			SigningOptions { low_r: true, grind: false } => {
+				let signature = keypair.private().sign(hash)?;
+				if !signature.has_low_r() {
+					return Err(KeysError::InvalidSignature);
+				}
+				signature
+			},
+			SigningOptions { low_r: false, .. } => keypair.private().sign(hash)?,
+		};
 
-		let mut signature: Vec<u8> = keypair.private().sign(&hash).unwrap().into();
+		let mut signature: Vec<u8> = signature.into();
 		signature.push(sighash as u8);
 		let script_sig = Builder::default()
 			.push_data(&signature)
-			//.push_data(keypair.public())
+			.push_data(keypair.public())
 			.into_script();
 
 		let unsigned_input = &self.inputs[input_index];
-		TransactionInput {
+		Ok(TransactionInput {
 			previous_output: unsigned_input.previous_output.clone(),
 			sequence: unsigned_input.sequence,
 			script_sig: script_sig.to_bytes(),
 			script_witness: vec![],
-		}
+		})
+	}
+
+	/// input_index - index of input to sign
+	/// script_code - BIP143 script code of the spent output, for P2WPKH it's the P2PKH script of the key hash
+	pub fn signed_witness_input(
+		&self,
+		keypair: &KeyPair,
+		input_index: usize,
+		input_amount: u64,
+		script_code: &Script,
+		sighash: u32,
+	) -> TransactionInput {
+		let hash = self.signature_hash(input_index, input_amount, script_code, SignatureVersion::WitnessV0, sighash);
+		self.signed_witness_input_with_hash(keypair, input_index, &hash, sighash).unwrap()
+	}
+
+	/// Creates witness of the input signing already computed signature `hash`.
+	fn signed_witness_input_with_hash(&self, keypair: &KeyPair, input_index: usize, hash: &H256, sighash: u32) -> Result<TransactionInput, KeysError> {
+		let mut signature: Vec<u8> = keypair.private().sign(hash)?.into();
+		signature.push(sighash as u8);
+
+		let unsigned_input = &self.inputs[input_index];
+		Ok(TransactionInput {
+			previous_output: unsigned_input.previous_output.clone(),
+			sequence: unsigned_input.sequence,
+			script_sig: Bytes::default(),
+			script_witness: vec![signature.into(), keypair.public().to_vec().into()],
+		})
 	}
 
 	pub fn signature_hash_original(&self, input_index: usize, script_pubkey: &Script, sighashtype: u32, sighash: Sighash) -> H256 {
@@ -316,23 +404,7 @@ impl TransactionInputSigner {
 	}
 
 	fn signature_hash_witness0(&self, input_index: usize, input_amount: u64, script_pubkey: &Script, sighashtype: u32, sighash: Sighash) -> H256 {
-		let hash_prevouts = compute_hash_prevouts(sighash, &self.inputs);
-		let hash_sequence = compute_hash_sequence(sighash, &self.inputs);
-		let hash_outputs = compute_hash_outputs(sighash, input_index, &self.outputs);
-
-		let mut stream = Stream::default();
-		stream.append(&self.version);
-		stream.append(&hash_prevouts);
-		stream.append(&hash_sequence);
-		stream.append(&self.inputs[input_index].previous_output);
-		stream.append_list(&**script_pubkey);
-		stream.append(&input_amount);
-		stream.append(&self.inputs[input_index].sequence);
-		stream.append(&hash_outputs);
-		stream.append(&self.lock_time);
-		stream.append(&sighashtype); // this also includes 24-bit fork id. which is 0 for BitcoinCash
-		let out = stream.out();
-		dhash256(&out)
+		SighashCache::with_sighash(self, sighashtype, sighash).signature_hash(input_index, input_amount, script_pubkey)
 	}
 
 	fn signature_hash_fork_id(&self, input_index: usize, input_amount: u64, script_pubkey: &Script, sighashtype: u32, sighash: Sighash) -> H256 {
@@ -347,10 +419,10 @@ impl TransactionInputSigner {
 		self.signature_hash_witness0(input_index, input_amount, script_pubkey, sighashtype, sighash)
 	}
 
-	/// https://github.com/zcash/zips/blob/master/zip-0243.rst#notes
-	/// This method doesn't cover all possible Sighash combinations so it doesn't fully match the
-	/// specification, however I don't need other cases yet as BarterDEX marketmaker always uses
-	/// SIGHASH_ALL
+	/// https://github.com/zcash/zips/blob/master/zip-0143.rst
+	/// https://github.com/zcash/zips/blob/master/zip-0243.rst
+	/// SIGHASH_SINGLE with `input_index` not matching any output commits to the zero outputs hash,
+	/// as there is no SIGHASH_SINGLE bug in Zcash.
 	pub fn signature_hash_overwintered(
 		&self,
 		input_index: usize,
@@ -377,26 +449,44 @@ impl TransactionInputSigner {
 		sig_hash_stream.append(&header);
 		sig_hash_stream.append(&self.version_group_id);
 
-		let mut prev_out_stream = Stream::new();
-		for input in self.inputs.iter() {
-			prev_out_stream.append(&input.previous_output);
+		if !sighash.anyone_can_pay {
+			let mut prev_out_stream = Stream::new();
+			for input in self.inputs.iter() {
+				prev_out_stream.append(&input.previous_output);
+			}
+			sig_hash_stream.append(&blake_2b_256_personal(&prev_out_stream.out(), ZCASH_PREVOUTS_HASH_PERSONALIZATION));
+		} else {
+			sig_hash_stream.append(&H256::default());
 		}
-		sig_hash_stream.append(&blake_2b_256_personal(&prev_out_stream.out(), ZCASH_PREVOUTS_HASH_PERSONALIZATION));
 
-		let mut sequence_stream = Stream::new();;
-		for input in self.inputs.iter() {
-			sequence_stream.append(&input.sequence);
+		if !sighash.anyone_can_pay && sighash.base == SighashBase::All {
+			let mut sequence_stream = Stream::new();
+			for input in self.inputs.iter() {
+				sequence_stream.append(&input.sequence);
+			}
+			sig_hash_stream.append(&blake_2b_256_personal(&sequence_stream.out(), ZCASH_SEQUENCE_HASH_PERSONALIZATION));
+		} else {
+			sig_hash_stream.append(&H256::default());
 		}
 
-		sig_hash_stream.append(&blake_2b_256_personal(&sequence_stream.out(), ZCASH_SEQUENCE_HASH_PERSONALIZATION));
-
-		let mut outputs_stream = Stream::new();;
-		for output in self.outputs.iter() {
-			outputs_stream.append(output);
+		match sighash.base {
+			SighashBase::All => {
+				let mut outputs_stream = Stream::new();
+				for output in self.outputs.iter() {
+					outputs_stream.append(output);
+				}
+				sig_hash_stream.append(&blake_2b_256_personal(&outputs_stream.out(), ZCASH_OUTPUTS_HASH_PERSONALIZATION));
+			},
+			SighashBase::Single if input_index < self.outputs.len() => {
+				let mut outputs_stream = Stream::new();
+				outputs_stream.append(&self.outputs[input_index]);
+				sig_hash_stream.append(&blake_2b_256_personal(&outputs_stream.out(), ZCASH_OUTPUTS_HASH_PERSONALIZATION));
+			},
+			_ => {
+				sig_hash_stream.append(&H256::default());
+			},
 		}
 
-		sig_hash_stream.append(&blake_2b_256_personal(&outputs_stream.out(), ZCASH_OUTPUTS_HASH_PERSONALIZATION));
-
 		if self.join_splits.len() > 0 {
 			let mut join_splits_stream = Stream::new();
 			for split in self.join_splits.iter() {
@@ -445,6 +535,276 @@ impl TransactionInputSigner {
 	}
 }
 
+/// BIP143 signature hasher, which computes `hashPrevouts`, `hashSequence` and `hashOutputs`
+/// only once and reuses them for all inputs signed with the same sighash type.
+pub struct SighashCache<'a> {
+	signer: &'a TransactionInputSigner,
+	sighashtype: u32,
+	sighash: Sighash,
+	hash_prevouts: H256,
+	hash_sequence: H256,
+	/// Commits to all outputs, used only by `SighashBase::All`
+	hash_outputs: H256,
+}
+
+impl<'a> SighashCache<'a> {
+	pub fn new(signer: &'a TransactionInputSigner, sighashtype: u32) -> Self {
+		SighashCache::with_sighash(signer, sighashtype, Sighash::from_u32(SignatureVersion::WitnessV0, sighashtype))
+	}
+
+	fn with_sighash(signer: &'a TransactionInputSigner, sighashtype: u32, sighash: Sighash) -> Self {
+		let hash_outputs = match sighash.base {
+			SighashBase::All => compute_hash_outputs(sighash, 0, &signer.outputs),
+			_ => 0u8.into(),
+		};
+
+		SighashCache {
+			signer,
+			sighashtype,
+			sighash,
+			hash_prevouts: compute_hash_prevouts(sighash, &signer.inputs),
+			hash_sequence: compute_hash_sequence(sighash, &signer.inputs),
+			hash_outputs,
+		}
+	}
+
+	/// input_index - index of input to sign
+	/// script_code - BIP143 script code of the spent output
+	pub fn signature_hash(&self, input_index: usize, input_amount: u64, script_code: &Script) -> H256 {
+		let hash_outputs = match self.sighash.base {
+			SighashBase::All => self.hash_outputs.clone(),
+			_ => compute_hash_outputs(self.sighash, input_index, &self.signer.outputs),
+		};
+		let input = &self.signer.inputs[input_index];
+
+		let mut stream = Stream::default();
+		stream.append(&self.signer.version);
+		stream.append(&self.hash_prevouts);
+		stream.append(&self.hash_sequence);
+		stream.append(&input.previous_output);
+		stream.append_list(script_code);
+		stream.append(&input_amount);
+		stream.append(&input.sequence);
+		stream.append(&hash_outputs);
+		stream.append(&self.signer.lock_time);
+		stream.append(&self.sighashtype); // this also includes 24-bit fork id. which is 0 for BitcoinCash
+		let out = stream.out();
+		dhash256(&out)
+	}
+}
+
+/// Legacy signature hasher, which serializes outputs and the transaction tail only once
+/// and streams the modified inputs directly, instead of building a copy of the transaction for every input.
+/// Produces the same hashes as `TransactionInputSigner::signature_hash_original`.
+pub struct LegacySighashCache<'a> {
+	signer: &'a TransactionInputSigner,
+	sighashtype: u32,
+	sighash: Sighash,
+	/// Serialized outputs list, used only by `SighashBase::All`
+	outputs: Bytes,
+	/// Serialized lock time and coin-specific data following it
+	tail: Bytes,
+}
+
+impl<'a> LegacySighashCache<'a> {
+	pub fn new(signer: &'a TransactionInputSigner, sighashtype: u32) -> Self {
+		let sighash = Sighash::from_u32(SignatureVersion::Base, sighashtype);
+		let outputs = match sighash.base {
+			SighashBase::All => {
+				let mut stream = Stream::default();
+				stream.append_list(&signer.outputs);
+				stream.out()
+			},
+			_ => Bytes::default(),
+		};
+
+		let mut stream = Stream::default();
+		stream.append(&signer.lock_time);
+		if signer.zcash && signer.version == 2 {
+			// empty join splits list
+			stream.append(&CompactInteger::from(0u8));
+		}
+		if let Some(ref string) = signer.str_d_zeel {
+			stream.append(&CompactInteger::from(string.len()));
+			stream.append_slice(string.as_bytes());
+		}
+
+		LegacySighashCache {
+			signer,
+			sighashtype,
+			sighash,
+			outputs,
+			tail: stream.out(),
+		}
+	}
+
+	/// input_index - index of input to sign
+	/// script_pubkey - script of the spent output
+	pub fn signature_hash(&self, input_index: usize, script_pubkey: &Script) -> H256 {
+		let signer = self.signer;
+		if input_index >= signer.inputs.len() {
+			return 1u8.into();
+		}
+
+		if self.sighash.base == SighashBase::Single && input_index >= signer.outputs.len() {
+			return 1u8.into();
+		}
+
+		if signer.version >= 3 && signer.overwintered {
+			return signer.signature_hash_original(input_index, script_pubkey, self.sighashtype, self.sighash);
+		}
+
+		let script_pubkey = script_pubkey.without_separators().to_bytes();
+
+		let mut stream = Stream::default();
+		stream.append(&signer.version);
+		if let Some(n_time) = signer.n_time {
+			stream.append(&n_time);
+		}
+
+		if self.sighash.anyone_can_pay {
+			let input = &signer.inputs[input_index];
+			stream.append(&CompactInteger::from(1u8))
+				.append(&input.previous_output)
+				.append(&script_pubkey)
+				.append(&input.sequence);
+		} else {
+			stream.append(&CompactInteger::from(signer.inputs.len()));
+			for (n, input) in signer.inputs.iter().enumerate() {
+				let sequence = match self.sighash.base {
+					SighashBase::Single | SighashBase::None if n != input_index => 0,
+					_ => input.sequence,
+				};
+
+				stream.append(&input.previous_output);
+				if n == input_index {
+					stream.append(&script_pubkey);
+				} else {
+					stream.append(&Bytes::default());
+				}
+				stream.append(&sequence);
+			}
+		}
+
+		match self.sighash.base {
+			SighashBase::All => {
+				stream.append_slice(&self.outputs);
+			},
+			SighashBase::Single => {
+				stream.append(&CompactInteger::from(input_index + 1));
+				for _ in 0..input_index {
+					stream.append(&TransactionOutput::default());
+				}
+				stream.append(&signer.outputs[input_index]);
+			},
+			SighashBase::None => {
+				stream.append(&CompactInteger::from(0u8));
+			},
+		}
+
+		stream.append_slice(&self.tail);
+		stream.append(&self.sighashtype);
+		dhash256(&stream.out())
+	}
+}
+
+/// Data required to sign the input spending some output.
+#[derive(Debug)]
+pub struct SignData {
+	/// Script of the spent output, either P2PKH or P2WPKH
+	pub script_pubkey: Script,
+	/// Value of the spent output
+	pub amount: u64,
+	/// Key owning the spent output
+	pub key_pair: KeyPair,
+}
+
+/// What `sign_transaction` does with inputs, which have no `SignData`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum MissingKeyPolicy {
+	/// Keep the input as it is, e.g. to be signed by another party.
+	LeaveUnsigned,
+	/// Fail with `SignError::MissingKey`.
+	Fail,
+}
+
+/// Transaction signing error
+#[derive(Debug, PartialEq)]
+pub enum SignError {
+	/// No `SignData` for the input spending the outpoint
+	MissingKey(OutPoint),
+	/// Spent output is neither P2PKH nor P2WPKH
+	UnsupportedScript(OutPoint),
+	/// Key doesn't own the spent output
+	KeyMismatch(OutPoint),
+	Keys(KeysError),
+}
+
+impl From<KeysError> for SignError {
+	fn from(e: KeysError) -> Self {
+		SignError::Keys(e)
+	}
+}
+
+/// Signs all P2PKH and P2WPKH inputs of the transaction with the sighash type of their
+/// original signatures, or `SIGHASH_ALL` if they are not signed yet.
+/// Overwintered transactions are signed with `consensus_branch_id` of the upgrade active at the height
+/// the transaction is mined at, see `ConsensusBranchId::for_height`. It's ignored for other transactions.
+pub fn sign_transaction(
+	tx: Transaction,
+	inputs: &HashMap<OutPoint, SignData>,
+	consensus_branch_id: u32,
+	missing_key: MissingKeyPolicy,
+) -> Result<Transaction, SignError> {
+	let mut signer = TransactionInputSigner::from(tx.clone());
+	if signer.overwintered {
+		signer.consensus_branch_id = consensus_branch_id;
+	}
+	for (input, unsigned) in signer.inputs.iter_mut().zip(tx.inputs.iter()) {
+		if let Some(data) = inputs.get(&unsigned.previous_output) {
+			input.amount = data.amount;
+		}
+	}
+
+	// sighash digests shared by all inputs are computed once per sighash type
+	let mut legacy_caches = HashMap::new();
+	let mut witness_caches = HashMap::new();
+	let mut signed = tx;
+	for (index, input) in signed.inputs.iter_mut().enumerate() {
+		let outpoint = input.previous_output.clone();
+		let data = match inputs.get(&outpoint) {
+			Some(data) => data,
+			None if missing_key == MissingKeyPolicy::LeaveUnsigned => continue,
+			None => return Err(SignError::MissingKey(outpoint)),
+		};
+
+		let key_hash = data.key_pair.public().address_hash();
+		let script_code = Builder::build_p2pkh(&key_hash);
+		let sighash = signer.default_sighash(index);
+		*input = if data.script_pubkey.is_pay_to_public_key_hash() {
+			if data.script_pubkey != script_code {
+				return Err(SignError::KeyMismatch(outpoint));
+			}
+			let hash = legacy_caches.entry(sighash)
+				.or_insert_with(|| LegacySighashCache::new(&signer, sighash))
+				.signature_hash(index, &script_code);
+			signer.signed_input_with_hash(&data.key_pair, index, &hash, sighash, SigningOptions::default())?
+		} else if data.script_pubkey.is_pay_to_witness_key_hash() {
+			if data.script_pubkey.parse_witness_program() != Some((0, &*key_hash)) {
+				return Err(SignError::KeyMismatch(outpoint));
+			}
+			let hash = witness_caches.entry(sighash)
+				.or_insert_with(|| SighashCache::new(&signer, sighash))
+				.signature_hash(index, data.amount, &script_code);
+			signer.signed_witness_input_with_hash(&data.key_pair, index, &hash, sighash)?
+		} else {
+			return Err(SignError::UnsupportedScript(outpoint));
+		};
+	}
+
+	Ok(signed)
+}
+
 fn compute_hash_prevouts(sighash: Sighash, inputs: &[UnsignedTransactionInput]) -> H256 {
 	match sighash.anyone_can_pay {
 		false => {
@@ -504,10 +864,14 @@ fn blake_2b_256_personal(input: &[u8], personal: &[u8]) -> H256 {
 mod tests {
 	use bytes::Bytes;
 	use hash::H256;
-	use keys::{Private, Address};
-	use chain::{OutPoint, TransactionOutput, Transaction};
+	use keys::{Private, Address, KeyPair, Network};
+	use chain::{OutPoint, TransactionInput, TransactionOutput, Transaction};
 	use script::Script;
-	use super::{Sighash, UnsignedTransactionInput, TransactionInputSigner, SighashBase, SignatureVersion, blake_2b_256_personal};
+	use std::collections::HashMap;
+	use {Builder, ConsensusBranchId, ScriptWitness, TransactionSignatureChecker, VerificationFlags, verify_script};
+	use super::{Sighash, UnsignedTransactionInput, TransactionInputSigner, SighashBase, SignatureVersion, SighashCache,
+		LegacySighashCache, SigningOptions, KeysError, SignData, MissingKeyPolicy, SignError, sign_transaction,
+		blake_2b_256_personal};
 
 	// http://www.righto.com/2014/02/bitcoins-hard-way-using-raw-bitcoin.html
 	// https://blockchain.info/rawtx/81b4c832d70cb56ff957589752eb4125a4cab78a25a8fc52d6a09e5bd4404d48
@@ -533,6 +897,7 @@ mod tests {
 				hash: previous_tx_hash,
 			},
 			amount: 0,
+			sighash: None,
 		};
 
 		let output = TransactionOutput {
@@ -562,6 +927,326 @@ mod tests {
 		assert_eq!(hash, expected_signature_hash);
 	}
 
+	fn check_signed_input_pushes(compressed: bool, public_len: usize) {
+		let keypair = KeyPair::from_private(Private {
+			prefix: 128,
+			secret: 1.into(),
+			compressed,
+			checksum_type: Default::default(),
+		}).unwrap();
+		let script_pubkey = Builder::build_p2pkh(&keypair.public().address_hash());
+		let input_signer = TransactionInputSigner {
+			version: 1,
+			n_time: None,
+			overwintered: false,
+			version_group_id: 0,
+			consensus_branch_id: 0,
+			expiry_height: 0,
+			value_balance: 0,
+			lock_time: 0,
+			inputs: vec![UnsignedTransactionInput {
+				previous_output: OutPoint::default(),
+				sequence: 0xffff_ffff,
+				amount: 0,
+				sighash: None,
+			}],
+			outputs: vec![],
+			join_splits: vec![],
+			shielded_spends: vec![],
+			shielded_outputs: vec![],
+			zcash: false,
+			str_d_zeel: None,
+		};
+
+		let input = input_signer.signed_input(&keypair, 0, 0, &script_pubkey, SignatureVersion::Base, SighashBase::All.into());
+		let script_sig: Script = input.script_sig.into();
+		let pushes: Vec<_> = script_sig.iter().map(|i| i.unwrap().data.unwrap().to_vec()).collect();
+		assert_eq!(pushes.len(), 2);
+		assert_eq!(pushes[0].last(), Some(&(SighashBase::All as u8)));
+		assert_eq!(&pushes[1][..], &**keypair.public());
+		assert_eq!(pushes[1].len(), public_len);
+	}
+
+	#[test]
+	fn test_signed_input_pushes_public_key() {
+		check_signed_input_pushes(true, 33);
+		check_signed_input_pushes(false, 65);
+	}
+
+	#[test]
+	fn test_signed_input_low_r() {
+		let tx: Transaction = "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000".into();
+		let signer: TransactionInputSigner = tx.into();
+		let keypair = KeyPair::from_private(Private {
+			prefix: 128,
+			secret: "619c335025c7f4012e556c2a58b2506e30b8511b53ade95ea316fd8c3286feb9".into(),
+			compressed: true,
+			checksum_type: Default::default(),
+		}).unwrap();
+		let script_code = Builder::build_p2pkh(&keypair.public().address_hash());
+		let low_r = SigningOptions { low_r: true, grind: true };
+		let no_grind = SigningOptions { low_r: true, grind: false };
+
+		let mut high_r = 0;
+		for amount in 0..16 {
+			let sign = |options| signer.signed_input_with_options(&keypair, 1, amount, &script_code, SignatureVersion::WitnessV0, SighashBase::All.into(), options);
+			let input = sign(low_r).unwrap();
+			let script_sig: Script = input.script_sig.into();
+			let signature = script_sig.iter().next().unwrap().unwrap().data.unwrap().to_vec();
+			assert!(signature.len() <= 71);
+
+			let default = sign(SigningOptions::default()).unwrap();
+			if default.script_sig.len() > script_sig.len() {
+				high_r += 1;
+				assert_eq!(sign(no_grind), Err(KeysError::InvalidSignature));
+			} else {
+				assert_eq!(sign(no_grind).unwrap(), default);
+			}
+		}
+		assert!(high_r > 0);
+	}
+
+	// https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki#native-p2wpkh
+	#[test]
+	fn test_signed_witness_input() {
+		let tx: Transaction = "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000".into();
+		let signer: TransactionInputSigner = tx.into();
+		let keypair = KeyPair::from_private(Private {
+			prefix: 128,
+			secret: "619c335025c7f4012e556c2a58b2506e30b8511b53ade95ea316fd8c3286feb9".into(),
+			compressed: true,
+			checksum_type: Default::default(),
+		}).unwrap();
+		let script_pubkey: Script = "00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1".into();
+		let (_, program) = script_pubkey.parse_witness_program().unwrap();
+		assert_eq!(program, &*keypair.public().address_hash());
+		let script_code = Builder::build_p2pkh(&keypair.public().address_hash());
+
+		let input = signer.signed_witness_input(&keypair, 1, 600_000_000, &script_code, SighashBase::All.into());
+		assert!(input.script_sig.is_empty());
+		assert_eq!(input.script_witness.len(), 2);
+		assert_eq!(input.script_witness[0], "304402203609e17b84f6a7d30c80bfa610b5b4542f32a8a0d5447a12fb1366d7f01cc44a0220573a954c4518331561406f90300e8f3358f51928d43c212a8caed02de67eebee01".into());
+		assert_eq!(input.script_witness[1], "025476c2e83188368da1ff3e292e7acafcdb3566bb0ad253f62fc70f07aeee6357".into());
+	}
+
+	#[test]
+	fn test_sign_transaction() {
+		// https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki#native-p2wpkh
+		let tx: Transaction = "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000".into();
+		let legacy_pair = KeyPair::from_private("5HusYj2b2x4nroApgfvaSfKYZhRbKFH41bVyPooymbC6KfgSXdD".into()).unwrap();
+		let witness_pair = KeyPair::from_private(Private::new(Network::Mainnet, "619c335025c7f4012e556c2a58b2506e30b8511b53ade95ea316fd8c3286feb9".into(), true)).unwrap();
+		let legacy_script = Builder::build_p2pkh(&legacy_pair.public().address_hash());
+		let witness_script: Script = "00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1".into();
+
+		let mut inputs = HashMap::new();
+		inputs.insert(tx.inputs[1].previous_output.clone(), SignData {
+			script_pubkey: witness_script.clone(),
+			amount: 600_000_000,
+			key_pair: witness_pair,
+		});
+		assert_eq!(sign_transaction(tx.clone(), &inputs, 0, MissingKeyPolicy::Fail).unwrap_err(),
+			SignError::MissingKey(tx.inputs[0].previous_output.clone()));
+		let partially_signed = sign_transaction(tx.clone(), &inputs, 0, MissingKeyPolicy::LeaveUnsigned).unwrap();
+		assert_eq!(partially_signed.inputs[0], tx.inputs[0]);
+
+		inputs.insert(tx.inputs[0].previous_output.clone(), SignData {
+			script_pubkey: legacy_script.clone(),
+			amount: 625_000_000,
+			key_pair: legacy_pair,
+		});
+		let signed = sign_transaction(tx.clone(), &inputs, 0, MissingKeyPolicy::Fail).unwrap();
+		assert_eq!(signed.inputs[1], partially_signed.inputs[1]);
+		assert_eq!(signed.inputs[1].script_witness[0], "304402203609e17b84f6a7d30c80bfa610b5b4542f32a8a0d5447a12fb1366d7f01cc44a0220573a954c4518331561406f90300e8f3358f51928d43c212a8caed02de67eebee01".into());
+
+		let flags = VerificationFlags::default()
+			.verify_p2sh(true)
+			.verify_witness(true);
+		let spent = vec![(legacy_script, 625_000_000), (witness_script, 600_000_000)];
+		for (index, (script_pubkey, amount)) in spent.into_iter().enumerate() {
+			let checker = TransactionSignatureChecker {
+				signer: signed.clone().into(),
+				input_index: index,
+				input_amount: amount,
+			};
+			let input = &signed.inputs[index];
+			let witness: ScriptWitness = input.script_witness.clone();
+			assert_eq!(verify_script(&input.script_sig.clone().into(), &script_pubkey, &witness, &flags, &checker, SignatureVersion::Base), Ok(()));
+		}
+	}
+
+	#[test]
+	fn test_sign_transaction_keeps_sighash() {
+		let mut tx: Transaction = "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000".into();
+		// signature with SIGHASH_SINGLE from an earlier signing round
+		let previous_signature: Bytes = "304402203609e17b84f6a7d30c80bfa610b5b4542f32a8a0d5447a12fb1366d7f01cc44a0220573a954c4518331561406f90300e8f3358f51928d43c212a8caed02de67eebee03".into();
+		tx.inputs[0].script_sig = Builder::default().push_data(&previous_signature).into_script().to_bytes();
+
+		let key_pair = KeyPair::from_private("5HusYj2b2x4nroApgfvaSfKYZhRbKFH41bVyPooymbC6KfgSXdD".into()).unwrap();
+		let script_pubkey = Builder::build_p2pkh(&key_pair.public().address_hash());
+		let mut inputs = HashMap::new();
+		inputs.insert(tx.inputs[0].previous_output.clone(), SignData {
+			script_pubkey: script_pubkey.clone(),
+			amount: 625_000_000,
+			key_pair,
+		});
+
+		let signed = sign_transaction(tx, &inputs, 0, MissingKeyPolicy::LeaveUnsigned).unwrap();
+		assert_eq!(signed.inputs[0].detected_sighash(), Some(SighashBase::Single.into()));
+
+		let flags = VerificationFlags::default().verify_p2sh(true);
+		let checker = TransactionSignatureChecker {
+			signer: signed.clone().into(),
+			input_index: 0,
+			input_amount: 625_000_000,
+		};
+		let script_sig = signed.inputs[0].script_sig.clone().into();
+		assert_eq!(verify_script(&script_sig, &script_pubkey, &vec![], &flags, &checker, SignatureVersion::Base), Ok(()));
+	}
+
+	#[test]
+	fn test_sign_transaction_matches_signed_input() {
+		let mut tx: Transaction = "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000".into();
+		let extra_input = tx.inputs[1].clone();
+		tx.inputs.push(TransactionInput { previous_output: OutPoint { index: 2, ..extra_input.previous_output.clone() }, ..extra_input });
+		// second input was signed with SIGHASH_NONE before
+		let previous_signature: Bytes = "304402203609e17b84f6a7d30c80bfa610b5b4542f32a8a0d5447a12fb1366d7f01cc44a0220573a954c4518331561406f90300e8f3358f51928d43c212a8caed02de67eebee02".into();
+		tx.inputs[1].script_sig = Builder::default().push_data(&previous_signature).into_script().to_bytes();
+
+		let script_pubkey = Builder::build_p2pkh(&KeyPair::from_private("5HusYj2b2x4nroApgfvaSfKYZhRbKFH41bVyPooymbC6KfgSXdD".into()).unwrap().public().address_hash());
+		let mut inputs = HashMap::new();
+		for (index, input) in tx.inputs.iter().enumerate() {
+			inputs.insert(input.previous_output.clone(), SignData {
+				script_pubkey: script_pubkey.clone(),
+				amount: 1_000 * index as u64,
+				key_pair: KeyPair::from_private("5HusYj2b2x4nroApgfvaSfKYZhRbKFH41bVyPooymbC6KfgSXdD".into()).unwrap(),
+			});
+		}
+
+		let signed = sign_transaction(tx.clone(), &inputs, 0, MissingKeyPolicy::Fail).unwrap();
+		let signer = TransactionInputSigner::from(tx.clone());
+		for (index, input) in tx.inputs.iter().enumerate() {
+			let data = &inputs[&input.previous_output];
+			let sighash = if index == 1 { SighashBase::None.into() } else { SighashBase::All.into() };
+			let expected = signer.signed_input(&data.key_pair, index, data.amount, &script_pubkey, SignatureVersion::Base, sighash);
+			assert_eq!(signed.inputs[index], expected);
+		}
+	}
+
+	#[test]
+	fn test_sign_transaction_overwintered() {
+		let mut tx: Transaction = "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000".into();
+		tx.version = 4;
+		tx.overwintered = true;
+		tx.version_group_id = 0x892f_2085;
+		tx.zcash = true;
+
+		let key_pair = KeyPair::from_private("5HusYj2b2x4nroApgfvaSfKYZhRbKFH41bVyPooymbC6KfgSXdD".into()).unwrap();
+		let script_pubkey = Builder::build_p2pkh(&key_pair.public().address_hash());
+		let mut inputs = HashMap::new();
+		inputs.insert(tx.inputs[0].previous_output.clone(), SignData {
+			script_pubkey: script_pubkey.clone(),
+			amount: 625_000_000,
+			key_pair,
+		});
+
+		let signed = sign_transaction(tx, &inputs, ConsensusBranchId::SAPLING, MissingKeyPolicy::LeaveUnsigned).unwrap();
+		let script_sig = signed.inputs[0].script_sig.clone().into();
+		let flags = VerificationFlags::default().verify_p2sh(true);
+		for &(consensus_branch_id, valid) in &[(ConsensusBranchId::SAPLING, true), (ConsensusBranchId::BLOSSOM, false)] {
+			let mut signer: TransactionInputSigner = signed.clone().into();
+			signer.consensus_branch_id = consensus_branch_id;
+			signer.inputs[0].amount = 625_000_000;
+			let checker = TransactionSignatureChecker {
+				signer,
+				input_index: 0,
+				input_amount: 625_000_000,
+			};
+			assert_eq!(verify_script(&script_sig, &script_pubkey, &vec![], &flags, &checker, SignatureVersion::Base).is_ok(), valid);
+		}
+	}
+
+	#[test]
+	fn test_sign_transaction_wrong_key() {
+		let tx: Transaction = "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000".into();
+		let outpoint = tx.inputs[0].previous_output.clone();
+		let sign = |script_pubkey: &'static str| {
+			let mut inputs = HashMap::new();
+			inputs.insert(outpoint.clone(), SignData {
+				script_pubkey: script_pubkey.into(),
+				amount: 0,
+				key_pair: KeyPair::from_private("5HusYj2b2x4nroApgfvaSfKYZhRbKFH41bVyPooymbC6KfgSXdD".into()).unwrap(),
+			});
+			sign_transaction(tx.clone(), &inputs, 0, MissingKeyPolicy::LeaveUnsigned)
+		};
+
+		assert_eq!(sign("76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac").unwrap_err(), SignError::KeyMismatch(outpoint.clone()));
+		assert_eq!(sign("00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1").unwrap_err(), SignError::KeyMismatch(outpoint.clone()));
+		assert_eq!(sign("a9141a8b0026343166625c7475f01e48b5ede8c0252e87").unwrap_err(), SignError::UnsupportedScript(outpoint.clone()));
+	}
+
+	#[test]
+	fn test_sighash_cache_bip143_vectors() {
+		// https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki#native-p2wpkh
+		let tx: Transaction = "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000".into();
+		let signer: TransactionInputSigner = tx.into();
+		let script_code: Script = "76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac".into();
+		let expected: H256 = "c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670".into();
+		assert_eq!(SighashCache::new(&signer, SighashBase::All.into()).signature_hash(1, 600_000_000, &script_code), expected);
+
+		// https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki#p2sh-p2wsh
+		let tx: Transaction = "010000000136641869ca081e70f394c6948e8af409e18b619df2ed74aa106c1ca29787b96e0100000000ffffffff0200e9a435000000001976a914389ffce9cd9ae88dcc0631e88a821ffdbe9bfe2688acc0832f05000000001976a9147480a33f950689af511e6e84c138dbbd3c3ee41588ac00000000".into();
+		let signer: TransactionInputSigner = tx.into();
+		let witness_script: Script = "56210307b8ae49ac90a048e9b53357a2354b3334e9c8bee813ecb98e99a7e07e8c3ba32103b28f0c28bfab54554ae8c658ac5c3e0ce6e79ad336331f78c428dd43eea8449b21034b8113d703413d57761b8b9781957b8c0ac1dfe69f492580ca4195f50376ba4a21033400f6afecb833092a9a21cfdf1ed1376e58c5d1f47de74683123987e967a8f42103a6d48b1131e94ba04d9737d61acdaa1322008af9602b3b14862c07a1789aac162102d8b661b0b3302ee2f162b09e07a55ad5dfbe673a9f01d9f0c19617681024306b56ae".into();
+		let vectors = [
+			(0x01, "185c0be5263dce5b4bb50a047973c1b6272bfbd0103a89444597dc40b248ee7c"),
+			(0x02, "e9733bc60ea13c95c6527066bb975a2ff29a925e80aa14c213f686cbae5d2f36"),
+			(0x03, "1e1f1c303dc025bd664acb72e583e933fae4cff9148bf78c157d1e8f78530aea"),
+			(0x81, "2a67f03e63a6a422125878b40b82da593be8d4efaafe88ee528af6e5a9955c6e"),
+			(0x82, "781ba15f3779d5542ce8ecb5c18716733a5ee42a6f51488ec96154934e2c890a"),
+			(0x83, "511e8e52ed574121fc1b654970395502128263f62662e076dc6baf05c2e6a99b"),
+		];
+
+		for &(sighashtype, expected) in &vectors {
+			let expected: H256 = expected.into();
+			assert_eq!(SighashCache::new(&signer, sighashtype).signature_hash(0, 987_654_321, &witness_script), expected);
+			assert_eq!(signer.signature_hash(0, 987_654_321, &witness_script, SignatureVersion::WitnessV0, sighashtype), expected);
+		}
+	}
+
+	#[test]
+	fn test_legacy_sighash_cache_matches_uncached() {
+		// from Bitcoin Core sighash.json
+		let tx: Transaction = "22d81c740469695a6a83a9a4824f77ecff8804d020df23713990afce2b72591ed7de98500502000000065352526a6a6affffffff90dc85e118379b1005d7bbc7d2b8b0bab104dad7eaa49ff5bead892f17d8c3ba010000000665656300ab51ffffffff965193879e1d5628b52005d8560a35a2ba57a7f19201a4045b7cbab85133311d0200000003ac005348af21a13f9b4e0ad90ed20bf84e4740c8a9d7129632590349afc03799414b76fd6e826200000000025353ffffffff04a0d40d04000000000060702700000000000652655151516ad31f1502000000000365ac0069a1ac0500000000095100655300ab53525100000000".into();
+		let signer: TransactionInputSigner = tx.into();
+		let script: Script = "51636a52ac".into();
+		let expected = H256::from_reversed_str("add7f5da27262f13da6a1e2cc2feafdc809bd66a67fb8ae2a6f5e6be95373b6f");
+		assert_eq!(LegacySighashCache::new(&signer, -1644680765i32 as u32).signature_hash(0, &script), expected);
+
+		let mut pos = signer.clone();
+		pos.n_time = Some(0x5a8c4a6f);
+		pos.str_d_zeel = Some("navcoin test".into());
+		let mut zcash = signer.clone();
+		zcash.version = 2;
+		zcash.zcash = true;
+		// more inputs than outputs, so that SIGHASH_SINGLE of the last input returns one
+		let mut single = signer.clone();
+		let extra_input = single.inputs[0].clone();
+		single.inputs.push(extra_input);
+
+		// script with OP_CODESEPARATOR
+		let script: Script = "51ab52".into();
+		for signer in &[signer, pos, zcash, single] {
+			for sighashtype in &[0x01, 0x02, 0x03, 0x81, 0x82, 0x83, 0x04] {
+				let cache = LegacySighashCache::new(signer, *sighashtype);
+				let sighash = Sighash::from_u32(SignatureVersion::Base, *sighashtype);
+				for input_index in 0..signer.inputs.len() + 1 {
+					let expected = signer.signature_hash_original(input_index, &script, *sighashtype, sighash);
+					assert_eq!(cache.signature_hash(input_index, &script), expected);
+				}
+			}
+		}
+	}
+
 	fn run_test_sighash(
 		tx: &'static str,
 		script: &'static str,
@@ -579,6 +1264,56 @@ mod tests {
 		assert_eq!(expected, hash);
 	}
 
+	// Vectors below are from Bitcoin Core sighash.json. Vectors with the highest version bit set
+	// are skipped, since it marks overwintered transactions here.
+
+	// SIGHASH_SINGLE | SIGHASH_ANYONECANPAY
+	#[test]
+	fn test_sighash_single_anyone_can_pay() {
+		run_test_sighash("d3b7421e011f4de0f1cea9ba7458bf3486bee722519efab711a963fa8c100970cf7488b7bb0200000003525352dcd61b300148be5d05000000000000000000", "535251536aac536a", 0, -1960128125, "29aa6d2d752d3310eba20442770ad345b7f6a35f96161ede5f07b33e92053e2a");
+		run_test_sighash("92c9fe210201e781b72554a0ed5e22507fb02434ddbaa69aff6e74ea8bad656071f1923f3f02000000056a63ac6a514470cef985ba83dcb8eee2044807bedbf0d983ae21286421506ae276142359c8c6a34d68020000000863ac63525265006aa796dd0102ca3f9d05000000000800abab52ab535353cd5c83010000000007ac00525252005322ac75ee", "5165", 0, 97879971, "6e6307cef4f3a9b386f751a6f40acebab12a0e7e17171d2989293cbec7fd45c2");
+		run_test_sighash("22d81c740469695a6a83a9a4824f77ecff8804d020df23713990afce2b72591ed7de98500502000000065352526a6a6affffffff90dc85e118379b1005d7bbc7d2b8b0bab104dad7eaa49ff5bead892f17d8c3ba010000000665656300ab51ffffffff965193879e1d5628b52005d8560a35a2ba57a7f19201a4045b7cbab85133311d0200000003ac005348af21a13f9b4e0ad90ed20bf84e4740c8a9d7129632590349afc03799414b76fd6e826200000000025353ffffffff04a0d40d04000000000060702700000000000652655151516ad31f1502000000000365ac0069a1ac0500000000095100655300ab53525100000000", "51636a52ac", 0, -1644680765, "add7f5da27262f13da6a1e2cc2feafdc809bd66a67fb8ae2a6f5e6be95373b6f");
+		run_test_sighash("ed3bb93802ddbd08cb030ef60a2247f715a0226de390c9c1a81d52e83f8674879065b5f87d0300000003ab6552ffffffff04d2c5e60a21fb6da8de20bf206db43b720e2a24ce26779bca25584c3f765d1e0200000008ab656a6aacab00ab6e946ded025a811d04000000000951abac6352ac00ab5143cfa3030000000005635200636a00000000", "5352ac650065535300", 1, -668727133, "e9995065e1fddef72a796eef5274de62012249660dc9d233a4f24e02a2979c87");
+		run_test_sighash("344fa11e01c19c4dd232c77742f0dd0aeb3695f18f76da627628741d0ee362b0ea1fb3a2180200000007635151005100529bab25af01937c1f0500000000055153ab53656e7630af", "6351005163ac51", 0, -629732125, "228ca52a0a376fe0527a61cfa8da6d7baf87486bba92d49dfd3899cac8a1034f");
+	}
+
+	// SIGHASH_NONE | SIGHASH_ANYONECANPAY
+	#[test]
+	fn test_sighash_none_anyone_can_pay() {
+		run_test_sighash("32fa0b0804e6ea101e137665a041cc2350b794e59bf42d9b09088b01cde806ec1bbea077df0200000008515153650000006506a11c55904258fa418e57b88b12724b81153260d3f4c9f080439789a391ab147aabb0fa0000000007000052ac51ab510986f2a15c0d5e05d20dc876dd2dafa435276d53da7b47c393f20900e55f163b97ce0b800000000008ab526a520065636a8087df7d4d9c985fb42308fb09dce704650719140aa6050e8955fa5d2ea46b464a333f870000000009636300636a6565006affffffff01994a0d040000000002536500000000", "516563530065", 2, -163068286, "f58637277d2bc42e18358dc55f7e87e7043f5e33f4ce1fc974e715ef0d3d1c2a");
+	}
+
+	// SIGHASH_SINGLE
+	#[test]
+	fn test_sighash_single() {
+		run_test_sighash("ceecfa6c02b7e3345445b82226b15b7a097563fa7d15f3b0c979232b138124b62c0be007890200000009abac51536a63525253ffffffffbae481ccb4f15d94db5ec0d8854c24c1cc8642bd0c6300ede98a91ca13a4539a0200000001ac50b0813d023110f5020000000006acabac526563e2b0d0040000000009656aac0063516a536300000000", "0063526500", 0, -1862053821, "e1600e6df8a6160a79ac32aa40bb4644daa88b5f76c0d7d13bf003327223f70c");
+		run_test_sighash("ae23424d040cd884ebfb9a815d8f17176980ab8015285e03fdde899449f4ae71e04275e9a80100000007ab006553530053ffffffff018e06db6af519dadc5280c07791c0fd33251500955e43fe4ac747a4df5c54df020000000251ac330e977c0fec6149a1768e0d312fdb53ed9953a3737d7b5d06aad4d86e9970346a4feeb5030000000951ab51ac6563ab526a67cabc431ee3d8111224d5ecdbb7d717aa8fe82ce4a63842c9bd1aa848f111910e5ae1eb0100000004ac515300bfb7e0d7048acddc030000000009636a5253636a655363a3428e040000000001525b99c6050000000004655265ab717e6e020000000000d99011eb", "ac6a6a516565", 1, -716251549, "b098eb9aff1bbd375c70a0cbb9497882ab51f3abfebbf4e1f8d74c0739dc7717");
+		run_test_sighash("86bc233e02ba3c647e356558e7252481a7769491fb46e883dd547a4ce9898fc9a1ca1b77790000000006ab5351abab51f0c1d09c37696d5c7c257788f5dff5583f4700687bcb7d4acfb48521dc953659e325fa390300000003acac5280f29523027225af03000000000963abac0065ab65acab7e59d90400000000016549dac846", "53006aac52acac", 0, 711159875, "880330ccde00991503ea598a6dfd81135c6cda9d317820352781417f89134d85");
+		run_test_sighash("b1c0b71804dff30812b92eefb533ac77c4b9fdb9ab2f77120a76128d7da43ad70c20bbfb990200000002536392693e6001bc59411aebf15a3dc62a6566ec71a302141b0c730a3ecc8de5d76538b30f55010000000665535252ac514b740c6271fb9fe69fdf82bf98b459a7faa8a3b62f3af34943ad55df4881e0d93d3ce0ac0200000000c4158866eb9fb73da252102d1e64a3ce611b52e873533be43e6883137d0aaa0f63966f060000000001abffffffff04a605b604000000000851006a656a630052f49a0300000000000252515a94e1050000000009abac65ab0052abab00fd8dd002000000000651535163526a2566852d", "ac5363", 0, -1718831517, "b0dc030661783dd9939e4bf1a6dfcba809da2017e1b315a6312e5942d714cf05");
+	}
+
+	// SIGHASH_NONE
+	#[test]
+	fn test_sighash_none() {
+		run_test_sighash("97be4f7702dc20b087a1fdd533c7de762a3f2867a8f439bddf0dcec9a374dfd0276f9c55cc0300000000cdfb1dbe6582499569127bda6ca4aaff02c132dc73e15dcd91d73da77e92a32a13d1a0ba0200000002ab51ffffffff048cfbe202000000000900516351515363ac535128ce0100000000076aac5365ab6aabc84e8302000000000863536a53ab6a6552f051230500000000066aac535153510848d813", "ac51", 0, 229541474, "e5da9a416ea883be1f8b8b2d178463633f19de3fa82ae25d44ffb531e35bdbc8");
+		run_test_sighash("cc4dda57047bd0ca6806243a6a4b108f7ced43d8042a1acaa28083c9160911cf47eab910c40200000007526a0000ab6a63e4154e581fcf52567836c9a455e8b41b162a78c85906ccc1c2b2b300b4c69caaaa2ba0230300000008ab5152ac5100ab65ffffffff69696b523ed4bd41ecd4d65b4af73c9cf77edf0e066138712a8e60a04614ea1c0300000004ab6a000016c9045c7df7836e05ac4b2e397e2dd72a5708f4a8bf6d2bc36adc5af3cacefcf074b8b403000000065352ac5252acffffffff01d7e380050000000000cf4e699a", "525163656351", 1, -776533694, "ff18c5bffd086e00917c2234f880034d24e7ea2d1e1933a28973d134ca9e35d2");
+	}
+
+	// Core returns uint256 one when SIGHASH_SINGLE input has no matching output, regardless of ANYONECANPAY
+	#[test]
+	fn test_sighash_single_without_output() {
+		let tx: Transaction = "0100000002a6b97044d03da79c005b20ea9c0e1a6d9dc12d9f7b91a5911c9030a439eed8f50000000000ffffffffa6b97044d03da79c005b20ea9c0e1a6d9dc12d9f7b91a5911c9030a439eed8f50100000000ffffffff0100f2052a010000001976a914404371705fa9bd789a2fcd52d2c580b65d35549d88ac00000000".into();
+		let signer: TransactionInputSigner = tx.into();
+		let script: Script = "76a914404371705fa9bd789a2fcd52d2c580b65d35549d88ac".into();
+		let one = H256::from_reversed_str("0000000000000000000000000000000000000000000000000000000000000001");
+
+		for &hash_type in &[0x03, 0x83] {
+			let sighash = Sighash::from_u32(SignatureVersion::Base, hash_type);
+			assert_eq!(signer.signature_hash_original(1, &script, hash_type, sighash), one);
+			assert!(signer.signature_hash_original(0, &script, hash_type, sighash) != one);
+		}
+	}
+
 	#[test]
 	fn test_sighash_forkid_from_u32() {
 		assert!(!Sighash::is_defined(SignatureVersion::Base, 0xFFFFFF82));
@@ -609,7 +1344,7 @@ mod tests {
 		let tx: Transaction = "0400008085202f8901a8c685478265f4c14dada651969c45a65e1aeb8cd6791f2f5bb6a1d9952104d9010000006b483045022100a61e5d557568c2ddc1d9b03a7173c6ce7c996c4daecab007ac8f34bee01e6b9702204d38fdc0bcf2728a69fde78462a10fb45a9baa27873e6a5fc45fb5c76764202a01210365ffea3efa3908918a8b8627724af852fc9b86d7375b103ab0543cf418bcaa7ffeffffff02005a6202000000001976a9148132712c3ff19f3a151234616777420a6d7ef22688ac8b959800000000001976a9145453e4698f02a38abdaa521cd1ff2dee6fac187188ac29b0040048b004000000000000000000000000".into();
 		let mut signer = TransactionInputSigner::from(tx);
 		signer.inputs[0].amount = 50000000;
-		signer.consensus_branch_id = 0x76b809bb;
+		signer.consensus_branch_id = ConsensusBranchId::SAPLING;
 
 		let sig_hash = Sighash::from_u32(SignatureVersion::Base, 1);
 		let hash = signer.signature_hash_overwintered(
@@ -637,7 +1372,7 @@ mod tests {
 		let tx: Transaction = "0400008085202f89012c07a03638d9cf4d2cc837784b3b06aa9a5c8b819f7cb0d373bf711108f4c0f2010000006b483045022100fceec7ffa2686377fa2e13d43aa1d8836c3b5ace5292dd2f65a75befec2660bd02205dc000c13a89975bf3fe85aa9c891fcdea6eb25bd5459ad204fe2946d22e49c3012102031d4256c4bc9f99ac88bf3dba21773132281f65f9bf23a59928bce08961e2f3ffffffff0240420f00000000001976a91405aab5342166f8594baf17a7d9bef5d56744332788ac7c288800000000001976a91405aab5342166f8594baf17a7d9bef5d56744332788ac00000000000000000000000000000000000000".into();
 		let mut signer = TransactionInputSigner::from(tx);
 		signer.inputs[0].amount = 9924260;
-		signer.consensus_branch_id = 0x76b809bb;
+		signer.consensus_branch_id = ConsensusBranchId::SAPLING;
 
 		let sig_hash = Sighash::from_u32(SignatureVersion::Base, 1);
 		let hash = signer.signature_hash_overwintered(
@@ -659,4 +1394,64 @@ mod tests {
 
 		assert_eq!(H256::from("047da0d9932545770fc570122c4451b53fadad219650008e5026162e957a46f9"), hash);
 	}
+
+	// https://github.com/zcash-hackworks/zcash-test-vectors/blob/master/zip_0243.py
+	fn check_zip243_vector(tx: &'static str, input_index: usize, sighashtype: u32, amount: u64, script_code: &'static str, expected: &'static str) {
+		let tx: Transaction = tx.into();
+		let mut signer = TransactionInputSigner::from(tx);
+		signer.inputs[input_index].amount = amount;
+		signer.consensus_branch_id = ConsensusBranchId::SAPLING;
+
+		let sighash = Sighash::from_u32(SignatureVersion::Base, sighashtype);
+		let hash = signer.signature_hash_overwintered(input_index, &Script::from(script_code), sighashtype, sighash);
+		assert_eq!(H256::from(expected), hash.unwrap());
+	}
+
+	#[test]
+	fn test_sapling_sig_hash_none() {
+		check_zip243_vector(
+			"0400008085202f89020bbe32a598c22adfb48cef72ba5d4287c0cefbacfd8ce195b4963c34a94bba7a175dae4b0465ac656353708915090f47a068e227433f9e49d3aa09e356d8d66d0c0121e91a3c4aa3f27fa1b63396e2b41d090063535300ac53ac514e97056802da071b970d4807000152a844550bdc2002000752526a65520052d7034302011b9a076620edc067ff0200000353e3b8a71face1c9f37745ed36883529304bfd5a390b37bc5a3445241f03f64a818820dfeddd75375159fbd21eca9872104f8d7b3c8c869703a1e7848a5c941e45a9c7943446d0dc9627cb31f80e7aa596d4821dc99a7d777cd57e194842a023471f0f6288a150647b2afe9df7cccf01f5cde5f04680bbfed87f6cf429fb27ad6babe791766611cf5bc20e48bef119259b9b8a0e39c3df28cb9582ea338601cdc481b32fb82adeebb3dade25d1a3df20c37e712506b5d996c49a9f0f30ddcb91fe9004e1e83294a6c9203d94e8dc2cbb449de4155032604e47997016b304fd437d8235045e255a19b743a0a9f2e336b44cae307bb3987bd3e4e777fbb34c0ab8cc3d67466c0a88dd4ccad18a07a8d1068df5b629e5718d0f6df5c957cf71bb00a5178f175caca944e635c5159f738e2402a2d21aa081e10e456afb00b9f62416c8b9c0f7228f510729e0be3f305313d77f7379dc2af24869c6c74ee4471498861d192f0ff0f508285dab6b6a36ccf7d12256cc76b95503720ac672d08268d2cf7773b6ba2a5f664847bf707f2fc10c98f2f006ec22ccb5a8c8b7c40c7c2d49a6639b9f2ce33c25c04bc461e744dfa536b00d94baddf4f4d14044c695a33881477df124f0fcf206a9fb2e65e304cdbf0c4d2390170c130ab849c2f22b5cdd3921640c8cf1976ae1010b0dfd9cb2543e45f99749cc4d61f2e8aabfe98bd905fa39951b33ea769c45ab9531c57209862ad12fd76ba4807e65417b6cd12fa8ec916f013ebb8706a96effeda06c4be24b04846392e9d1e6930eae01fa21fbd700583fb598b92c8f4eb8a61aa6235db60f2841cf3a1c6ab54c67066844711d091eb931a1bd6281aedf2a0e8fab18817202a9be06402ed9cc720c16bfe881e4df4255e87afb7fc62f38116bbe03cd8a3cb11a27d568414782f47b1a44c97c680467694bc9709d32916c97e8006cbb07ba0e4180a3738038c374c4cce8f32959afb25f303f5815c4533124acf9d18940e77522ac5dc4b9570aae8f47b7f57fd8767bea1a24ae7bed65b4afdc8f1278c30e2db98fd172730ac6bbed4f1127cd32b04a95b205526cfcb4c4e1cc955175b3e8de1f5d81b18669692350aaa1a1d797617582e54d7a5b57a683b32fb1098062dad7b0c2eb518f6862e83db25e3dbaf7aed504de932acb99d735992ce62bae9ef893ff6acc0ffcf8e3483e146b9d49dd8c7835f43a37dca0787e3ec9f6605223d5ba7ae0ab9025b73bc03f7fac36c009a56d4d95d1e81d3b3ebca7e54cc1a12d127b57c8138976e791013b015f06a624f521b6ee04ec980893c7e5e01a336203594094f82833d74427880084d35863c8e7ebb5c9eed98e72572ec40c79b26623b58022f489b0893d88be63f3f8c0d23249ebcde13db9312941c36c1d1cbcabac0c78cb3b1912db0dcbfe1893d9b51be4af1d000bac1ad0a3ae2ce1e73225fb114d05af4cefc06e875f074ffeae0cba7da3a516c173be1c513323e119f635e8209a074b216b7023fadc2d25949c90037e71e3e550726d210a2c688342e52440635e9cc14afe10102621a9c9accb782e9e4a5fa87f0a956f5b85509960285c22627c59483a5a4c28cce4b156e551406a7ee8355656a21e43e38ce129fdadb759eddfa08f00fc8e567cef93c6792d01df05e6d580f4d5d48df042451a33590d3e8cf49b2627218f0c292fa66ada945fa55bb23548e33a83a562957a3149a993cc472362298736a8b778d97ce423013d64b32cd172efa551bf7f368f04bdaec6091a3004a757598b801dcf675cb83e43a53ae8b254d333bcda20d4817d3477abfba25bb83df5949c126f149b1d99341e4e6f9120f4d41e629185002c72c012c414d2382a6d47c7b3deaba770c400ca96b2814f6b26c3ef17429f1a98c85d83db20efad48be8996fb1bff591efff360fe1199056c56e5feec61a7b8b9f699d6012c2849232f329fef95c7af370098ffe4918e0ca1df47f275867b739e0a514d3209325e217045927b479c1ce2e5d54f25488cad1513e3f44a21266cfd841633327dee6cf810fbf7393e317d9e53d1be1d5ae7839b66b943b9ed18f2c530e975422332c3439cce49a29f2a336a4851263c5e9bd13d731109e844b7f8c392a5c1dcaa2ae5f50ff63fab9765e016702c35a67cd7364d3fab552fb349e35c15c50250453fd18f7b855992632e2c76c0fbf1ef963ea80e3223de3277bc559251725829ec03f213ba8955cab2822ff21a9b0a4904d668fcd77224bde3dd01f6ffc4828f6b64230b35c6a049873494276ea1d7ed5e92cb4f90ba83a9e49601b194042f2900d99d312d7b70508cf176066d154dbe96ef9d4367e4c840e4a17b5e5122e8ebe2158a3c5f4cbae21ea3fa1ae6c25a9462ebcbb0fd5f14554bc97747c33e34da90c816d8d0d50bfe37618c5812891484fa259322c15092d4155d8696d6f12f24fd364496b3be0871ca3dd9625348a614b59bde45885649bae36de34def8fcec85343475d976ae1e9b27829ce2ac5efd0b399a8b448be6504294ee6b3c1c6a5342d7c01ae9d8ad3070c2b1a91573af5e0c5e4cbbf4acdc6b54c9272200d9970250c17c1036f06085c41858ed3a0c48150bc697e4a695fef335f7ad07e1a46dc767ff822db70e6669080b9816b2232c81a4c66cc586abfe1eaa8ca6cf41fc30eb8dc57c37a3c39c59c94232df9d388dbfa35c2cd5c75f328e9fea78f65568f2bb934c82c4142da69d12ca7de9a7df706400ec79878d868e17e8f71ea31495a8bae7bdc2e48b5118771c2fca078cca1fce0d7ef0af3478cf36f69e85a41dd29b4294a65d3e055ff718dd9dc8c75e7e5b2efe442637371b7c48f6ee99e3ea38a4b0f2f67fc2b908cda657eae754e037e262e9a9f9bd7ec4267ed8e96930e1084783c37d6f9dd15fd29f4cc477e66f130d630430dcc0104899b4f9f46eb090ef7fc90b479abf61f93955ee00e6a1848f1ab14ad334f2b68035808cdf1bb9e9d9a816baf728a955b960b7701fa626687dc3c9cba646337b53e29816e9482ddf5578a8768aae477fce410ac2d5de6095861c111d7feb3e6bb4fbb5a54955495972798350a253f05f66c2ecfcbc0ed43f5ec2e6d8dba15a51254d97b1821107c07dd9a16ef8406f943e282b95d4b362530c913d6ba421df6027de5af1e4745d5868106954be6c1962780a2941072e95131b1679df0637625042c37d48ffb152e5ebc185c8a2b7d4385f1c95af937df78dfd8757fab434968b0b57c66574468f160b447ac8221e5060676a842a1c6b7172dd3340f764070ab1fe091c5c74c95a5dc043390723a4c127da14cdde1dc2675a62340b3e6afd0522a31de26e7d1ec3a9c8a091ffdc75b7ecfdc7c12995a5e37ce3488bd29f8629d68f696492448dd526697476dc061346ebe3f677217ff9c60efce943af28dfd3f9e59692598a6047c23c4c01400f1ab5730eac0ae8d5843d5051c376240172af218d7a1ecfe65b4f75100638983c14de4974755dade8018c9b8f4543fb095961513e67c61dbc59c607f9b51f8d09bdcad28bcfb9e5d2744ea8848b2623ac07f8ef61a81a35910b8a1baf39a919a7b60bc604d63185f759221d847cc54a22765a4c33475b5791e9af3271fc8d9350667090d8184ec50522d804f23c4fb44ffa481bc92ae408d1b9f2b131904f9705c59e2f4bde7a3b2c085d93fd2abc5e14d163001a12f51938d021afa92239b873dc6c357eaa8af4ee6d00540657fe32914103b5d98f68bd3e2b5359f08ccd88d0c811e4c31fbb49f3a90bbd05dce62f344e7077593159ae35050b04c9e6b86bc432dc8b048c73c0018ca5b69411297732a4e1aa99a928c71e7a24fd277856aa42501e51b012aea9446a2104e93f815a0b3a29b458314f3d8be2b9823d342f46213e942a7e19a46e970b5c506708430317b1bb3b35df68ae33a4926a03e6bfeb5510416fcbb0524c9ca5074156cc5a5d6fe1c995edc60a2f550411aa41e3da3bdcf64bcf04a0510571b936d47e55cec0330008dfe73563404f047d7f3a8a3d7743bc554955210f1eb0d08599ea77d5f974d87176d37d98b9c0ad440407209ed6a9f08464d565593e1a63b938536b49244e97d",
+			1, 2, 652655344020909, "",
+			"bbe6d84f57c56b29b914c694baaccb891297e961de3eb46c68e3c89c47b1a1db",
+		);
+	}
+
+	#[test]
+	fn test_sapling_sig_hash_single() {
+		check_zip243_vector(
+			"0400008085202f8902881ddf4f95789734fcc165ee1e044085b6e7a177508c29da0ce77ded750898de89d260d302635244cc75e19834525fba56900de99385442eb9ec9a5f182b875d70b5b153790a1ee79c0e86783795fa066a0063000063fc922992008364fffc7c00c00e0f99de474289060001392197d623f7ebda07cd0058d9a1d172043c2fc94f14193e270eefe83c3f01b265054c3f6a60e2b76e1756088b87da839f772cbd0f275c9228385a04bb50ec3cfa9ee2e15b153d4c85fe50b6006258e9e8c25299c09df8b455466ba25f7e4c8fe7e250edba60695da47faafdd626ba7e9d4896e4b8a8a1a1dc215b0a25eeb04ed1befb5b3138c69fe528e7291123fcdf8a366c257d329538250a0cb7f54e1c016ce1c623b2e276a52c6e41241b2ac509373c188140e8365c94f58c63f27ff8e6e869a985afb61e97d8ceec2a7824a5c107b0baa4d6e79a6c71872a7b3b17ef918ae4e25f98a72db53ba7f26e408bd4d1f9e3474ddca5833ff5ff8d11b1bf1e2bb4d1968a823888bd91a21a76796bca4453e2892d1b6e1363ed107a9e7ed93fb1da994a9d4e7ec92e29a687f218d28a7646069bcacb4da7badf4eb1331aab212b92c6ea6476a0a09d6bd2e0f76fa87379abfd17582f3eb23b86c9669f86737048d771849b8f70bd8799013be0bfbd7b57bea1a49a4a39147912d7baf68004d415026bbc6f69325f4ff78728775a67aadd722c73311dba5c2cf14ccbd57eabed71920ff9623289bb76051c73a206a3c2b40cac01d5f11fa64c1b7ded70ea17429c6621ca9b923c4811850c3df4013d17bdc5101c8d80b3a04a4cc23d13fe3184e8b1ade63517593f7be66948c0857aece01bc272295e60b1806946c93bc8c7d2a2edc37fa37c477a69a90b59b4c6912e913a57efa9d54c7e80d5ac8a4294d0fd31a402e4b47ec7bf0331b2c9a48f44573fc7e7f102ed48c97508cbe43065a9e99fb4ce1362bb8a76b1419d95030e9c24eeba9ff8cfda957b17098cdf8c9a919e47a13a5b3346e37e827cc83b3c9aabf2d0ba17ff3d9e0d223c41c88ec2391c76622d7bd62117331e21ffec3272c1e1423982c6b63aec8dbf5ca2dd15810f5342af49fad279b7ca23ded30824799630dedc6db724bce1113621c4a6479dd555f485217cb567139eeadd7ee8dc5b2662f1066a7c60dee0093c9246de7a05e8b0f6bef0033dde2e87cba68d236ef66a23d55e7bd28d02599cca0df7a900637bb3464d622b7c9c9c8c91468974880164def799908a11a591abb3c8d8bd9c12b1f6f3cdc9ed8e16e57d2334b217797df19052feebed6cdb99ac44ea13afeac4377d0fa37ef516ddaceab0d9395bd440460e28b5f57a6efd37d268a864cb5ca34be287e1048efc1e40cdf4fcfc024cf182038b9d80ed1c07636200c819a7e7c240c3c4f7a91732e3ff13e2a56a64666610cad9841c1a934fe933b0f19fb71d061c58f21a4981ce3e68c5023903608de58302c6c8def4e5619ec0d91cf9354475972bfe0d7575602aaf0e9e885c6baf9d567b1fcb63190cb792f1d871611adb4f3d1ed328026918e28d2fd45ab9d370e7292ed754ce29fb787fd5d09e6d47cbc80021abf7d2efebdbe0add870168f51dcc40957a4a3c8e192601383b7684136dca282623f31ba7ae5366b453c6a26f68a14db6559bcb10237379a27a9502ff9d64a338320751530f1f892a6d46f50311b5e18f0336fc477215666e188933c6939989f6e6a3adba22996aae6a0fe1bddcbe1496d968de093df44a3300f7515a12c9d82226d6b4d62c46a213d5f0107106fd2a22d3b598613db491f70ccb1f03b8659669ed74434e43b771f22780710fbd8f2f20e9897df5cc23548779c6c0830839d231c3ff9ac54407dfdfcc59014bf67d9685706a5622e38f7a933c34afbb6aa8cdfd93bd2ec91ad37904ce13b8ab8ef772366fad3c3ebee8f2611ee7b6c2af7e653efbec4dc4cbf13acf37e399e2b0b05b61cb7e17b15627b62962e2100b195fefe94bc484e88139700737de1a5ec7d9cc85d533b61ecad8653cedbb771f675af61e4c6f7efaacc9f7e424c16715b0a98c446059a271a27bd569d1b5dbfae8f53898524cae87059ff34fb2a533226bd29a0ba6f8d0836fd0a4c0d609a72e10539a44f8c39f6279be396e41ca9f29a28ce9fa0dd51a302e770e1e3db706a34cb904ef08d9c82c55bc728c955b120bb2ec373fcffff3c46d603ab387896d49cd21b2f77ecfbbb02a5e153b171afed986c15da6f2d4cf745d1995f5136e1b3e68a67a8996fe765616d8aa11bcd9f8b591db87efcdaaffd41003ec729360542620854fb04b80cb861a636a4717d666894c32f1f2bf2247cc415de1d0c4e712b958842d6a4b276dea5db88423f2b4c664b1d2b1877baf33747343614e5ebe9b7e12ed0153f9ca7458e4da497639dff1352ff0efae01d140321c28dd0b67b069890f6130f8246ab8544717532d3a5f63639a99d7f8e9831c64851b7ef6893b3c9740f9844d18a613b5f9a6ab4bd6e6a93e8e4bea5575d2cb4330c0af8558319a909a5988a992e406343dd1c742d64cd4a17a2f3795e8db4d30ccdf4415655eda7b437e3397323896b11b1bed72d63e310aa49671d85534f6dbc181febb5bdc08ac0d123829d108cd269f3b0a396f4241e7dda72f54862bedef01c12e3c6cfdf75f676c2ddef91af7f8a8a769c25e177cd430bede74b57690519a98db1fb5c361280f7540ac827a91b2d08752decfb7156fcdb617578b053eee41f66a60e045c3a569f3f7edb7631682fde9ef91ea8811fc2c78f646af6b4710edbb8bf2328bd3273a2cb72ffcca7c217b827192dd2ea929e976d131c9d202ec506a35d93ab216f64bd73fe5d8abae4571f85beb84a7f93a3de37a451f308f7de6ccd1a6eefef24699f2158d1261fe25182b502da3e74611a6116fc3064fa723c5a81adc0a32f1ed6299157d1c11c0ad99041894696301d5b3f1bf43205d7dccfa68bbb4a1f5e242b3e690bfc97b94366a343f5dd16df67b2ed2be21c747118872b462ee20c778ced856fa980403fb24b786137d0ef0278539b00ce6e23c07ef2a07cb24c51c5b485e454edf661db4b931ab8cb494eb394fd13c1b32085f27b204a4b87ee6c806345d7584cb161006ad9848a24a22a5771e3a2ab65463f553d52cd535ef10bdd40d8877372a532e3731b0ee90c04e8e43747cc3eb96bb879bd94d7012af46a93ba177037f062744d3fdfccd36aabe0f8ccca19dcf7841b1ee2f4feb1800e75441c51e95cce94ceeecd8587fbf574308dd763631b7335783091f4c8b3c8fb3cd93970cef0eda4ca08447568239c02fe8f675e15c49b5121b100cc19fcc2b2913df74f758f70bd6eeb7339516e5f1eff9700f8ee130e5c84ced7b1ced66be9a05596be8e55f6d9fdf7cf0fa62290ec670b6bdd6738bb5cfb341ef5ffb42bc2abc508ff231248f2c2dc15770d33722b9c9dae",
+			0, 3, 391892287957268, "ac65",
+			"6a3b2bcc155789a27439aa275ca99ec648ddd588e82efae4ac46ba3fd0e3bba0",
+		);
+	}
+
+	// ZIP243 has no ANYONECANPAY vectors, these reuse the transactions above with the
+	// hash type changed; the expected hashes come from a port of zip_0243.py that
+	// reproduces all of the upstream vectors
+	#[test]
+	fn test_sapling_sig_hash_all_anyonecanpay() {
+		check_zip243_vector(
+			"0400008085202f89020bbe32a598c22adfb48cef72ba5d4287c0cefbacfd8ce195b4963c34a94bba7a175dae4b0465ac656353708915090f47a068e227433f9e49d3aa09e356d8d66d0c0121e91a3c4aa3f27fa1b63396e2b41d090063535300ac53ac514e97056802da071b970d4807000152a844550bdc2002000752526a65520052d7034302011b9a076620edc067ff0200000353e3b8a71face1c9f37745ed36883529304bfd5a390b37bc5a3445241f03f64a818820dfeddd75375159fbd21eca9872104f8d7b3c8c869703a1e7848a5c941e45a9c7943446d0dc9627cb31f80e7aa596d4821dc99a7d777cd57e194842a023471f0f6288a150647b2afe9df7cccf01f5cde5f04680bbfed87f6cf429fb27ad6babe791766611cf5bc20e48bef119259b9b8a0e39c3df28cb9582ea338601cdc481b32fb82adeebb3dade25d1a3df20c37e712506b5d996c49a9f0f30ddcb91fe9004e1e83294a6c9203d94e8dc2cbb449de4155032604e47997016b304fd437d8235045e255a19b743a0a9f2e336b44cae307bb3987bd3e4e777fbb34c0ab8cc3d67466c0a88dd4ccad18a07a8d1068df5b629e5718d0f6df5c957cf71bb00a5178f175caca944e635c5159f738e2402a2d21aa081e10e456afb00b9f62416c8b9c0f7228f510729e0be3f305313d77f7379dc2af24869c6c74ee4471498861d192f0ff0f508285dab6b6a36ccf7d12256cc76b95503720ac672d08268d2cf7773b6ba2a5f664847bf707f2fc10c98f2f006ec22ccb5a8c8b7c40c7c2d49a6639b9f2ce33c25c04bc461e744dfa536b00d94baddf4f4d14044c695a33881477df124f0fcf206a9fb2e65e304cdbf0c4d2390170c130ab849c2f22b5cdd3921640c8cf1976ae1010b0dfd9cb2543e45f99749cc4d61f2e8aabfe98bd905fa39951b33ea769c45ab9531c57209862ad12fd76ba4807e65417b6cd12fa8ec916f013ebb8706a96effeda06c4be24b04846392e9d1e6930eae01fa21fbd700583fb598b92c8f4eb8a61aa6235db60f2841cf3a1c6ab54c67066844711d091eb931a1bd6281aedf2a0e8fab18817202a9be06402ed9cc720c16bfe881e4df4255e87afb7fc62f38116bbe03cd8a3cb11a27d568414782f47b1a44c97c680467694bc9709d32916c97e8006cbb07ba0e4180a3738038c374c4cce8f32959afb25f303f5815c4533124acf9d18940e77522ac5dc4b9570aae8f47b7f57fd8767bea1a24ae7bed65b4afdc8f1278c30e2db98fd172730ac6bbed4f1127cd32b04a95b205526cfcb4c4e1cc955175b3e8de1f5d81b18669692350aaa1a1d797617582e54d7a5b57a683b32fb1098062dad7b0c2eb518f6862e83db25e3dbaf7aed504de932acb99d735992ce62bae9ef893ff6acc0ffcf8e3483e146b9d49dd8c7835f43a37dca0787e3ec9f6605223d5ba7ae0ab9025b73bc03f7fac36c009a56d4d95d1e81d3b3ebca7e54cc1a12d127b57c8138976e791013b015f06a624f521b6ee04ec980893c7e5e01a336203594094f82833d74427880084d35863c8e7ebb5c9eed98e72572ec40c79b26623b58022f489b0893d88be63f3f8c0d23249ebcde13db9312941c36c1d1cbcabac0c78cb3b1912db0dcbfe1893d9b51be4af1d000bac1ad0a3ae2ce1e73225fb114d05af4cefc06e875f074ffeae0cba7da3a516c173be1c513323e119f635e8209a074b216b7023fadc2d25949c90037e71e3e550726d210a2c688342e52440635e9cc14afe10102621a9c9accb782e9e4a5fa87f0a956f5b85509960285c22627c59483a5a4c28cce4b156e551406a7ee8355656a21e43e38ce129fdadb759eddfa08f00fc8e567cef93c6792d01df05e6d580f4d5d48df042451a33590d3e8cf49b2627218f0c292fa66ada945fa55bb23548e33a83a562957a3149a993cc472362298736a8b778d97ce423013d64b32cd172efa551bf7f368f04bdaec6091a3004a757598b801dcf675cb83e43a53ae8b254d333bcda20d4817d3477abfba25bb83df5949c126f149b1d99341e4e6f9120f4d41e629185002c72c012c414d2382a6d47c7b3deaba770c400ca96b2814f6b26c3ef17429f1a98c85d83db20efad48be8996fb1bff591efff360fe1199056c56e5feec61a7b8b9f699d6012c2849232f329fef95c7af370098ffe4918e0ca1df47f275867b739e0a514d3209325e217045927b479c1ce2e5d54f25488cad1513e3f44a21266cfd841633327dee6cf810fbf7393e317d9e53d1be1d5ae7839b66b943b9ed18f2c530e975422332c3439cce49a29f2a336a4851263c5e9bd13d731109e844b7f8c392a5c1dcaa2ae5f50ff63fab9765e016702c35a67cd7364d3fab552fb349e35c15c50250453fd18f7b855992632e2c76c0fbf1ef963ea80e3223de3277bc559251725829ec03f213ba8955cab2822ff21a9b0a4904d668fcd77224bde3dd01f6ffc4828f6b64230b35c6a049873494276ea1d7ed5e92cb4f90ba83a9e49601b194042f2900d99d312d7b70508cf176066d154dbe96ef9d4367e4c840e4a17b5e5122e8ebe2158a3c5f4cbae21ea3fa1ae6c25a9462ebcbb0fd5f14554bc97747c33e34da90c816d8d0d50bfe37618c5812891484fa259322c15092d4155d8696d6f12f24fd364496b3be0871ca3dd9625348a614b59bde45885649bae36de34def8fcec85343475d976ae1e9b27829ce2ac5efd0b399a8b448be6504294ee6b3c1c6a5342d7c01ae9d8ad3070c2b1a91573af5e0c5e4cbbf4acdc6b54c9272200d9970250c17c1036f06085c41858ed3a0c48150bc697e4a695fef335f7ad07e1a46dc767ff822db70e6669080b9816b2232c81a4c66cc586abfe1eaa8ca6cf41fc30eb8dc57c37a3c39c59c94232df9d388dbfa35c2cd5c75f328e9fea78f65568f2bb934c82c4142da69d12ca7de9a7df706400ec79878d868e17e8f71ea31495a8bae7bdc2e48b5118771c2fca078cca1fce0d7ef0af3478cf36f69e85a41dd29b4294a65d3e055ff718dd9dc8c75e7e5b2efe442637371b7c48f6ee99e3ea38a4b0f2f67fc2b908cda657eae754e037e262e9a9f9bd7ec4267ed8e96930e1084783c37d6f9dd15fd29f4cc477e66f130d630430dcc0104899b4f9f46eb090ef7fc90b479abf61f93955ee00e6a1848f1ab14ad334f2b68035808cdf1bb9e9d9a816baf728a955b960b7701fa626687dc3c9cba646337b53e29816e9482ddf5578a8768aae477fce410ac2d5de6095861c111d7feb3e6bb4fbb5a54955495972798350a253f05f66c2ecfcbc0ed43f5ec2e6d8dba15a51254d97b1821107c07dd9a16ef8406f943e282b95d4b362530c913d6ba421df6027de5af1e4745d5868106954be6c1962780a2941072e95131b1679df0637625042c37d48ffb152e5ebc185c8a2b7d4385f1c95af937df78dfd8757fab434968b0b57c66574468f160b447ac8221e5060676a842a1c6b7172dd3340f764070ab1fe091c5c74c95a5dc043390723a4c127da14cdde1dc2675a62340b3e6afd0522a31de26e7d1ec3a9c8a091ffdc75b7ecfdc7c12995a5e37ce3488bd29f8629d68f696492448dd526697476dc061346ebe3f677217ff9c60efce943af28dfd3f9e59692598a6047c23c4c01400f1ab5730eac0ae8d5843d5051c376240172af218d7a1ecfe65b4f75100638983c14de4974755dade8018c9b8f4543fb095961513e67c61dbc59c607f9b51f8d09bdcad28bcfb9e5d2744ea8848b2623ac07f8ef61a81a35910b8a1baf39a919a7b60bc604d63185f759221d847cc54a22765a4c33475b5791e9af3271fc8d9350667090d8184ec50522d804f23c4fb44ffa481bc92ae408d1b9f2b131904f9705c59e2f4bde7a3b2c085d93fd2abc5e14d163001a12f51938d021afa92239b873dc6c357eaa8af4ee6d00540657fe32914103b5d98f68bd3e2b5359f08ccd88d0c811e4c31fbb49f3a90bbd05dce62f344e7077593159ae35050b04c9e6b86bc432dc8b048c73c0018ca5b69411297732a4e1aa99a928c71e7a24fd277856aa42501e51b012aea9446a2104e93f815a0b3a29b458314f3d8be2b9823d342f46213e942a7e19a46e970b5c506708430317b1bb3b35df68ae33a4926a03e6bfeb5510416fcbb0524c9ca5074156cc5a5d6fe1c995edc60a2f550411aa41e3da3bdcf64bcf04a0510571b936d47e55cec0330008dfe73563404f047d7f3a8a3d7743bc554955210f1eb0d08599ea77d5f974d87176d37d98b9c0ad440407209ed6a9f08464d565593e1a63b938536b49244e97d",
+			1, 0x81, 652655344020909, "",
+			"38f65416c17283119e49b305e752bc4283e811509ca80c5d1695df825e6e2224",
+		);
+	}
+
+	#[test]
+	fn test_sapling_sig_hash_none_anyonecanpay() {
+		check_zip243_vector(
+			"0400008085202f89020bbe32a598c22adfb48cef72ba5d4287c0cefbacfd8ce195b4963c34a94bba7a175dae4b0465ac656353708915090f47a068e227433f9e49d3aa09e356d8d66d0c0121e91a3c4aa3f27fa1b63396e2b41d090063535300ac53ac514e97056802da071b970d4807000152a844550bdc2002000752526a65520052d7034302011b9a076620edc067ff0200000353e3b8a71face1c9f37745ed36883529304bfd5a390b37bc5a3445241f03f64a818820dfeddd75375159fbd21eca9872104f8d7b3c8c869703a1e7848a5c941e45a9c7943446d0dc9627cb31f80e7aa596d4821dc99a7d777cd57e194842a023471f0f6288a150647b2afe9df7cccf01f5cde5f04680bbfed87f6cf429fb27ad6babe791766611cf5bc20e48bef119259b9b8a0e39c3df28cb9582ea338601cdc481b32fb82adeebb3dade25d1a3df20c37e712506b5d996c49a9f0f30ddcb91fe9004e1e83294a6c9203d94e8dc2cbb449de4155032604e47997016b304fd437d8235045e255a19b743a0a9f2e336b44cae307bb3987bd3e4e777fbb34c0ab8cc3d67466c0a88dd4ccad18a07a8d1068df5b629e5718d0f6df5c957cf71bb00a5178f175caca944e635c5159f738e2402a2d21aa081e10e456afb00b9f62416c8b9c0f7228f510729e0be3f305313d77f7379dc2af24869c6c74ee4471498861d192f0ff0f508285dab6b6a36ccf7d12256cc76b95503720ac672d08268d2cf7773b6ba2a5f664847bf707f2fc10c98f2f006ec22ccb5a8c8b7c40c7c2d49a6639b9f2ce33c25c04bc461e744dfa536b00d94baddf4f4d14044c695a33881477df124f0fcf206a9fb2e65e304cdbf0c4d2390170c130ab849c2f22b5cdd3921640c8cf1976ae1010b0dfd9cb2543e45f99749cc4d61f2e8aabfe98bd905fa39951b33ea769c45ab9531c57209862ad12fd76ba4807e65417b6cd12fa8ec916f013ebb8706a96effeda06c4be24b04846392e9d1e6930eae01fa21fbd700583fb598b92c8f4eb8a61aa6235db60f2841cf3a1c6ab54c67066844711d091eb931a1bd6281aedf2a0e8fab18817202a9be06402ed9cc720c16bfe881e4df4255e87afb7fc62f38116bbe03cd8a3cb11a27d568414782f47b1a44c97c680467694bc9709d32916c97e8006cbb07ba0e4180a3738038c374c4cce8f32959afb25f303f5815c4533124acf9d18940e77522ac5dc4b9570aae8f47b7f57fd8767bea1a24ae7bed65b4afdc8f1278c30e2db98fd172730ac6bbed4f1127cd32b04a95b205526cfcb4c4e1cc955175b3e8de1f5d81b18669692350aaa1a1d797617582e54d7a5b57a683b32fb1098062dad7b0c2eb518f6862e83db25e3dbaf7aed504de932acb99d735992ce62bae9ef893ff6acc0ffcf8e3483e146b9d49dd8c7835f43a37dca0787e3ec9f6605223d5ba7ae0ab9025b73bc03f7fac36c009a56d4d95d1e81d3b3ebca7e54cc1a12d127b57c8138976e791013b015f06a624f521b6ee04ec980893c7e5e01a336203594094f82833d74427880084d35863c8e7ebb5c9eed98e72572ec40c79b26623b58022f489b0893d88be63f3f8c0d23249ebcde13db9312941c36c1d1cbcabac0c78cb3b1912db0dcbfe1893d9b51be4af1d000bac1ad0a3ae2ce1e73225fb114d05af4cefc06e875f074ffeae0cba7da3a516c173be1c513323e119f635e8209a074b216b7023fadc2d25949c90037e71e3e550726d210a2c688342e52440635e9cc14afe10102621a9c9accb782e9e4a5fa87f0a956f5b85509960285c22627c59483a5a4c28cce4b156e551406a7ee8355656a21e43e38ce129fdadb759eddfa08f00fc8e567cef93c6792d01df05e6d580f4d5d48df042451a33590d3e8cf49b2627218f0c292fa66ada945fa55bb23548e33a83a562957a3149a993cc472362298736a8b778d97ce423013d64b32cd172efa551bf7f368f04bdaec6091a3004a757598b801dcf675cb83e43a53ae8b254d333bcda20d4817d3477abfba25bb83df5949c126f149b1d99341e4e6f9120f4d41e629185002c72c012c414d2382a6d47c7b3deaba770c400ca96b2814f6b26c3ef17429f1a98c85d83db20efad48be8996fb1bff591efff360fe1199056c56e5feec61a7b8b9f699d6012c2849232f329fef95c7af370098ffe4918e0ca1df47f275867b739e0a514d3209325e217045927b479c1ce2e5d54f25488cad1513e3f44a21266cfd841633327dee6cf810fbf7393e317d9e53d1be1d5ae7839b66b943b9ed18f2c530e975422332c3439cce49a29f2a336a4851263c5e9bd13d731109e844b7f8c392a5c1dcaa2ae5f50ff63fab9765e016702c35a67cd7364d3fab552fb349e35c15c50250453fd18f7b855992632e2c76c0fbf1ef963ea80e3223de3277bc559251725829ec03f213ba8955cab2822ff21a9b0a4904d668fcd77224bde3dd01f6ffc4828f6b64230b35c6a049873494276ea1d7ed5e92cb4f90ba83a9e49601b194042f2900d99d312d7b70508cf176066d154dbe96ef9d4367e4c840e4a17b5e5122e8ebe2158a3c5f4cbae21ea3fa1ae6c25a9462ebcbb0fd5f14554bc97747c33e34da90c816d8d0d50bfe37618c5812891484fa259322c15092d4155d8696d6f12f24fd364496b3be0871ca3dd9625348a614b59bde45885649bae36de34def8fcec85343475d976ae1e9b27829ce2ac5efd0b399a8b448be6504294ee6b3c1c6a5342d7c01ae9d8ad3070c2b1a91573af5e0c5e4cbbf4acdc6b54c9272200d9970250c17c1036f06085c41858ed3a0c48150bc697e4a695fef335f7ad07e1a46dc767ff822db70e6669080b9816b2232c81a4c66cc586abfe1eaa8ca6cf41fc30eb8dc57c37a3c39c59c94232df9d388dbfa35c2cd5c75f328e9fea78f65568f2bb934c82c4142da69d12ca7de9a7df706400ec79878d868e17e8f71ea31495a8bae7bdc2e48b5118771c2fca078cca1fce0d7ef0af3478cf36f69e85a41dd29b4294a65d3e055ff718dd9dc8c75e7e5b2efe442637371b7c48f6ee99e3ea38a4b0f2f67fc2b908cda657eae754e037e262e9a9f9bd7ec4267ed8e96930e1084783c37d6f9dd15fd29f4cc477e66f130d630430dcc0104899b4f9f46eb090ef7fc90b479abf61f93955ee00e6a1848f1ab14ad334f2b68035808cdf1bb9e9d9a816baf728a955b960b7701fa626687dc3c9cba646337b53e29816e9482ddf5578a8768aae477fce410ac2d5de6095861c111d7feb3e6bb4fbb5a54955495972798350a253f05f66c2ecfcbc0ed43f5ec2e6d8dba15a51254d97b1821107c07dd9a16ef8406f943e282b95d4b362530c913d6ba421df6027de5af1e4745d5868106954be6c1962780a2941072e95131b1679df0637625042c37d48ffb152e5ebc185c8a2b7d4385f1c95af937df78dfd8757fab434968b0b57c66574468f160b447ac8221e5060676a842a1c6b7172dd3340f764070ab1fe091c5c74c95a5dc043390723a4c127da14cdde1dc2675a62340b3e6afd0522a31de26e7d1ec3a9c8a091ffdc75b7ecfdc7c12995a5e37ce3488bd29f8629d68f696492448dd526697476dc061346ebe3f677217ff9c60efce943af28dfd3f9e59692598a6047c23c4c01400f1ab5730eac0ae8d5843d5051c376240172af218d7a1ecfe65b4f75100638983c14de4974755dade8018c9b8f4543fb095961513e67c61dbc59c607f9b51f8d09bdcad28bcfb9e5d2744ea8848b2623ac07f8ef61a81a35910b8a1baf39a919a7b60bc604d63185f759221d847cc54a22765a4c33475b5791e9af3271fc8d9350667090d8184ec50522d804f23c4fb44ffa481bc92ae408d1b9f2b131904f9705c59e2f4bde7a3b2c085d93fd2abc5e14d163001a12f51938d021afa92239b873dc6c357eaa8af4ee6d00540657fe32914103b5d98f68bd3e2b5359f08ccd88d0c811e4c31fbb49f3a90bbd05dce62f344e7077593159ae35050b04c9e6b86bc432dc8b048c73c0018ca5b69411297732a4e1aa99a928c71e7a24fd277856aa42501e51b012aea9446a2104e93f815a0b3a29b458314f3d8be2b9823d342f46213e942a7e19a46e970b5c506708430317b1bb3b35df68ae33a4926a03e6bfeb5510416fcbb0524c9ca5074156cc5a5d6fe1c995edc60a2f550411aa41e3da3bdcf64bcf04a0510571b936d47e55cec0330008dfe73563404f047d7f3a8a3d7743bc554955210f1eb0d08599ea77d5f974d87176d37d98b9c0ad440407209ed6a9f08464d565593e1a63b938536b49244e97d",
+			1, 0x82, 652655344020909, "",
+			"170b288abbd8709f7e3958b85920742a3c66af16ab9b2a8c58258ed5f9a5e5f2",
+		);
+	}
+
+	#[test]
+	fn test_sapling_sig_hash_single_anyonecanpay() {
+		check_zip243_vector(
+			"0400008085202f8902881ddf4f95789734fcc165ee1e044085b6e7a177508c29da0ce77ded750898de89d260d302635244cc75e19834525fba56900de99385442eb9ec9a5f182b875d70b5b153790a1ee79c0e86783795fa066a0063000063fc922992008364fffc7c00c00e0f99de474289060001392197d623f7ebda07cd0058d9a1d172043c2fc94f14193e270eefe83c3f01b265054c3f6a60e2b76e1756088b87da839f772cbd0f275c9228385a04bb50ec3cfa9ee2e15b153d4c85fe50b6006258e9e8c25299c09df8b455466ba25f7e4c8fe7e250edba60695da47faafdd626ba7e9d4896e4b8a8a1a1dc215b0a25eeb04ed1befb5b3138c69fe528e7291123fcdf8a366c257d329538250a0cb7f54e1c016ce1c623b2e276a52c6e41241b2ac509373c188140e8365c94f58c63f27ff8e6e869a985afb61e97d8ceec2a7824a5c107b0baa4d6e79a6c71872a7b3b17ef918ae4e25f98a72db53ba7f26e408bd4d1f9e3474ddca5833ff5ff8d11b1bf1e2bb4d1968a823888bd91a21a76796bca4453e2892d1b6e1363ed107a9e7ed93fb1da994a9d4e7ec92e29a687f218d28a7646069bcacb4da7badf4eb1331aab212b92c6ea6476a0a09d6bd2e0f76fa87379abfd17582f3eb23b86c9669f86737048d771849b8f70bd8799013be0bfbd7b57bea1a49a4a39147912d7baf68004d415026bbc6f69325f4ff78728775a67aadd722c73311dba5c2cf14ccbd57eabed71920ff9623289bb76051c73a206a3c2b40cac01d5f11fa64c1b7ded70ea17429c6621ca9b923c4811850c3df4013d17bdc5101c8d80b3a04a4cc23d13fe3184e8b1ade63517593f7be66948c0857aece01bc272295e60b1806946c93bc8c7d2a2edc37fa37c477a69a90b59b4c6912e913a57efa9d54c7e80d5ac8a4294d0fd31a402e4b47ec7bf0331b2c9a48f44573fc7e7f102ed48c97508cbe43065a9e99fb4ce1362bb8a76b1419d95030e9c24eeba9ff8cfda957b17098cdf8c9a919e47a13a5b3346e37e827cc83b3c9aabf2d0ba17ff3d9e0d223c41c88ec2391c76622d7bd62117331e21ffec3272c1e1423982c6b63aec8dbf5ca2dd15810f5342af49fad279b7ca23ded30824799630dedc6db724bce1113621c4a6479dd555f485217cb567139eeadd7ee8dc5b2662f1066a7c60dee0093c9246de7a05e8b0f6bef0033dde2e87cba68d236ef66a23d55e7bd28d02599cca0df7a900637bb3464d622b7c9c9c8c91468974880164def799908a11a591abb3c8d8bd9c12b1f6f3cdc9ed8e16e57d2334b217797df19052feebed6cdb99ac44ea13afeac4377d0fa37ef516ddaceab0d9395bd440460e28b5f57a6efd37d268a864cb5ca34be287e1048efc1e40cdf4fcfc024cf182038b9d80ed1c07636200c819a7e7c240c3c4f7a91732e3ff13e2a56a64666610cad9841c1a934fe933b0f19fb71d061c58f21a4981ce3e68c5023903608de58302c6c8def4e5619ec0d91cf9354475972bfe0d7575602aaf0e9e885c6baf9d567b1fcb63190cb792f1d871611adb4f3d1ed328026918e28d2fd45ab9d370e7292ed754ce29fb787fd5d09e6d47cbc80021abf7d2efebdbe0add870168f51dcc40957a4a3c8e192601383b7684136dca282623f31ba7ae5366b453c6a26f68a14db6559bcb10237379a27a9502ff9d64a338320751530f1f892a6d46f50311b5e18f0336fc477215666e188933c6939989f6e6a3adba22996aae6a0fe1bddcbe1496d968de093df44a3300f7515a12c9d82226d6b4d62c46a213d5f0107106fd2a22d3b598613db491f70ccb1f03b8659669ed74434e43b771f22780710fbd8f2f20e9897df5cc23548779c6c0830839d231c3ff9ac54407dfdfcc59014bf67d9685706a5622e38f7a933c34afbb6aa8cdfd93bd2ec91ad37904ce13b8ab8ef772366fad3c3ebee8f2611ee7b6c2af7e653efbec4dc4cbf13acf37e399e2b0b05b61cb7e17b15627b62962e2100b195fefe94bc484e88139700737de1a5ec7d9cc85d533b61ecad8653cedbb771f675af61e4c6f7efaacc9f7e424c16715b0a98c446059a271a27bd569d1b5dbfae8f53898524cae87059ff34fb2a533226bd29a0ba6f8d0836fd0a4c0d609a72e10539a44f8c39f6279be396e41ca9f29a28ce9fa0dd51a302e770e1e3db706a34cb904ef08d9c82c55bc728c955b120bb2ec373fcffff3c46d603ab387896d49cd21b2f77ecfbbb02a5e153b171afed986c15da6f2d4cf745d1995f5136e1b3e68a67a8996fe765616d8aa11bcd9f8b591db87efcdaaffd41003ec729360542620854fb04b80cb861a636a4717d666894c32f1f2bf2247cc415de1d0c4e712b958842d6a4b276dea5db88423f2b4c664b1d2b1877baf33747343614e5ebe9b7e12ed0153f9ca7458e4da497639dff1352ff0efae01d140321c28dd0b67b069890f6130f8246ab8544717532d3a5f63639a99d7f8e9831c64851b7ef6893b3c9740f9844d18a613b5f9a6ab4bd6e6a93e8e4bea5575d2cb4330c0af8558319a909a5988a992e406343dd1c742d64cd4a17a2f3795e8db4d30ccdf4415655eda7b437e3397323896b11b1bed72d63e310aa49671d85534f6dbc181febb5bdc08ac0d123829d108cd269f3b0a396f4241e7dda72f54862bedef01c12e3c6cfdf75f676c2ddef91af7f8a8a769c25e177cd430bede74b57690519a98db1fb5c361280f7540ac827a91b2d08752decfb7156fcdb617578b053eee41f66a60e045c3a569f3f7edb7631682fde9ef91ea8811fc2c78f646af6b4710edbb8bf2328bd3273a2cb72ffcca7c217b827192dd2ea929e976d131c9d202ec506a35d93ab216f64bd73fe5d8abae4571f85beb84a7f93a3de37a451f308f7de6ccd1a6eefef24699f2158d1261fe25182b502da3e74611a6116fc3064fa723c5a81adc0a32f1ed6299157d1c11c0ad99041894696301d5b3f1bf43205d7dccfa68bbb4a1f5e242b3e690bfc97b94366a343f5dd16df67b2ed2be21c747118872b462ee20c778ced856fa980403fb24b786137d0ef0278539b00ce6e23c07ef2a07cb24c51c5b485e454edf661db4b931ab8cb494eb394fd13c1b32085f27b204a4b87ee6c806345d7584cb161006ad9848a24a22a5771e3a2ab65463f553d52cd535ef10bdd40d8877372a532e3731b0ee90c04e8e43747cc3eb96bb879bd94d7012af46a93ba177037f062744d3fdfccd36aabe0f8ccca19dcf7841b1ee2f4feb1800e75441c51e95cce94ceeecd8587fbf574308dd763631b7335783091f4c8b3c8fb3cd93970cef0eda4ca08447568239c02fe8f675e15c49b5121b100cc19fcc2b2913df74f758f70bd6eeb7339516e5f1eff9700f8ee130e5c84ced7b1ced66be9a05596be8e55f6d9fdf7cf0fa62290ec670b6bdd6738bb5cfb341ef5ffb42bc2abc508ff231248f2c2dc15770d33722b9c9dae",
+			0, 0x83, 391892287957268, "ac65",
+			"e0b05b71fb79f49844dab54f1a90ebd31b4531daf005ae352a7f2074049e6561",
+		);
+	}
 }
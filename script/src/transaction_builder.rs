@@ -0,0 +1,207 @@
+//! Builder of transparent transactions.
+
+use bytes::Bytes;
+use chain::{Transaction, TransactionInput, TransactionOutput, OutPoint};
+use chain::constants::SEQUENCE_FINAL;
+use keys::{Address, Network};
+use ser::Serializable;
+use build::pay_to_address;
+use Script;
+
+/// Estimated size of P2PKH `script_sig`: DER signature with sighash byte and compressed public key.
+const P2PKH_SCRIPT_SIG_SIZE: usize = 107;
+
+/// Fee rate used by standard nodes to find dust outputs, in satoshis per 1000 bytes.
+const DUST_RELAY_FEE: u64 = 3000;
+
+/// Errors returned by `TransactionBuilder::consolidate`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BuilderError {
+	/// No outputs to spend.
+	NoInputs,
+	/// Destination address doesn't belong to the network.
+	NetworkMismatch,
+	/// No script pubkey can pay to the destination address.
+	UnsupportedAddress,
+	/// Sum of spent amounts or the fee overflows.
+	AmountOverflow,
+	/// Spent amounts don't cover the fee.
+	InsufficientFunds,
+	/// Remaining value is below the dust threshold, contains the value.
+	Dust(u64),
+}
+
+/// Builds non-Zcash transactions, leaving all shielded and overwinter fields empty.
+#[derive(Debug, Clone)]
+pub struct TransactionBuilder {
+	transaction: Transaction,
+}
+
+impl Default for TransactionBuilder {
+	fn default() -> Self {
+		TransactionBuilder::new()
+	}
+}
+
+impl TransactionBuilder {
+	/// Creates builder of version 1 transaction without inputs and outputs.
+	pub fn new() -> Self {
+		TransactionBuilder {
+			transaction: Transaction {
+				version: 1,
+				..Default::default()
+			},
+		}
+	}
+
+	pub fn version(mut self, version: i32) -> Self {
+		self.transaction.version = version;
+		self
+	}
+
+	pub fn add_input(mut self, previous_output: OutPoint, script_sig: Bytes, sequence: u32) -> Self {
+		self.transaction.inputs.push(TransactionInput {
+			previous_output,
+			script_sig,
+			sequence,
+			script_witness: vec![],
+		});
+		self
+	}
+
+	pub fn add_output(mut self, value: u64, script_pubkey: Bytes) -> Self {
+		self.transaction.outputs.push(TransactionOutput {
+			value,
+			script_pubkey,
+		});
+		self
+	}
+
+	pub fn lock_time(mut self, lock_time: u32) -> Self {
+		self.transaction.lock_time = lock_time;
+		self
+	}
+
+	pub fn build(self) -> Transaction {
+		self.transaction
+	}
+
+	/// Builds unsigned transaction spending all `utxos` to the single `destination` output.
+	/// The fee is `fee_per_vbyte` multiplied by the virtual size estimated for signed P2PKH inputs.
+	pub fn consolidate(utxos: &[(OutPoint, u64)], destination: &Address, fee_per_vbyte: u64, network: &Network) -> Result<Transaction, BuilderError> {
+		if utxos.is_empty() {
+			return Err(BuilderError::NoInputs);
+		}
+
+		if !is_network_address(destination, network) {
+			return Err(BuilderError::NetworkMismatch);
+		}
+
+		let script_pubkey = pay_to_address(destination).map_err(|_| BuilderError::UnsupportedAddress)?;
+		let dust = dust_threshold(&script_pubkey);
+		let total = utxos.iter()
+			.try_fold(0u64, |acc, &(_, value)| acc.checked_add(value))
+			.ok_or(BuilderError::AmountOverflow)?;
+
+		let mut transaction = utxos.iter()
+			.fold(TransactionBuilder::new(), |builder, (outpoint, _)| builder.add_input(outpoint.clone(), Bytes::new(), SEQUENCE_FINAL))
+			.add_output(0, script_pubkey.into())
+			.build();
+
+		let vsize = transaction.serialized_size() + utxos.len() * P2PKH_SCRIPT_SIG_SIZE;
+		let fee = (vsize as u64).checked_mul(fee_per_vbyte).ok_or(BuilderError::AmountOverflow)?;
+		let value = total.checked_sub(fee).ok_or(BuilderError::InsufficientFunds)?;
+		if value < dust {
+			return Err(BuilderError::Dust(value));
+		}
+
+		transaction.outputs[0].value = value;
+		Ok(transaction)
+	}
+}
+
+/// Minimal value of output with the script pubkey which is not considered dust by standard nodes:
+/// the fee of spending it at the dust relay fee rate.
+pub fn dust_threshold(script_pubkey: &Script) -> u64 {
+	let output = TransactionOutput {
+		value: 0,
+		script_pubkey: script_pubkey.to_bytes(),
+	};
+
+	// outpoint, script sig length, estimated script sig and sequence; witness data is discounted
+	let input_size = match script_pubkey.parse_witness_program() {
+		Some(_) => 32 + 4 + 1 + P2PKH_SCRIPT_SIG_SIZE / 4 + 4,
+		None => 32 + 4 + 1 + P2PKH_SCRIPT_SIG_SIZE + 4,
+	};
+
+	(output.serialized_size() + input_size) as u64 * DUST_RELAY_FEE / 1000
+}
+
+/// Returns true if the `address` belongs to the `network`.
+fn is_network_address(address: &Address, network: &Network) -> bool {
+	if let Some(ref witness) = address.witness {
+		return network.bech32_hrp() == Some(witness.hrp.as_str());
+	}
+
+	address.t_addr_prefix == network.t_addr_prefix() &&
+		address.checksum_type == network.checksum_type() &&
+		(address.prefix == network.p2pkh_prefix() || address.prefix == network.p2sh_prefix())
+}
+
+#[cfg(test)]
+mod tests {
+	use chain::{Transaction, OutPoint};
+	use hash::H256;
+	use keys::{Address, Network};
+	use Script;
+	use super::{TransactionBuilder, BuilderError, dust_threshold};
+
+	// real transaction from block 80000
+	#[test]
+	fn test_transaction_builder() {
+		let expected: Transaction = "0100000001a6b97044d03da79c005b20ea9c0e1a6d9dc12d9f7b91a5911c9030a439eed8f5000000004948304502206e21798a42fae0e854281abd38bacd1aeed3ee3738d9e1446618c4571d1090db022100e2ac980643b0b82c0e88ffdfec6b64e3e6ba35e7ba5fdd7d5d6cc8d25c6b241501ffffffff0100f2052a010000001976a914404371705fa9bd789a2fcd52d2c580b65d35549d88ac00000000".into();
+		let previous_output = OutPoint {
+			hash: H256::from_reversed_str("f5d8ee39a430901c91a5917b9f2dc19d6d1a0e9cea205b009ca73dd04470b9a6"),
+			index: 0,
+		};
+
+		let t = TransactionBuilder::new()
+			.version(1)
+			.add_input(previous_output, "48304502206e21798a42fae0e854281abd38bacd1aeed3ee3738d9e1446618c4571d1090db022100e2ac980643b0b82c0e88ffdfec6b64e3e6ba35e7ba5fdd7d5d6cc8d25c6b241501".into(), 0xffff_ffff)
+			.add_output(5_000_000_000, "76a914404371705fa9bd789a2fcd52d2c580b65d35549d88ac".into())
+			.lock_time(0)
+			.build();
+
+		assert_eq!(t, expected);
+		assert_eq!(t.hash(), H256::from_reversed_str("5a4ebf66822b0b2d56bd9dc64ece0bc38ee7844a23ff1d7320a88c5fdb2ad3e2"));
+	}
+
+	#[test]
+	fn test_transaction_builder_consolidate() {
+		let destination: Address = "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH".parse().unwrap();
+		let utxos: Vec<(OutPoint, u64)> = (0..5u8)
+			.map(|i| (OutPoint { hash: H256::from(i + 1), index: i as u32 }, 100_000))
+			.collect();
+
+		let t = TransactionBuilder::consolidate(&utxos, &destination, 2, &Network::Mainnet).unwrap();
+		assert_eq!(t.inputs.len(), 5);
+		assert_eq!(t.inputs[4].previous_output, utxos[4].0);
+		assert_eq!(t.outputs.len(), 1);
+		// 784 estimated vbytes: 10 overhead, 5 * 148 per input and 34 for the output
+		assert_eq!(t.outputs[0].value, 500_000 - 784 * 2);
+		assert_eq!(t.outputs[0].script_pubkey, "76a914751e76e8199196d454941c45d1b3a323f1433bd688ac".into());
+
+		assert_eq!(TransactionBuilder::consolidate(&utxos[..1], &destination, 519, &Network::Mainnet), Err(BuilderError::Dust(100_000 - 192 * 519)));
+		assert_eq!(TransactionBuilder::consolidate(&utxos[..1], &destination, 1000, &Network::Mainnet), Err(BuilderError::InsufficientFunds));
+		assert_eq!(TransactionBuilder::consolidate(&utxos, &destination, 2, &Network::Testnet), Err(BuilderError::NetworkMismatch));
+		assert_eq!(TransactionBuilder::consolidate(&[], &destination, 2, &Network::Mainnet), Err(BuilderError::NoInputs));
+	}
+
+	#[test]
+	fn test_dust_threshold() {
+		assert_eq!(dust_threshold(&Script::from("76a914751e76e8199196d454941c45d1b3a323f1433bd688ac")), 546);
+		assert_eq!(dust_threshold(&Script::from("a914fef59ae800bb89050d25f67be432b231097e184987")), 540);
+		assert_eq!(dust_threshold(&Script::from("0014751e76e8199196d454941c45d1b3a323f1433bd6")), 294);
+		assert_eq!(dust_threshold(&Script::from("00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262")), 330);
+	}
+}
@@ -6,6 +6,10 @@ use hash::{H32, H48, H64, H96, H160, H256, H264, H512, H520, OutCipherText, EncC
 use compact_integer::CompactInteger;
 use {Serializable, Stream, Deserializable, Reader, Error};
 
+/// Limit of generic length-prefixed `Bytes`. Transaction scripts are read with
+/// the larger `MAX_SCRIPT_BYTES` instead.
+const MAX_BYTES_LEN: usize = 10_000;
+
 impl Serializable for bool {
 	#[inline]
 	fn serialize(&self, s: &mut Stream) {
@@ -234,15 +238,9 @@ impl Serializable for Bytes {
 
 impl Deserializable for Bytes {
 	fn deserialize<T>(reader: &mut Reader<T>) -> Result<Self, Error> where T: io::Read {
-		let len = try!(reader.read::<CompactInteger>());
-		// limit the len to 10000 (MAX_SCRIPT_SIZE) as invalid inputs might result to huge
-		// len being calculated causing attempt to allocate the huge amount of memory and then crash
-		if u64::from(len) > 10000 {
-			return Err(Error::MalformedData);
-		}
-		let mut bytes = Bytes::new_with_len(len.into());
-		try!(reader.read_slice(&mut bytes));
-		Ok(bytes)
+		// invalid inputs might result to huge len being calculated causing attempt
+		// to allocate the huge amount of memory and then crash
+		reader.read_bytes_max(MAX_BYTES_LEN)
 	}
 }
 
@@ -282,6 +280,17 @@ mod tests {
 		assert_eq!(Error::UnexpectedEnd, reader.read::<u8>().unwrap_err());
 	}
 
+	#[test]
+	fn test_reader_read_list_huge_length() {
+		// 0xffffffff elements declared, only one present
+		let buffer = vec![0xfe, 0xff, 0xff, 0xff, 0xff, 1, 0];
+		let mut reader = Reader::new(&buffer);
+		assert_eq!(Error::UnexpectedEnd, reader.read_list::<u16>().unwrap_err());
+
+		let mut reader = Reader::new(&buffer);
+		assert_eq!(Error::MalformedData, reader.read_list_max::<u16>(1024).unwrap_err());
+	}
+
 	#[test]
 	fn test_reader_iterator() {
 		let buffer = vec![
@@ -322,6 +331,13 @@ mod tests {
 		assert_eq!(expected, deserialize(raw.as_ref()).unwrap());
 	}
 
+	#[test]
+	fn test_bytes_deserialize_max_len() {
+		// 10001 bytes declared
+		let raw: Bytes = "fd1127".into();
+		assert_eq!(Error::MalformedData, deserialize::<_, Bytes>(raw.as_ref()).unwrap_err());
+	}
+
 	#[test]
 	fn test_bytes_serialize() {
 		let expected: Bytes = "020145".into();
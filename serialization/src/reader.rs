@@ -1,6 +1,12 @@
-use std::{io, marker};
+use std::{cmp, error, fmt, io, marker, str};
+use bytes::Bytes;
 use compact_integer::CompactInteger;
 
+/// Maximal number of list elements allocated before they are read.
+const MAX_PREALLOCATED_LIST_SIZE: usize = 1024;
+/// Default limit of length-prefixed scripts and strings, 100 KB.
+pub const MAX_SCRIPT_BYTES: usize = 100_000;
+
 pub fn deserialize<R, T>(buffer: R) -> Result<T, Error> where R: io::Read, T: Deserializable {
 	let mut reader = Reader::from_read(buffer);
 	let result = try!(reader.read());
@@ -24,15 +30,34 @@ pub enum Error {
 	MalformedData,
 	UnexpectedEnd,
 	UnreadData,
+	TooLarge,
 	Custom(String),
 }
 
 impl From<io::Error> for Error {
-	fn from(_: io::Error) -> Self {
-		Error::UnexpectedEnd
+	fn from(e: io::Error) -> Self {
+		let budget_exceeded = e.get_ref().is_some_and(|inner| inner.is::<BudgetExceeded>());
+		if budget_exceeded {
+			Error::MalformedData
+		} else {
+			Error::UnexpectedEnd
+		}
 	}
 }
 
+/// Returned by the `Reader` wrapped in `io::Error` when the read budget is exceeded,
+/// to tell it apart from errors of the underlying reader.
+#[derive(Debug)]
+struct BudgetExceeded;
+
+impl fmt::Display for BudgetExceeded {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		"read budget exceeded".fmt(f)
+	}
+}
+
+impl error::Error for BudgetExceeded {}
+
 pub trait Deserializable {
 	fn deserialize<T>(reader: &mut Reader<T>) -> Result<Self, Error> where Self: Sized, T: io::Read;
 }
@@ -42,6 +67,8 @@ pub trait Deserializable {
 pub struct Reader<T> {
 	buffer: T,
 	peeked: Option<u8>,
+	/// Number of bytes which can still be read, if limited.
+	budget: Option<usize>,
 }
 
 impl<'a> Reader<&'a [u8]> {
@@ -50,26 +77,39 @@ impl<'a> Reader<&'a [u8]> {
 		Reader {
 			buffer: buffer,
 			peeked: None,
+			budget: None,
 		}
 	}
+
+	/// Number of bytes which were not read yet.
+	/// Allows callers to detect coin-specific data trailing the parsed structure.
+	pub fn remaining_bytes(&self) -> usize {
+		self.buffer.len() + self.peeked.is_some() as usize
+	}
 }
 
 impl<T> io::Read for Reader<T> where T: io::Read {
 	fn read(&mut self, buf: &mut [u8]) -> Result<usize, io::Error> {
-		// most of the times, there will be nothing in peeked,
-		// so to make it as efficient as possible, check it
-		// only once
-		match self.peeked.take() {
-			None => io::Read::read(&mut self.buffer, buf),
-			Some(peeked) if buf.is_empty() => {
-				self.peeked = Some(peeked);
-				Ok(0)
-			},
-			Some(peeked) => {
-				buf[0] = peeked;
-				io::Read::read(&mut self.buffer, &mut buf[1..]).map(|x| x + 1)
-			},
+		let limit = match self.budget {
+			Some(budget) => cmp::min(budget, buf.len()),
+			None => buf.len(),
+		};
+
+		if limit == 0 && !buf.is_empty() {
+			// budget is exhausted, which is an error only if there is more data
+			let mut probe = [0u8];
+			if self.read_unbounded(&mut probe)? == 0 {
+				return Ok(0);
+			}
+			self.peeked = Some(probe[0]);
+			return Err(io::Error::new(io::ErrorKind::InvalidData, BudgetExceeded));
+		}
+
+		let len = self.read_unbounded(&mut buf[..limit])?;
+		if let Some(ref mut budget) = self.budget {
+			*budget -= len;
 		}
+		Ok(len)
 	}
 }
 
@@ -78,9 +118,17 @@ impl<R> Reader<R> where R: io::Read {
 		Reader {
 			buffer: read,
 			peeked: None,
+			budget: None,
 		}
 	}
 
+	/// Limits total number of bytes which can be read.
+	/// Reading past the limit fails with `Error::MalformedData`.
+	pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+		self.budget = Some(max_bytes);
+		self
+	}
+
 	pub fn read<T>(&mut self) -> Result<T, Error> where T: Deserializable {
 		T::deserialize(self)
 	}
@@ -91,12 +139,13 @@ impl<R> Reader<R> where R: io::Read {
 	}
 
 	pub fn read_slice(&mut self, bytes: &mut [u8]) -> Result<(), Error> {
-		io::Read::read_exact(self, bytes).map_err(|_| Error::UnexpectedEnd)
+		io::Read::read_exact(self, bytes).map_err(Error::from)
 	}
 
 	pub fn read_list<T>(&mut self) -> Result<Vec<T>, Error> where T: Deserializable {
 		let len: usize = try!(self.read::<CompactInteger>()).into();
-		let mut result = Vec::with_capacity(len);
+		// declared length is not trusted, the list grows as elements are actually read
+		let mut result = Vec::with_capacity(cmp::min(len, MAX_PREALLOCATED_LIST_SIZE));
 
 		for _ in 0..len {
 			result.push(try!(self.read()));
@@ -120,6 +169,25 @@ impl<R> Reader<R> where R: io::Read {
 		Ok(result)
 	}
 
+	/// Reads length-prefixed bytes. Fails with `Error::MalformedData`
+	/// if the declared length exceeds `max`, before anything is allocated.
+	pub fn read_bytes_max(&mut self, max: usize) -> Result<Bytes, Error> {
+		let len: usize = self.read::<CompactInteger>()?.into();
+		if len > max {
+			return Err(Error::MalformedData);
+		}
+
+		let mut bytes = Bytes::new_with_len(len);
+		self.read_slice(&mut bytes)?;
+		Ok(bytes)
+	}
+
+	/// Reads length-prefixed UTF-8 string, limited to `max` bytes.
+	pub fn read_string_max(&mut self, max: usize) -> Result<String, Error> {
+		let bytes = self.read_bytes_max(max)?;
+		str::from_utf8(&bytes).map(Into::into).map_err(|_| Error::MalformedData)
+	}
+
 	#[cfg_attr(feature="cargo-clippy", allow(wrong_self_convention))]
 	pub fn is_finished(&mut self) -> bool {
 		if self.peeked.is_some() {
@@ -130,9 +198,31 @@ impl<R> Reader<R> where R: io::Read {
 		match self.read_slice(peek) {
 			Ok(_) => {
 				self.peeked = Some(peek[0]);
+				// peeked byte is not consumed yet
+				if let Some(ref mut budget) = self.budget {
+					*budget += 1;
+				}
 				false
 			},
-			Err(_) => true,
+			// if the budget is exceeded, next byte is already peeked
+			Err(_) => self.peeked.is_none(),
+		}
+	}
+
+	fn read_unbounded(&mut self, buf: &mut [u8]) -> Result<usize, io::Error> {
+		// most of the times, there will be nothing in peeked,
+		// so to make it as efficient as possible, check it
+		// only once
+		match self.peeked.take() {
+			None => io::Read::read(&mut self.buffer, buf),
+			Some(peeked) if buf.is_empty() => {
+				self.peeked = Some(peeked);
+				Ok(0)
+			},
+			Some(peeked) => {
+				buf[0] = peeked;
+				io::Read::read(&mut self.buffer, &mut buf[1..]).map(|x| x + 1)
+			},
 		}
 	}
 }
@@ -177,3 +267,47 @@ impl<F, T> io::Read for Proxy<F, T> where F: io::Read, T: FnMut(&[u8]) {
 		Ok(len)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::io;
+	use super::{Reader, Error};
+
+	struct InvalidDataRead;
+
+	impl io::Read for InvalidDataRead {
+		fn read(&mut self, _: &mut [u8]) -> Result<usize, io::Error> {
+			Err(io::Error::new(io::ErrorKind::InvalidData, "invalid data"))
+		}
+	}
+
+	#[test]
+	fn test_reader_max_bytes() {
+		let buffer = [1, 0, 0, 0, 2];
+
+		let mut reader = Reader::new(&buffer).with_max_bytes(5);
+		assert_eq!(reader.read::<u32>(), Ok(1));
+		assert_eq!(reader.read::<u8>(), Ok(2));
+		assert!(reader.is_finished());
+
+		let mut reader = Reader::new(&buffer).with_max_bytes(4);
+		assert_eq!(reader.read::<u32>(), Ok(1));
+		assert!(!reader.is_finished());
+		assert_eq!(reader.read::<u8>(), Err(Error::MalformedData));
+
+		assert_eq!(Reader::new(&buffer).with_max_bytes(3).read::<u32>(), Err(Error::MalformedData));
+	}
+
+	#[test]
+	fn test_reader_max_bytes_unexpected_end() {
+		let buffer = [1, 0];
+		assert_eq!(Reader::new(&buffer).with_max_bytes(4).read::<u32>(), Err(Error::UnexpectedEnd));
+		assert_eq!(Reader::new(&buffer).with_max_bytes(1).read::<u32>(), Err(Error::MalformedData));
+	}
+
+	#[test]
+	fn test_reader_invalid_data_of_underlying_reader() {
+		assert_eq!(Reader::from_read(InvalidDataRead).read::<u8>(), Err(Error::UnexpectedEnd));
+		assert_eq!(Reader::from_read(InvalidDataRead).with_max_bytes(10).read::<u8>(), Err(Error::UnexpectedEnd));
+	}
+}